@@ -32,6 +32,34 @@ fn criterion_benchmark(c: &mut Criterion) {
         });
     }
 
+    {
+        let ranges = (0..100)
+            .map(|i| {
+                let min = Char::from_u32(0x100 + i * 7).unwrap();
+                let max = Char::from_u32(0x100 + i * 7 + 3).unwrap();
+                RangeSet::new_from_range(min..=max)
+            })
+            .collect::<Vec<_>>();
+        c.bench_function("to_regex_miss_direct", |b| {
+            b.iter(|| {
+                for range in &ranges {
+                    range.to_regex();
+                }
+            })
+        });
+    }
+
+    {
+        let range6 = RangeSet::<Char>::from_regex("\\p{Greek}").unwrap();
+        let range7 = range6.union(&RangeSet::new_from_range_char('a'..='a'));
+        c.bench_function("to_regex_long_class", |b| {
+            b.iter(|| {
+                range6.to_regex();
+                range7.to_regex();
+            })
+        });
+    }
+
     {
         c.bench_function("get_cardinality", |b| {
             b.iter(|| {
@@ -39,6 +67,48 @@ fn criterion_benchmark(c: &mut Criterion) {
             })
         });
     }
+
+    {
+        let regex = "[a-z0-9A-F_\\-\\u{e9}[:punct:]\\p{Greek}&&[^q]]";
+        c.bench_function("from_regex", |b| {
+            b.iter(|| {
+                RangeSet::<Char>::from_regex(regex).unwrap();
+            })
+        });
+    }
+
+    {
+        let classes = (0..100)
+            .map(|i| {
+                let min = Char::from_u32(0x100 + i * 7).unwrap();
+                let max = Char::from_u32(0x100 + i * 7 + 3).unwrap();
+                RangeSet::new_from_range(min..=max)
+            })
+            .collect::<Vec<_>>();
+        c.bench_function("union_all", |b| {
+            b.iter(|| {
+                classes
+                    .iter()
+                    .fold(RangeSet::empty(), |acc, class| acc.union(class));
+            })
+        });
+    }
+
+    {
+        let ranges = (0..500)
+            .map(|i| {
+                let min = Char::from_u32(0x1000 + i * 4).unwrap();
+                let max = Char::from_u32(0x1000 + i * 4 + 1).unwrap();
+                AnyRange::from(min..=max)
+            })
+            .collect::<Vec<_>>();
+        let range5 = RangeSet::new_from_ranges(&ranges);
+        c.bench_function("to_regex_500_ranges", |b| {
+            b.iter(|| {
+                range5.to_regex();
+            })
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
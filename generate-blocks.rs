@@ -0,0 +1,97 @@
+//! Generate `src/tokens/unicode/block.rs` from the `Blocks.txt` file of a Unicode Character Database directory,
+//! in the layout of `ucd-generate`, which has no command for the blocks.
+//!
+//! rustc -O generate-blocks.rs -o /tmp/generate-blocks && /tmp/generate-blocks /tmp/ucd-16.0.0
+
+use std::{env, fs, process};
+
+/// The maximum width of a line of entries, as in `ucd-generate`.
+const WIDTH: usize = 78;
+
+fn main() {
+    let Some(dir) = env::args().nth(1) else {
+        eprintln!("usage: generate-blocks <ucd-dir>");
+        process::exit(1);
+    };
+    let path = format!("{}/Blocks.txt", dir.trim_end_matches('/'));
+    let data = fs::read_to_string(&path).unwrap_or_else(|err| {
+        eprintln!("{}: {}", path, err);
+        process::exit(1);
+    });
+
+    // The first line is `# Blocks-16.0.0.txt`.
+    let version = data
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("# Blocks-"))
+        .and_then(|line| line.strip_suffix(".txt"))
+        .expect("missing version");
+
+    let mut blocks = vec![];
+    for line in data.lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (range, name) = line.split_once(';').expect("invalid line");
+        let (min, max) = range.trim().split_once("..").expect("invalid range");
+        let min = u32::from_str_radix(min, 16).expect("invalid code point");
+        let max = u32::from_str_radix(max, 16).expect("invalid code point");
+        // The surrogate blocks hold no `char`.
+        let (Some(min), Some(max)) = (char::from_u32(min), char::from_u32(max)) else {
+            continue;
+        };
+        blocks.push((to_name(name.trim()), min, max));
+    }
+    blocks.sort();
+
+    println!("// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:");
+    println!("//");
+    println!("//   generate-blocks {}", dir);
+    println!("//");
+    println!("// Unicode version: {}.", version);
+    println!("//");
+    println!("// generate-blocks.rs is run by generate-classes.sh.");
+    println!();
+    println!("pub const BY_NAME: &'static [(&'static str, &'static [(char, char)])] = &[");
+    let entries = blocks
+        .iter()
+        .map(|(name, _, _)| format!("(\"{}\", {}),", name, name.to_uppercase()))
+        .collect::<Vec<_>>();
+    println!("{}", wrap(&entries));
+    println!("];");
+    for (name, min, max) in &blocks {
+        println!();
+        println!("pub const {}: &'static [(char, char)] = &[", name.to_uppercase());
+        println!("{}", wrap(&[format!("({:?}, {:?}),", min, max)]));
+        println!("];");
+    }
+}
+
+/// Write the name with its words capitalized and joined by `_`, as in `Greek_And_Coptic`.
+fn to_name(name: &str) -> String {
+    name.split([' ', '-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Join the entries into lines indented by two spaces, as long as they fit in `WIDTH` bytes.
+fn wrap(entries: &[String]) -> String {
+    let mut lines: Vec<String> = vec![];
+    for entry in entries {
+        match lines.last_mut() {
+            Some(line) if line.len() + 1 + entry.len() <= WIDTH => {
+                line.push(' ');
+                line.push_str(entry);
+            }
+            _ => lines.push(format!("  {}", entry)),
+        }
+    }
+    lines.join("\n")
+}
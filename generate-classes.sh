@@ -1,8 +1,12 @@
 #!/bin/bash
 
+ucd-generate case-folding-simple /tmp/ucd-16.0.0 --chars --all-pairs > src/tokens/unicode/case_folding_simple.rs
 ucd-generate general-category /tmp/ucd-16.0.0 --chars --exclude surrogate > src/tokens/unicode/general_category.rs
 ucd-generate general-category /tmp/ucd-16.0.0 --chars --include decimalnumber > src/tokens/unicode/perl_decimal.rs
 ucd-generate property-bool /tmp/ucd-16.0.0 --chars --include whitespace > src/tokens/unicode/perl_space.rs
 ucd-generate perl-word /tmp/ucd-16.0.0 --chars > src/tokens/unicode/perl_word.rs
-ucd-generate property-bool /tmp/ucd-16.0.0 --chars > src/tokens/unicode/property_bool.rs
-ucd-generate script /tmp/ucd-16.0.0 --chars > src/tokens/unicode/script.rs
\ No newline at end of file
+ucd-generate property-bool /tmp/ucd-16.0.0 --chars --exclude emoji,emojicomponent,emojimodifier,emojimodifierbase,emojipresentation,extendedpictographic > src/tokens/unicode/property_bool.rs
+ucd-generate property-bool /tmp/ucd-16.0.0 --chars --include emoji,emojicomponent,emojimodifier,emojimodifierbase,emojipresentation,extendedpictographic > src/tokens/unicode/emoji.rs
+ucd-generate script /tmp/ucd-16.0.0 --chars > src/tokens/unicode/script.rs
+ucd-generate script-extension /tmp/ucd-16.0.0 --chars > src/tokens/unicode/script_extension.rs
+rustc -O generate-blocks.rs -o /tmp/generate-blocks && /tmp/generate-blocks /tmp/ucd-16.0.0 > src/tokens/unicode/block.rs
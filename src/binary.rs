@@ -0,0 +1,104 @@
+use irange::{range::AnyRange, RangeSet};
+
+use crate::{char::Char, emit};
+
+/// Encode the coalesced ranges as pairs of LEB128 varints: the gap since the end of the previous range
+/// (or since U+0000 for the first one) and the length of the range minus one.
+pub(crate) fn to_bytes(range: &RangeSet<Char>) -> Vec<u8> {
+    let range = emit::normalize(range);
+    let mut bytes = Vec::with_capacity(range.0.len() * 2);
+    let mut next = 0;
+    for bounds in range.0.chunks_exact(2) {
+        let (min, max) = (bounds[0].to_u32(), bounds[1].to_u32());
+        write_varint(&mut bytes, min - next);
+        write_varint(&mut bytes, max - min);
+        next = max + 1;
+    }
+    bytes
+}
+
+/// Decode ranges written by `to_bytes`, return `None` if the bytes are truncated or hold invalid `char` codes.
+pub(crate) fn from_bytes(bytes: &[u8]) -> Option<RangeSet<Char>> {
+    let mut ranges = vec![];
+    let mut next: u32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let min = next.checked_add(read_varint(bytes, &mut i)?)?;
+        let max = min.checked_add(read_varint(bytes, &mut i)?)?;
+        ranges.push(AnyRange::from(Char::from_u32(min)?..=Char::from_u32(max)?));
+        next = max.checked_add(1)?;
+    }
+    Some(RangeSet::new_from_ranges(&ranges))
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], i: &mut usize) -> Option<u32> {
+    let mut value: u32 = 0;
+    for shift in (0..32).step_by(7) {
+        let byte = *bytes.get(*i)?;
+        *i += 1;
+        // The fifth byte only holds the 4 upper bits.
+        if shift == 28 && byte > 0x0F {
+            return None;
+        }
+        value |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-scripts"))]
+    #[test]
+    fn test_round_trip() -> Result<(), String> {
+        use crate::CharacterClass;
+
+        assert!(to_bytes(&RangeSet::empty()).is_empty());
+        assert_eq!(Some(RangeSet::empty()), from_bytes(&[]));
+
+        let total = RangeSet::total();
+        assert_eq!(vec![0x00, 0xFF, 0xFF, 0x43], to_bytes(&total));
+        assert_eq!(Some(total), from_bytes(&to_bytes(&RangeSet::total())));
+
+        for regex in [
+            "[a-z]",
+            "\\d",
+            "\\w",
+            "\\p{Greek}",
+            "\\p{Lu}",
+            "\\P{Lu}",
+            "[\\u{D7FF}\\u{E000}\\u{10FFFF}]",
+        ] {
+            let range = RangeSet::<Char>::from_regex(regex).map_err(|err| err.to_string())?;
+            let bytes = to_bytes(&range);
+            assert!(bytes.len() <= range.0.len() * 3, "{}", regex);
+            assert_eq!(Some(range), from_bytes(&bytes), "{}", regex);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_bytes() -> Result<(), String> {
+        assert_eq!(None, from_bytes(&[0x61]));
+        assert_eq!(None, from_bytes(&[0x80]));
+        assert_eq!(None, from_bytes(&[0x80, 0xB0, 0x03, 0x00]));
+        assert_eq!(None, from_bytes(&[0x80, 0x80, 0xC4, 0x00, 0x00]));
+        assert_eq!(None, from_bytes(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0x00]));
+        assert_eq!(None, from_bytes(&[0x80, 0x80, 0x80, 0x80, 0x10, 0x00]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use irange::{range::AnyRange, RangeSet};
+
+use crate::char::{Char, INVALID_MIN, INVALID_SIZE};
+
+/// A builder accumulating `char` and ranges of `char` to create a `RangeSet<Char>` at once.
+///
+/// # Example:
+///
+/// ```
+/// use regex_charclass::{builder::CharClassBuilder, CharacterClass};
+///
+/// let range = CharClassBuilder::new()
+///     .add_char('x')
+///     .add_range('a', 'c')
+///     .build();
+/// assert_eq!("[a-cx]", range.to_regex());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CharClassBuilder {
+    ranges: Vec<(Char, Char)>,
+    negated: bool,
+}
+
+impl CharClassBuilder {
+    /// Create a new empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the given `char`.
+    #[inline]
+    pub fn add_char(&mut self, c: char) -> &mut Self {
+        self.ranges.push((Char::new(c), Char::new(c)));
+        self
+    }
+
+    /// Add the `char` from `min` to `max` included, nothing is added if `min` is greater than `max`.
+    #[inline]
+    pub fn add_range(&mut self, min: char, max: char) -> &mut Self {
+        if min <= max {
+            self.ranges.push((Char::new(min), Char::new(max)));
+        }
+        self
+    }
+
+    /// Add the valid `char` codes from `min` to `max` included, the surrogates and the codes above U+10FFFF are left out.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{builder::CharClassBuilder, CharacterClass};
+    ///
+    /// let range = CharClassBuilder::new().add_u32_range(0xD7FF, 0x11_0000).build();
+    /// assert_eq!("[\\u{d7ff}-\\u{10ffff}]", range.to_regex());
+    /// assert_eq!(0x10FFFF - 0xD7FF + 1 - 0x800, range.get_cardinality());
+    /// ```
+    pub fn add_u32_range(&mut self, min: u32, max: u32) -> &mut Self {
+        let surrogates = INVALID_MIN..INVALID_MIN + INVALID_SIZE;
+        let min = if surrogates.contains(&min) {
+            INVALID_MIN + INVALID_SIZE
+        } else {
+            min
+        };
+        let max = if surrogates.contains(&max) {
+            INVALID_MIN - 1
+        } else {
+            max.min(char::MAX as u32)
+        };
+        if let (Some(min), Some(max)) = (Char::from_u32(min), Char::from_u32(max)) {
+            if min <= max {
+                self.ranges.push((min, max));
+            }
+        }
+        self
+    }
+
+    /// Build the complement of the class instead, calling it again cancels it.
+    ///
+    /// It applies to everything added, before or after the call.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{builder::CharClassBuilder, CharacterClass};
+    ///
+    /// let range = CharClassBuilder::new().negate().add_range('a', 'z').build();
+    /// assert_eq!("[^a-z]", range.to_regex());
+    /// ```
+    #[inline]
+    pub fn negate(&mut self) -> &mut Self {
+        self.negated = !self.negated;
+        self
+    }
+
+    /// Create the `RangeSet<Char>` holding everything added so far, or its complement if negated.
+    pub fn build(&self) -> RangeSet<Char> {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|(min, max)| AnyRange::from(*min..=*max))
+            .collect::<Vec<_>>();
+        let range = RangeSet::new_from_ranges(&ranges);
+        if self.negated {
+            range.complement()
+        } else {
+            range
+        }
+    }
+}
+
+/// An operation recorded by `DebugClass`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClassOp {
+    AddChar(char),
+    AddRange(char, char),
+    AddU32Range(u32, u32),
+    Negate,
+}
+
+/// A `CharClassBuilder` recording every operation in the order they were made.
+///
+/// This is meant for diagnostic, to compare the original inputs with the coalesced class.
+///
+/// # Example:
+///
+/// ```
+/// use regex_charclass::{builder::{ClassOp, DebugClass}, CharacterClass};
+///
+/// let mut class = DebugClass::new();
+/// class.add_range('a', 'b').add_char('c');
+///
+/// assert_eq!(&[ClassOp::AddRange('a', 'b'), ClassOp::AddChar('c')], class.build_history());
+/// assert_eq!("[a-c]", class.build().to_regex());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DebugClass {
+    builder: CharClassBuilder,
+    history: Vec<ClassOp>,
+}
+
+impl DebugClass {
+    /// Create a new empty instance.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the given `char` and record it.
+    #[inline]
+    pub fn add_char(&mut self, c: char) -> &mut Self {
+        self.history.push(ClassOp::AddChar(c));
+        self.builder.add_char(c);
+        self
+    }
+
+    /// Add the `char` from `min` to `max` included and record it.
+    #[inline]
+    pub fn add_range(&mut self, min: char, max: char) -> &mut Self {
+        self.history.push(ClassOp::AddRange(min, max));
+        self.builder.add_range(min, max);
+        self
+    }
+
+    /// Add the valid `char` codes from `min` to `max` included and record it.
+    #[inline]
+    pub fn add_u32_range(&mut self, min: u32, max: u32) -> &mut Self {
+        self.history.push(ClassOp::AddU32Range(min, max));
+        self.builder.add_u32_range(min, max);
+        self
+    }
+
+    /// Build the complement of the class instead and record it.
+    #[inline]
+    pub fn negate(&mut self) -> &mut Self {
+        self.history.push(ClassOp::Negate);
+        self.builder.negate();
+        self
+    }
+
+    /// Return every operation made so far, in order.
+    #[inline]
+    pub fn build_history(&self) -> &[ClassOp] {
+        &self.history
+    }
+
+    /// Create the coalesced `RangeSet<Char>`.
+    #[inline]
+    pub fn build(&self) -> RangeSet<Char> {
+        self.builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharacterClass;
+
+    use super::*;
+
+    #[test]
+    fn test_builder() -> Result<(), String> {
+        assert!(CharClassBuilder::new().build().is_empty());
+
+        let range = CharClassBuilder::new()
+            .add_range('z', 'a')
+            .add_range('0', '9')
+            .add_char('5')
+            .add_char('\u{10FFFF}')
+            .build();
+        assert_eq!("[0-9\\u{10ffff}]", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_u32_range() -> Result<(), String> {
+        let range = CharClassBuilder::new()
+            .add_u32_range(0x61, 0x7A)
+            .add_u32_range(0xD800, 0xDFFF)
+            .add_u32_range(0x7A, 0x61)
+            .add_u32_range(0x11_0000, u32::MAX)
+            .build();
+        assert_eq!("[a-z]", range.to_regex());
+
+        let range = CharClassBuilder::new()
+            .add_u32_range(0xD900, 0xE001)
+            .add_u32_range(0, 0xD900)
+            .build();
+        assert_eq!(RangeSet::new_from_range_char('\0'..='\u{E001}'), range);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_negate() -> Result<(), String> {
+        let range = CharClassBuilder::new().negate().build();
+        assert_eq!(RangeSet::total(), range);
+
+        let range = CharClassBuilder::new()
+            .add_range('a', 'z')
+            .negate()
+            .add_char('0')
+            .build();
+        assert_eq!("[^0a-z]", range.to_regex());
+
+        let range = CharClassBuilder::new()
+            .negate()
+            .add_char('a')
+            .negate()
+            .build();
+        assert_eq!("a", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_class() -> Result<(), String> {
+        let mut class = DebugClass::new();
+        class.add_char('c').add_char('a').add_char('b');
+
+        assert_eq!(
+            &[
+                ClassOp::AddChar('c'),
+                ClassOp::AddChar('a'),
+                ClassOp::AddChar('b')
+            ],
+            class.build_history()
+        );
+
+        let range = class.build();
+        assert_eq!(vec![Char::new('a'), Char::new('c')], range.0);
+        assert_eq!("[a-c]", range.to_regex());
+
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 pub(super) static INVALID_MIN: u32 = 0xD800;
 pub(super) static INVALID_MAX: u32 = 0xDFFF;
 pub(super) static INVALID_SIZE: u32 = 0x800;
+pub(super) static VALID_SIZE: u32 = 0x110000 - INVALID_SIZE;
 
 /// A structure holding a `char` to use within a `RangeSet`.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, PartialOrd, Ord)]
@@ -0,0 +1,2525 @@
+use std::borrow::Cow;
+
+use irange::{integer::Bounded, range::AnyRange, RangeSet};
+
+use crate::{
+    char::{Char, INVALID_MIN, INVALID_SIZE},
+    options::{
+        CostModel, EmissionPlan, EmptyClassStyle, EscapeStyle, LossyError, RegexFlavor,
+        RegexOptions,
+    },
+    tokens::{self, identify_character},
+    CharacterClass,
+};
+
+pub(crate) fn to_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    if options.flavor == RegexFlavor::RustBytes {
+        return to_bytes_regex(range, options);
+    }
+    if options.ascii_only {
+        return to_ascii_regex(&normalize(range), options);
+    }
+    let max_char = get_max_char(options);
+    let mut range = normalize(range);
+    if max_char != Char::max_value() {
+        range = Cow::Owned(range.intersection(&RangeSet::new_from_range(..=max_char)));
+    }
+    let range = range.as_ref();
+    if options.quote_context {
+        return convert_to_quoted_regex(range, options);
+    }
+    if range.is_empty() {
+        match options.empty_as {
+            EmptyClassStyle::Brackets => String::from("[]"),
+            EmptyClassStyle::NegatedTotal => format!(
+                "[^{}-{}]",
+                escape_code_point('\0', options),
+                escape_code_point(max_char.to_char(), options)
+            ),
+        }
+    } else if range.0 == [Char::min_value(), max_char] {
+        if options.flavor == RegexFlavor::EcmaScript {
+            // JavaScript has no `s` flag before ES2018.
+            String::from("[\\s\\S]")
+        } else if options.flavor == RegexFlavor::Posix
+            || (options.assume_dotall && !options.always_bracket)
+        {
+            String::from(".")
+        } else {
+            format!(
+                "[{}-{}]",
+                escape_code_point('\0', options),
+                escape_code_point(max_char.to_char(), options)
+            )
+        }
+    } else if !options.assume_dotall
+        && !options.always_bracket
+        // `.` also excludes `\r` and the line separators in JavaScript, and matches `\n` in POSIX.
+        && !matches!(options.flavor, RegexFlavor::EcmaScript | RegexFlavor::Posix)
+        && *range == get_complement(&RangeSet::new_from_range_char('\n'..='\n'), options)
+    {
+        String::from(".")
+    } else if options.flavor == RegexFlavor::Posix {
+        convert_to_posix_regex(range, options)
+    } else if options.optimize_length {
+        convert_to_shortest_regex(range, options)
+    } else if let Some(token) = tokens::identify_class(range, options) {
+        wrap_in_brackets(token, options)
+    } else {
+        wrap_in_brackets(convert_to_regex(range, options), options)
+    }
+}
+
+pub(crate) fn to_anchored_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    // The anchored regex is used on its own, without the `s` flag.
+    let options = RegexOptions {
+        assume_dotall: false,
+        ..options.clone()
+    };
+    let regex = to_regex(range, &options);
+    if options.flavor == RegexFlavor::Pcre {
+        format!("\\A{}\\z", regex)
+    } else {
+        format!("^{}$", regex)
+    }
+}
+
+/// Write the given range with the syntax of the `regex` crate, enabling Unicode mode
+/// unless the class only matches ASCII bytes whatever the mode.
+fn to_bytes_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    let options = RegexOptions {
+        flavor: RegexFlavor::Rust,
+        ..options.clone()
+    };
+    enable_unicode_for_bytes(to_regex(range, &options), range)
+}
+
+/// Wrap the given regex of `range` in `(?u:...)` unless it only matches ASCII bytes whatever the mode.
+fn enable_unicode_for_bytes(regex: String, range: &RangeSet<Char>) -> String {
+    let range = normalize(range);
+    let is_ascii = range.0.last().is_some_and(|max| max.to_u32() < 0x80);
+    if is_ascii && !regex.contains("\\p") && !regex.contains("\\P") {
+        regex
+    } else {
+        format!("(?u:{})", regex)
+    }
+}
+
+/// Write the BMP part of the given range as a class and the astral part as UTF-16 surrogate pairs.
+fn to_ascii_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    // The classes are read by JavaScript as code units, where a negated class, `[\s\S]` or `.`
+    // would also match the surrogates on their own.
+    let bmp_options = RegexOptions {
+        ascii_only: false,
+        flavor: RegexFlavor::EcmaScript,
+        escape_style: EscapeStyle::JavaScriptUnicode,
+        allow_unicode_properties: false,
+        allow_negation: false,
+        max_code_point: Some(options.max_code_point.unwrap_or(0xFFFF).min(0xFFFF)),
+        ..options.clone()
+    };
+    let astral_min = Char::new('\u{10000}');
+    let astral = RangeSet::new_from_range(astral_min..=get_max_char(options)).intersection(range);
+    let bmp = RangeSet::new_from_range(..=get_max_char(&bmp_options)).intersection(range);
+    if bmp.is_empty() && astral.is_empty() {
+        return to_regex(&bmp, &bmp_options);
+    }
+
+    let mut alternatives = vec![];
+    if !bmp.is_empty() {
+        let regex = match tokens::identify_class(&bmp, &bmp_options) {
+            Some(token) => token,
+            None => write_class(&bmp, false, &bmp_options),
+        };
+        alternatives.push(wrap_in_brackets(regex, &bmp_options));
+    }
+    alternatives.extend(write_surrogate_pairs(&astral, options));
+
+    if alternatives.len() == 1 {
+        alternatives.remove(0)
+    } else {
+        format!("(?:{})", alternatives.join("|"))
+    }
+}
+
+/// An inclusive range of UTF-16 code units.
+type CodeUnits = (u32, u32);
+
+/// Write the given astral range as a list of high surrogates followed by low surrogates.
+fn write_surrogate_pairs(range: &RangeSet<Char>, options: &RegexOptions) -> Vec<String> {
+    // The range of high surrogates followed by the ranges of low surrogates.
+    let mut pairs: Vec<(CodeUnits, Vec<CodeUnits>)> = vec![];
+    let mut push = |high: CodeUnits, low: CodeUnits| match pairs.last_mut() {
+        Some((last_high, lows)) if *last_high == high => lows.push(low),
+        _ => pairs.push((high, vec![low])),
+    };
+    let split = |c: Char| {
+        let c = c.to_u32() - 0x10000;
+        (0xD800 + (c >> 10), 0xDC00 + (c & 0x3FF))
+    };
+    for bounds in range.0.chunks_exact(2) {
+        let ((high_min, low_min), (high_max, low_max)) = (split(bounds[0]), split(bounds[1]));
+        if high_min == high_max {
+            push((high_min, high_min), (low_min, low_max));
+            continue;
+        }
+        let mut full_min = high_min;
+        if low_min != 0xDC00 {
+            push((high_min, high_min), (low_min, 0xDFFF));
+            full_min += 1;
+        }
+        let mut full_max = high_max;
+        if low_max != 0xDFFF {
+            full_max -= 1;
+        }
+        if full_min <= full_max {
+            push((full_min, full_max), (0xDC00, 0xDFFF));
+        }
+        if low_max != 0xDFFF {
+            push((high_max, high_max), (0xDC00, low_max));
+        }
+    }
+
+    let escape = |unit: u32| {
+        if options.uppercase_hex {
+            format!("\\u{:04X}", unit)
+        } else {
+            format!("\\u{:04x}", unit)
+        }
+    };
+    let write_units = |ranges: &[CodeUnits]| {
+        let mut sb = ranges
+            .iter()
+            .map(|(min, max)| match max - min {
+                0 => escape(*min),
+                1 => format!("{}{}", escape(*min), escape(*max)),
+                _ => format!("{}-{}", escape(*min), escape(*max)),
+            })
+            .collect::<String>();
+        if ranges.len() > 1 || ranges[0].0 != ranges[0].1 {
+            sb = format!("[{}]", sb);
+        }
+        sb
+    };
+    pairs
+        .iter()
+        .map(|(high, lows)| format!("{}{}", write_units(&[*high]), write_units(lows)))
+        .collect()
+}
+
+/// Return the greatest `char` the engine can match according to `max_code_point`.
+#[inline]
+fn get_max_char(options: &RegexOptions) -> Char {
+    match options.max_code_point {
+        Some(max) if (INVALID_MIN..INVALID_MIN + INVALID_SIZE).contains(&max) => {
+            Char::new('\u{D7FF}')
+        }
+        Some(max) => Char::from_u32(max).unwrap_or(Char::max_value()),
+        None => Char::max_value(),
+    }
+}
+
+/// Return the complement of the given range among the `char` the engine can match.
+fn get_complement(range: &RangeSet<Char>, options: &RegexOptions) -> RangeSet<Char> {
+    let max_char = get_max_char(options);
+    if max_char == Char::max_value() {
+        range.complement()
+    } else {
+        RangeSet::new_from_range(..=max_char).difference(range)
+    }
+}
+
+/// Write the given class between brackets if `always_bracket` is enabled and it is not already.
+#[inline]
+fn wrap_in_brackets(regex: String, options: &RegexOptions) -> String {
+    if options.always_bracket && !regex.starts_with('[') {
+        format!("[{}]", regex)
+    } else {
+        regex
+    }
+}
+
+pub(crate) fn explain_emission(range: &RangeSet<Char>) -> EmissionPlan {
+    let range = normalize(range);
+    let range = range.as_ref();
+    // The same branches as `to_regex` with the default options.
+    if range.is_empty() {
+        EmissionPlan::Empty
+    } else if range.is_total() {
+        EmissionPlan::DirectBracket { range_count: 1 }
+    } else if *range == RangeSet::dot() {
+        EmissionPlan::Dot
+    } else if range.0.len() == 2 && range.0[0] == range.0[1] {
+        EmissionPlan::SingleChar {
+            c: range.0[0].to_char(),
+        }
+    } else if let Some(token) = tokens::identify_class(range, &RegexOptions::default()) {
+        match token.strip_prefix("\\p{").or(token.strip_prefix("\\P{")) {
+            Some(name) => EmissionPlan::NamedClass {
+                name: name.trim_end_matches('}').to_owned(),
+                negated: token.starts_with("\\P"),
+            },
+            None => EmissionPlan::PerlClass { shorthand: token },
+        }
+    } else {
+        let complement_count = count_complement_ranges(range, Char::max_value());
+        if complement_count < range.0.len() / 2 {
+            EmissionPlan::ComplementBracket {
+                range_count: complement_count,
+            }
+        } else {
+            EmissionPlan::DirectBracket {
+                range_count: range.0.len() / 2,
+            }
+        }
+    }
+}
+
+pub(crate) fn to_regex_checked(
+    range: &RangeSet<Char>,
+    options: &RegexOptions,
+) -> Result<String, LossyError> {
+    let max_char = get_max_char(options);
+    if max_char != Char::max_value() {
+        let above = RangeSet::new_from_range((max_char + Char::one())..);
+        if let Some(c) = range.intersection(&above).0.first() {
+            return Err(LossyError::UnsupportedChar(c.to_char()));
+        }
+    }
+
+    if options.flavor == RegexFlavor::Posix {
+        if normalize(range).is_empty() {
+            return Err(LossyError::EmptyClass);
+        }
+        let regex = to_regex(range, options);
+        // Outside ASCII, the meaning of a literal `char` depends on the locale.
+        return match regex.chars().find(|c| *c == '\0' || !c.is_ascii()) {
+            Some(c) => Err(LossyError::UnsupportedChar(c)),
+            None => Ok(regex),
+        };
+    }
+
+    if options.flavor == RegexFlavor::EcmaScript {
+        // An astral `char` is two code units, so it can't be matched by a class.
+        let astral = RangeSet::new_from_range(Char::new('\u{10000}')..=Char::new(char::MAX));
+        if let Some(c) = range.intersection(&astral).0.first() {
+            return Err(LossyError::UnsupportedChar(c.to_char()));
+        }
+        // A negated class would also match the surrogates on their own.
+        let options = RegexOptions {
+            escape_style: EscapeStyle::JavaScriptUnicode,
+            allow_negation: false,
+            ..options.clone()
+        };
+        return Ok(to_regex(range, &options));
+    }
+
+    let escape_style = match (options.flavor, options.escape_style) {
+        // PCRE2 does not understand `\u{...}` nor `\u...`.
+        (RegexFlavor::Pcre, EscapeStyle::RustUnicode | EscapeStyle::JavaScriptUnicode) => {
+            EscapeStyle::HexByte
+        }
+        (_, escape_style) => escape_style,
+    };
+    let options = RegexOptions {
+        empty_as: EmptyClassStyle::NegatedTotal,
+        escape_style,
+        ..options.clone()
+    };
+    Ok(to_regex(range, &options))
+}
+
+pub(crate) fn to_regex_delta(
+    range: &RangeSet<Char>,
+    base: &RangeSet<Char>,
+    options: &RegexOptions,
+) -> String {
+    if !options.use_set_ops
+        || !options.flavor.supports_set_operations()
+        || base.is_empty()
+        || base.is_total()
+        || range == base
+    {
+        return to_regex(range, options);
+    }
+    if options.flavor == RegexFlavor::RustBytes {
+        let options = RegexOptions {
+            flavor: RegexFlavor::Rust,
+            ..options.clone()
+        };
+        return enable_unicode_for_bytes(to_regex_delta(range, base, &options), range);
+    }
+
+    let additions = range.difference(base);
+    let removals = base.difference(range);
+    if !options.allow_negation && !removals.is_empty() {
+        return to_regex(range, options);
+    }
+
+    // Inside the brackets `.` is literal, and `&`, `~` and `-` would form an operator with their neighbors.
+    let mut extra_escape = options.extra_escape.clone();
+    extra_escape.extend(['&', '~', '-']);
+    let options = RegexOptions {
+        assume_dotall: true,
+        extra_escape,
+        ..options.clone()
+    };
+    let mut sb = String::from("[");
+    sb.push_str(&to_regex(base, &options));
+    if !additions.is_empty() {
+        sb.push_str(&to_regex(&additions, &options));
+    }
+    if !removals.is_empty() {
+        sb.push_str("&&");
+        sb.push_str(&to_regex(&removals.complement(), &options));
+    }
+    sb.push(']');
+    sb
+}
+
+pub(crate) fn alternation_regex(sets: &[RangeSet<Char>], options: &RegexOptions) -> String {
+    let mut singles = RangeSet::empty();
+    let mut groups: Vec<RangeSet<Char>> = Vec::with_capacity(sets.len());
+    for set in sets {
+        if set.0.len() == 2 && set.0[0] == set.0[1] {
+            singles = singles.union(set);
+        } else if !set.is_empty() {
+            groups.push(set.clone());
+        }
+    }
+    if !singles.is_empty() {
+        groups.push(singles);
+    }
+
+    // Merge the groups that overlap or are adjacent since their union takes fewer ranges.
+    let mut i = 0;
+    while i < groups.len() {
+        let mut merged = false;
+        let mut j = i + 1;
+        while j < groups.len() {
+            let union = groups[i].union(&groups[j]);
+            if union.0.len() < groups[i].0.len() + groups[j].0.len() {
+                groups[i] = union;
+                groups.remove(j);
+                merged = true;
+            } else {
+                j += 1;
+            }
+        }
+        if !merged {
+            i += 1;
+        }
+    }
+
+    match groups.len() {
+        0 => to_regex(&RangeSet::empty(), options),
+        1 => to_regex(&groups[0], options),
+        _ => format!(
+            "(?:{})",
+            groups
+                .iter()
+                .map(|group| to_regex(group, options))
+                .collect::<Vec<_>>()
+                .join("|")
+        ),
+    }
+}
+
+pub(crate) fn to_regex_merged(range: &RangeSet<Char>, gap: u32, options: &RegexOptions) -> String {
+    let range = normalize(range);
+    let mut merged: Vec<Char> = Vec::with_capacity(range.0.len());
+    for bounds in range.0.chunks_exact(2) {
+        match merged.last_mut() {
+            Some(max) if missing_chars(*max, bounds[0]) <= gap => *max = bounds[1],
+            _ => merged.extend_from_slice(bounds),
+        }
+    }
+    to_regex(&RangeSet(merged), options)
+}
+
+/// Return the number of valid `char` strictly between `min` and `max`.
+#[inline]
+fn missing_chars(min: Char, max: Char) -> u32 {
+    let index = |c: Char| {
+        let c = c.to_u32();
+        if c >= INVALID_MIN {
+            c - INVALID_SIZE
+        } else {
+            c
+        }
+    };
+    index(max) - index(min) - 1
+}
+
+/// Return the given range with sorted and maximal ranges, only allocating if it is not already the case.
+///
+/// A `RangeSet` can be built from any vector through its public field, so it can't be assumed normalized.
+pub(crate) fn normalize(range: &RangeSet<Char>) -> Cow<'_, RangeSet<Char>> {
+    let is_normalized = range.0.len().is_multiple_of(2)
+        && range.0.chunks_exact(2).all(|chunk| chunk[0] <= chunk[1])
+        && range
+            .0
+            .windows(3)
+            .step_by(2)
+            .all(|window| window[1] < window[2] && window[1] + Char::one() != window[2]);
+    if is_normalized {
+        return Cow::Borrowed(range);
+    }
+
+    let ranges = range
+        .0
+        .chunks_exact(2)
+        .filter(|chunk| chunk[0] <= chunk[1])
+        .map(|chunk| AnyRange::from(chunk[0]..=chunk[1]))
+        .collect::<Vec<_>>();
+    Cow::Owned(RangeSet::new_from_ranges(&ranges))
+}
+
+fn convert_to_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    if !options.allow_negation {
+        return write_class(range, false, options);
+    }
+    match options.cost_fn {
+        CostModel::RangeCount
+            if count_complement_ranges(range, get_max_char(options)) < range.0.len() / 2 =>
+        {
+            write_class(&get_complement(range, options), true, options)
+        }
+        CostModel::RangeCount => write_class(range, false, options),
+        CostModel::StringLength => {
+            let direct = write_class(range, false, options);
+            let negated = write_class(&get_complement(range, options), true, options);
+            if negated.len() < direct.len() {
+                negated
+            } else {
+                direct
+            }
+        }
+        CostModel::TransitionCount => {
+            let complement = get_complement(range, options);
+            if count_utf8_sequences(&complement) < count_utf8_sequences(range) {
+                write_class(&complement, true, options)
+            } else {
+                write_class(range, false, options)
+            }
+        }
+    }
+}
+
+/// Write every form of the given range and return the shortest, the first one written on a tie.
+fn convert_to_shortest_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    let property_options = RegexOptions {
+        allow_perl_classes: false,
+        ..options.clone()
+    };
+    let perl_options = RegexOptions {
+        allow_unicode_properties: false,
+        ..options.clone()
+    };
+    let complement = get_complement(range, options);
+    let mut classes = vec![(range, false)];
+    if options.allow_negation {
+        classes.push((&complement, true));
+    }
+
+    let mut candidates = vec![];
+    candidates.extend(tokens::identify_class(range, &property_options));
+    if options.allow_perl_classes {
+        candidates.extend(tokens::identify_class(range, &perl_options));
+    }
+    if options.allow_perl_classes && options.flavor.has_unicode_perl_classes() {
+        let embed_options = RegexOptions {
+            embed_classes: true,
+            ..options.clone()
+        };
+        for (class, is_complement) in &classes {
+            candidates.push(write_class(class, *is_complement, &embed_options));
+        }
+    }
+    let class_options = RegexOptions {
+        embed_classes: false,
+        ..options.clone()
+    };
+    for (class, is_complement) in &classes {
+        candidates.push(write_class(class, *is_complement, &class_options));
+    }
+
+    candidates
+        .into_iter()
+        .map(|candidate| wrap_in_brackets(candidate, options))
+        .min_by_key(|candidate| candidate.len())
+        .unwrap_or_default()
+}
+
+/// Return the number of ranges of the complement of the given normalized range up to `max_char`, without computing it.
+#[inline]
+fn count_complement_ranges(range: &RangeSet<Char>, max_char: Char) -> usize {
+    match (range.0.first(), range.0.last()) {
+        (Some(min), Some(max)) => {
+            range.0.len() / 2 - 1
+                + usize::from(*min != Char::min_value())
+                + usize::from(*max != max_char)
+        }
+        _ => 1,
+    }
+}
+
+/// Return the number of UTF-8 byte sequences, i.e. automaton transitions, needed to match the given range.
+fn count_utf8_sequences(range: &RangeSet<Char>) -> usize {
+    let mut count = 0;
+    let mut stack = range
+        .0
+        .chunks_exact(2)
+        .map(|bounds| (bounds[0].to_u32(), bounds[1].to_u32()))
+        .collect::<Vec<_>>();
+    'ranges: while let Some((min, max)) = stack.pop() {
+        if min < INVALID_MIN && max >= INVALID_MIN + INVALID_SIZE {
+            stack.push((INVALID_MIN + INVALID_SIZE, max));
+            stack.push((min, INVALID_MIN - 1));
+            continue;
+        }
+        for boundary in [0x7F, 0x7FF, 0xFFFF] {
+            if min <= boundary && boundary < max {
+                stack.push((boundary + 1, max));
+                stack.push((min, boundary));
+                continue 'ranges;
+            }
+        }
+        if max > 0x7F {
+            for i in 1..4 {
+                let mask = (1 << (6 * i)) - 1;
+                if min & !mask != max & !mask {
+                    if min & mask != 0 {
+                        stack.push(((min | mask) + 1, max));
+                        stack.push((min, min | mask));
+                        continue 'ranges;
+                    }
+                    if max & mask != mask {
+                        stack.push((max & !mask, max));
+                        stack.push((min, (max & !mask) - 1));
+                        continue 'ranges;
+                    }
+                }
+            }
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Write the given range as a class, negated if `is_complement`.
+fn write_class(
+    range_to_use: &RangeSet<Char>,
+    is_complement: bool,
+    options: &RegexOptions,
+) -> String {
+    let mut sb = String::new();
+
+    let rest;
+    let range_to_use = if options.embed_classes
+        && options.allow_perl_classes
+        && options.flavor.has_unicode_perl_classes()
+    {
+        rest = embed_perl_classes(range_to_use, &mut sb);
+        &rest
+    } else {
+        range_to_use
+    };
+
+    let in_class = !sb.is_empty()
+        || is_complement
+        || range_to_use.0.len() > 2
+        || range_to_use.0[0] != range_to_use.0[1];
+
+    // A `-` bounding a range is placed last, where it can't be read as a range.
+    let hyphen = Char::new('-');
+    let trailing_hyphen = in_class
+        && options.flavor.allows_literal_punctuation_in_class()
+        && range_to_use.0.contains(&hyphen);
+    let rest;
+    let range_to_use = if trailing_hyphen {
+        rest = range_to_use.difference(&RangeSet::new_from_range(hyphen..=hyphen));
+        &rest
+    } else {
+        range_to_use
+    };
+
+    // JavaScript reads the class as code units, where a range over the surrogates also matches them.
+    let split_at_surrogates = options.flavor == RegexFlavor::EcmaScript;
+    for r in (0..range_to_use.0.len()).step_by(2) {
+        let (min, max) = (range_to_use.0[r], range_to_use.0[r + 1]);
+        if split_at_surrogates && min.to_u32() < INVALID_MIN && max.to_u32() > INVALID_MIN {
+            write_range(min, Char::new('\u{D7FF}'), in_class, options, &mut sb);
+            write_range(Char::new('\u{E000}'), max, in_class, options, &mut sb);
+        } else {
+            write_range(min, max, in_class, options, &mut sb);
+        }
+    }
+
+    if trailing_hyphen {
+        sb.push('-');
+    }
+    // A leading `^` would negate the class.
+    if in_class && !is_complement && sb.starts_with('^') {
+        sb.insert(0, '\\');
+    }
+
+    if in_class {
+        if is_complement {
+            return format!("[^{}]", sb);
+        } else {
+            return format!("[{}]", sb);
+        }
+    }
+
+    sb
+}
+
+/// Write the range from `min` to `max` inside a class.
+fn write_range(min: Char, max: Char, in_class: bool, options: &RegexOptions, sb: &mut String) {
+    if min == max {
+        sb.push_str(get_printable_char(min.to_char(), in_class, options).as_str());
+    } else if min.to_u32() + 1 == max.to_u32() {
+        // Only consecutive code points are written side by side, the `regex` crate
+        // mishandles a negated class holding U+D7FF and U+E000 as separate items.
+        sb.push_str(
+            format!(
+                "{}{}",
+                get_printable_char(min.to_char(), in_class, options),
+                get_printable_char(max.to_char(), in_class, options)
+            )
+            .as_str(),
+        );
+    } else {
+        sb.push_str(
+            format!(
+                "{}-{}",
+                get_printable_char(min.to_char(), in_class, options),
+                get_printable_char(max.to_char(), in_class, options)
+            )
+            .as_str(),
+        );
+    }
+}
+
+/// Write the given range to be inserted between `\Q` and `\E`, where every `char` is literal.
+fn convert_to_quoted_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    if range.0.len() == 2 && range.0[0] == range.0[1] {
+        return match range.0[0].to_char() {
+            // `\E` would end the quote early.
+            '\\' => String::from("\\E\\\\\\Q"),
+            c => c.to_string(),
+        };
+    }
+
+    let options = RegexOptions {
+        quote_context: false,
+        ..options.clone()
+    };
+    format!("\\E{}\\Q", to_regex(range, &options))
+}
+
+/// Write the Perl classes contained in the given range and return the part left to write.
+fn embed_perl_classes(range: &RangeSet<Char>, sb: &mut String) -> RangeSet<Char> {
+    let mut rest = range.clone();
+    // `\w` comes first since it contains `\d`.
+    for letter in ['w', 'd', 's'] {
+        let Some(ranges) = tokens::get_perl_class_by_letter(letter) else {
+            continue;
+        };
+        let class = tokens::convert_from_range(ranges);
+        if class.difference(range).is_empty() && !class.intersection(&rest).is_empty() {
+            sb.push('\\');
+            sb.push(letter);
+            rest = rest.difference(&class);
+        }
+    }
+    rest
+}
+
+fn convert_to_posix_regex(range: &RangeSet<Char>, options: &RegexOptions) -> String {
+    let complement = get_complement(range, options);
+    let (range_to_use, is_complement) =
+        if options.allow_negation && complement.0.len() < range.0.len() {
+            (&complement, true)
+        } else {
+            (range, false)
+        };
+
+    if let Some(class) = tokens::identify_posix_class(&tokens::convert_to_range(range)) {
+        return format!("[[:{}:]]", class);
+    }
+    if options.allow_negation {
+        if let Some(class) = tokens::identify_posix_class(&tokens::convert_to_range(&complement)) {
+            return format!("[^[:{}:]]", class);
+        }
+    }
+
+    if !is_complement && range_to_use.0.len() == 2 && range_to_use.0[0] == range_to_use.0[1] {
+        let character = range_to_use.0[0].to_char();
+        return if ".[\\()*+?{|^$".contains(character) {
+            format!("\\{}", character)
+        } else {
+            character.to_string()
+        };
+    }
+
+    let closing_bracket = Char::new(']');
+    let caret = Char::new('^');
+    let hyphen = Char::new('-');
+    let rest = range_to_use.difference(&RangeSet::new_from_ranges(&[
+        AnyRange::from(closing_bracket..=closing_bracket),
+        AnyRange::from(caret..=caret),
+        AnyRange::from(hyphen..=hyphen),
+    ]));
+
+    let mut sb = String::new();
+    if range_to_use.contains(closing_bracket) {
+        sb.push(']');
+    }
+    for r in (0..rest.0.len()).step_by(2) {
+        let (min, max) = (rest.0[r].to_char(), rest.0[r + 1].to_char());
+        sb.push(min);
+        if min != max {
+            if rest.0[r].to_u32() + 1 != rest.0[r + 1].to_u32() {
+                sb.push('-');
+            }
+            sb.push(max);
+        }
+    }
+    if range_to_use.contains(caret) {
+        if sb.is_empty() && !is_complement {
+            // A leading `^` would negate the expression, `-` is literal in first position.
+            sb.push_str("-^");
+        } else {
+            sb.push('^');
+            if range_to_use.contains(hyphen) {
+                sb.push('-');
+            }
+        }
+    } else if range_to_use.contains(hyphen) {
+        sb.push('-');
+    }
+
+    if is_complement {
+        format!("[^{}]", sb)
+    } else {
+        format!("[{}]", sb)
+    }
+}
+
+fn get_printable_char(character: char, in_class: bool, options: &RegexOptions) -> String {
+    if options.extra_escape.contains(&character) || options.delimiter == Some(character) {
+        if character.is_ascii_punctuation() {
+            format!("\\{}", character)
+        } else if let Some(c) = identify_character(character, options) {
+            c.to_owned()
+        } else {
+            escape_code_point(character, options)
+        }
+    } else if ('\u{20}'..='\u{7E}').contains(&character) {
+        if in_class && options.flavor.allows_literal_punctuation_in_class() {
+            // `^` and `-` are placed by `write_class` where they are literal.
+            if matches!(character, '\\' | '[' | ']') {
+                format!("\\{}", character)
+            } else {
+                format!("{}", character)
+            }
+        } else if character == '*'
+            || character == '+'
+            || character == '?'
+            || character == '('
+            || character == ')'
+            || character == '['
+            || character == ']'
+            || character == '{'
+            || character == '}'
+            || character == '|'
+            || character == '\\'
+            || character == '-'
+            || character == '^'
+            || character == '$'
+            || character == '.'
+        {
+            format!("\\{}", character)
+        } else {
+            format!("{}", character)
+        }
+    } else if let Some(c) = identify_character(character, options) {
+        c.to_owned()
+    } else if character == '\u{8}'
+        && in_class
+        && options.use_class_backspace
+        && options.flavor.supports_class_backspace()
+    {
+        String::from("\\b")
+    } else {
+        escape_code_point(character, options)
+    }
+}
+
+fn escape_code_point(character: char, options: &RegexOptions) -> String {
+    let escape_style = if options.flavor == RegexFlavor::Re2 {
+        EscapeStyle::HexByte
+    } else {
+        options.escape_style
+    };
+    match escape_style {
+        EscapeStyle::RustUnicode if options.uppercase_hex => {
+            format!("\\u{{{:04X}}}", character as u32)
+        }
+        EscapeStyle::RustUnicode => format!("\\u{{{:04x}}}", character as u32),
+        EscapeStyle::NamedUnicode => format!("\\N{{U+{:04X}}}", character as u32),
+        EscapeStyle::HexByte if character as u32 <= 0xFF && options.uppercase_hex => {
+            format!("\\x{:02X}", character as u32)
+        }
+        EscapeStyle::HexByte if character as u32 <= 0xFF => {
+            format!("\\x{:02x}", character as u32)
+        }
+        EscapeStyle::HexByte if options.uppercase_hex => {
+            format!("\\x{{{:04X}}}", character as u32)
+        }
+        EscapeStyle::HexByte => format!("\\x{{{:04x}}}", character as u32),
+        EscapeStyle::JavaScriptUnicode if character as u32 <= 0xFFFF && options.uppercase_hex => {
+            format!("\\u{:04X}", character as u32)
+        }
+        EscapeStyle::JavaScriptUnicode if character as u32 <= 0xFFFF => {
+            format!("\\u{:04x}", character as u32)
+        }
+        EscapeStyle::JavaScriptUnicode if options.uppercase_hex => {
+            format!("\\u{{{:X}}}", character as u32)
+        }
+        EscapeStyle::JavaScriptUnicode => format!("\\u{{{:x}}}", character as u32),
+        EscapeStyle::UnicodeName => match get_unicode_name(character) {
+            Some(name) => format!("\\N{{{}}}", name),
+            None => format!("\\N{{U+{:04X}}}", character as u32),
+        },
+    }
+}
+
+#[cfg(feature = "unicode-names")]
+#[inline]
+fn get_unicode_name(character: char) -> Option<String> {
+    unicode_names2::name(character).map(|name| name.to_string())
+}
+
+#[cfg(not(feature = "unicode-names"))]
+#[inline]
+fn get_unicode_name(_: char) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        options::{ControlEscapeStyle, LossyError},
+        tokens::{
+            convert_from_range,
+            unicode::{perl_decimal, perl_word},
+        },
+        CharacterClass,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_named_unicode_escape() -> Result<(), String> {
+        let options = RegexOptions {
+            escape_style: EscapeStyle::NamedUnicode,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F600}');
+        assert_eq!("\\N{U+1F600}", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{e9}'..='\u{ff}');
+        assert_eq!("[\\N{U+00E9}-\\N{U+00FF}]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{e9}'..='\u{ff}');
+        assert_eq!("[\\u{00e9}-\\u{00ff}]", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_leading_caret() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('^')..=Char::new('^')),
+            AnyRange::from(Char::new('a')..=Char::new('b')),
+        ]);
+        assert_eq!("[\\^ab]", range.to_regex());
+        assert_eq!("[^^ab]", range.complement().to_regex());
+
+        let range = RangeSet::new_from_range_char('^'..='`');
+        assert_eq!("[\\^-`]", range.to_regex());
+
+        let range = RangeSet::new_from_range_char('^'..='^');
+        assert_eq!("\\^", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_class_punctuation() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_regex("[a\\-z]").unwrap();
+        assert_eq!("[az-]", range.to_regex());
+
+        let range = RangeSet::<Char>::from_regex("[.*+?()|$]").unwrap();
+        assert_eq!("[$(-+.?|]", range.to_regex());
+        assert_eq!("[^$(-+.?|]", range.complement().to_regex());
+
+        let range = RangeSet::<Char>::from_regex("[\\[\\]\\\\a]").unwrap();
+        assert_eq!("[\\[-\\]a]", range.to_regex());
+
+        let range = RangeSet::new_from_range_char('+'..='-');
+        assert_eq!("[+,-]", range.to_regex());
+        let range = RangeSet::new_from_range_char('-'..='/');
+        assert_eq!("[./-]", range.to_regex());
+
+        let options = RegexOptions {
+            flavor: RegexFlavor::EcmaScript,
+            escape_style: EscapeStyle::JavaScriptUnicode,
+            ..Default::default()
+        };
+        let range = RangeSet::<Char>::from_regex("[a.\\-]").unwrap();
+        assert_eq!("[\\-\\.a]", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_posix_closing_bracket() -> Result<(), String> {
+        let options = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new(']')..=Char::new(']')),
+            AnyRange::from(Char::new('a')..=Char::new('a')),
+        ]);
+        assert_eq!("[]a]", range.to_regex_with(&options));
+        assert_eq!("[^]a]", range.complement().to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('['..='a');
+        assert_eq!("[][\\_-a^]", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('-')..=Char::new('-')),
+            AnyRange::from(Char::new('^')..=Char::new('^')),
+        ]);
+        assert_eq!("[-^]", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('-')..=Char::new('-')),
+            AnyRange::from(Char::new('0')..=Char::new('9')),
+        ]);
+        assert_eq!("[0-9-]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char(']'..=']');
+        assert_eq!("]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('.'..='.');
+        assert_eq!("\\.", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_posix_blank() -> Result<(), String> {
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_ranges(&[
+            AnyRange::from(Char::new('\t')..=Char::new('\t')),
+            AnyRange::from(Char::new(' ')..=Char::new(' ')),
+        ]);
+        assert_eq!("[[:blank:]]", range.to_regex_with(&posix));
+        assert_eq!("[\\t ]", range.to_regex());
+        assert_eq!("[^[:blank:]]", range.complement().to_regex_with(&posix));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-gencat")]
+    #[test]
+    fn test_posix_cntrl() -> Result<(), String> {
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_ranges(&[
+            AnyRange::from(Char::new('\0')..=Char::new('\u{1F}')),
+            AnyRange::from(Char::new('\u{7F}')..=Char::new('\u{7F}')),
+        ]);
+        assert_eq!("[[:cntrl:]]", range.to_regex_with(&posix));
+        assert_eq!("[^[:cntrl:]]", range.complement().to_regex_with(&posix));
+
+        let cc = RangeSet::<Char>::from_regex("\\p{Cc}").unwrap();
+        assert_ne!(range, cc);
+        assert_eq!("\\p{Cc}", cc.to_regex());
+        assert!(!cc.to_regex_with(&posix).contains("[:cntrl:]"));
+        assert!(!range.to_regex().contains("Cc"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_posix_punct() -> Result<(), String> {
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_ranges(&[
+            AnyRange::from(Char::new('!')..=Char::new('/')),
+            AnyRange::from(Char::new(':')..=Char::new('@')),
+            AnyRange::from(Char::new('[')..=Char::new('`')),
+            AnyRange::from(Char::new('{')..=Char::new('~')),
+        ]);
+        assert_eq!("[[:punct:]]", range.to_regex_with(&posix));
+        assert_eq!("[^[:punct:]]", range.complement().to_regex_with(&posix));
+        assert_eq!("[!-/:-@\\[-`{-~]", range.to_regex());
+
+        Ok(())
+    }
+
+    #[cfg(all(
+        feature = "unicode-gencat",
+        feature = "unicode-props",
+        feature = "unicode-scripts"
+    ))]
+    #[test]
+    fn test_posix_alnum() -> Result<(), String> {
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let range = RangeSet::<Char>::from_regex("[0-9A-Za-z]").unwrap();
+        assert_eq!("[[:alnum:]]", range.to_regex_with(&posix));
+        assert_eq!("[^[:alnum:]]", range.complement().to_regex_with(&posix));
+        // There is no `\p{Alnum}` in the other flavors, the ranges are written as is.
+        for flavor in [
+            RegexFlavor::Rust,
+            RegexFlavor::Pcre,
+            RegexFlavor::EcmaScript,
+            RegexFlavor::Re2,
+        ] {
+            let options = RegexOptions {
+                flavor,
+                ..Default::default()
+            };
+            assert_eq!("[0-9A-Za-z]", range.to_regex_with(&options));
+            assert_eq!("[^0-9A-Za-z]", range.complement().to_regex_with(&options));
+        }
+        let options = RegexOptions {
+            cost_fn: CostModel::StringLength,
+            embed_classes: true,
+            ..Default::default()
+        };
+        assert_eq!("[0-9A-Za-z]", range.to_regex_with(&options));
+
+        let unicode = RangeSet::<Char>::from_regex("[\\p{Alphabetic}\\p{Nd}]").unwrap();
+        assert_ne!(range, unicode);
+        assert!(!unicode.to_regex_with(&posix).contains("[:alnum:]"));
+        assert!(!unicode.to_regex().contains("[:alnum:]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_posix_graph_print() -> Result<(), String> {
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+
+        let graph = RangeSet::new_from_range_char('!'..='~');
+        assert_eq!("[[:graph:]]", graph.to_regex_with(&posix));
+        assert_eq!("[^[:graph:]]", graph.complement().to_regex_with(&posix));
+        assert_eq!("[!-~]", graph.to_regex());
+
+        let print = RangeSet::new_from_range_char(' '..='~');
+        assert_eq!("[[:print:]]", print.to_regex_with(&posix));
+        assert_eq!("[^[:print:]]", print.complement().to_regex_with(&posix));
+        assert_eq!("[ -~]", print.to_regex());
+
+        let range = RangeSet::new_from_range_char('!'..='}');
+        assert_eq!("[]!-,.-\\_-}^-]", range.to_regex_with(&posix));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_as() -> Result<(), String> {
+        assert_eq!("[]", RangeSet::empty().to_regex());
+
+        let options = RegexOptions {
+            empty_as: EmptyClassStyle::NegatedTotal,
+            ..Default::default()
+        };
+        assert_eq!(
+            "[^\\u{0000}-\\u{10ffff}]",
+            RangeSet::empty().to_regex_with(&options)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assume_dotall() -> Result<(), String> {
+        let total = RangeSet::total();
+        let dot = RangeSet::dot();
+        assert_eq!("[\\u{0000}-\\u{10ffff}]", total.to_regex());
+        assert_eq!(".", dot.to_regex());
+        assert_eq!("\\n", dot.complement().to_regex());
+
+        let options = RegexOptions {
+            assume_dotall: true,
+            ..Default::default()
+        };
+        assert_eq!(".", total.to_regex_with(&options));
+        assert_eq!("[^\\n]", dot.to_regex_with(&options));
+
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+        assert_eq!(".", total.to_regex_with(&posix));
+        assert_eq!("[^\n]", dot.to_regex_with(&posix));
+        let ecmascript = RegexOptions {
+            flavor: RegexFlavor::EcmaScript,
+            ..Default::default()
+        };
+        assert_eq!("[^\\n]", dot.to_regex_with(&ecmascript));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-gencat")]
+    #[test]
+    fn test_always_bracket() -> Result<(), String> {
+        let options = RegexOptions {
+            always_bracket: true,
+            ..Default::default()
+        };
+
+        assert_eq!("[]", RangeSet::empty().to_regex_with(&options));
+        assert_eq!(
+            "[\\u{0000}-\\u{10ffff}]",
+            RangeSet::total().to_regex_with(&options)
+        );
+        assert_eq!("[^\\n]", RangeSet::dot().to_regex_with(&options));
+        assert_eq!(
+            "[\\n]",
+            RangeSet::dot().complement().to_regex_with(&options)
+        );
+        assert_eq!(
+            "[a]",
+            RangeSet::new_from_range_char('a'..='a').to_regex_with(&options)
+        );
+        assert_eq!(
+            "[\\[]",
+            RangeSet::new_from_range_char('['..='[').to_regex_with(&options)
+        );
+        assert_eq!(
+            "[a-z]",
+            RangeSet::new_from_range_char('a'..='z').to_regex_with(&options)
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+        assert_eq!("[\\d]", range.to_regex_with(&options));
+        assert_eq!("[\\D]", range.complement().to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("\\p{L}").unwrap();
+        assert_eq!("[\\p{L}]", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_code_point() -> Result<(), String> {
+        let options = RegexOptions {
+            max_code_point: Some(0xFFFF),
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('a'..='\u{1F64F}');
+        assert_eq!("[a-\\u{ffff}]", range.to_regex_with(&options));
+        assert_eq!(
+            Err(LossyError::UnsupportedChar('\u{10000}')),
+            range.to_regex_checked_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}');
+        assert_eq!("[]", range.to_regex_with(&options));
+        assert_eq!(
+            Err(LossyError::UnsupportedChar('\u{1F600}')),
+            range.to_regex_checked_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!("[a-z]", range.to_regex_with(&options));
+        assert_eq!(
+            Ok("[a-z]".to_owned()),
+            range.to_regex_checked_with(&options)
+        );
+        // The negated form does not hold the astral code points.
+        assert_eq!("[^a-z]", range.complement().to_regex_with(&options));
+        assert_eq!(
+            "[\\u{0000}-\\u{ffff}]",
+            RangeSet::<Char>::total().to_regex_with(&options)
+        );
+        assert_eq!(".", RangeSet::<Char>::dot().to_regex_with(&options));
+
+        let options = RegexOptions {
+            max_code_point: Some(0xFFFF),
+            empty_as: EmptyClassStyle::NegatedTotal,
+            always_bracket: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            "[^\\u{0000}-\\u{ffff}]",
+            RangeSet::<Char>::empty().to_regex_with(&options)
+        );
+        assert_eq!(
+            "[\\u{0000}-\\u{ffff}]",
+            RangeSet::<Char>::total().to_regex_with(&options)
+        );
+
+        let options = RegexOptions {
+            max_code_point: Some(0xD900),
+            ..Default::default()
+        };
+        assert_eq!(
+            "[a-\\u{d7ff}]",
+            RangeSet::new_from_range_char('a'..=char::MAX).to_regex_with(&options)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-scripts")]
+    #[test]
+    fn test_ascii_only() -> Result<(), String> {
+        let options = RegexOptions {
+            ascii_only: true,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F600}');
+        assert_eq!("\\ud83d\\ude00", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}');
+        assert_eq!("\\ud83d[\\ude00-\\ude4f]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{1F000}'..='\u{1F7FF}');
+        assert_eq!(
+            "[\\ud83c\\ud83d][\\udc00-\\udfff]",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('\u{1F3FF}'..='\u{1FC00}');
+        assert_eq!(
+            "(?:\\ud83c\\udfff|[\\ud83d\\ud83e][\\udc00-\\udfff]|\\ud83f\\udc00)",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::<Char>::from_regex("[a-zé\\u{1F600}\\u{1F602}]").unwrap();
+        assert_eq!(
+            "(?:[a-z\\u00e9]|\\ud83d[\\ude00\\ude02])",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\p{Greek}").unwrap();
+        let regex = range.to_regex_with(&options);
+        assert!(regex.is_ascii() && !regex.contains("\\p") && regex.contains("\\ud834"));
+
+        // The surrogates are left out of the BMP part, which is never negated.
+        assert_eq!(
+            "[\\u0000-\\u00e8\\u00ea-\\ud7ff\\ue000-\\uffff]",
+            RangeSet::new_from_range_char('é'..='é')
+                .complement()
+                .intersection(&RangeSet::new_from_range_char('\0'..='\u{FFFF}'))
+                .to_regex_with(&options)
+        );
+        assert_eq!(
+            "[\\ud7ff\\ue000]",
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}').to_regex_with(&options)
+        );
+        assert_eq!(
+            "(?:[a-\\ud7ff\\ue000-\\uffff]|[\\ud800-\\udbff][\\udc00-\\udfff])",
+            RangeSet::new_from_range_char('a'..).to_regex_with(&options)
+        );
+        assert_eq!(
+            "(?:[\\u0000-\\ud7ff\\ue000-\\uffff]|[\\ud800-\\udbff][\\udc00-\\udfff])",
+            RangeSet::<Char>::total().to_regex_with(&options)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_anchored_regex() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!("^[a-z]$", range.to_anchored_regex());
+        assert_eq!(
+            "^\\.$",
+            RangeSet::new_from_range_char('.'..='.').to_anchored_regex()
+        );
+        assert_eq!(
+            "^[^\\u{0000}-\\u{10ffff}]$",
+            RangeSet::<Char>::empty().to_anchored_regex()
+        );
+        assert_eq!(
+            "^[\\u{0000}-\\u{10ffff}]$",
+            RangeSet::<Char>::total().to_anchored_regex()
+        );
+        assert_eq!("^.$", RangeSet::<Char>::dot().to_anchored_regex());
+        let options = RegexOptions {
+            assume_dotall: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            "^[\\u{0000}-\\u{10ffff}]$",
+            RangeSet::<Char>::total().to_anchored_regex_with(&options)
+        );
+
+        for (flavor, expected) in [
+            (RegexFlavor::Rust, "^[a-z]$"),
+            (RegexFlavor::Pcre, "\\A[a-z]\\z"),
+            (RegexFlavor::Posix, "^[a-z]$"),
+            (RegexFlavor::EcmaScript, "^[a-z]$"),
+            (RegexFlavor::Re2, "^[a-z]$"),
+        ] {
+            let options = RegexOptions {
+                flavor,
+                ..Default::default()
+            };
+            assert_eq!(expected, range.to_anchored_regex_with(&options));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_surrogate_gap() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}');
+        assert_eq!("[\\u{d7ff}-\\u{e000}]", range.to_regex());
+        assert_eq!("[^\\u{d7ff}-\\u{e000}]", range.complement().to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_regex_merged() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_regex("[a-ce-gk]").unwrap();
+        assert_eq!("[a-ce-gk]", range.to_regex_merged(0));
+        assert_eq!("[a-gk]", range.to_regex_merged(1));
+        assert_eq!("[a-gk]", range.to_regex_merged(2));
+        assert_eq!("[a-k]", range.to_regex_merged(3));
+
+        let range = RangeSet::<Char>::from_regex("[\\u{d7fe}\\u{e001}]").unwrap();
+        assert_eq!("[\\u{d7fe}-\\u{e001}]", range.to_regex_merged(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tilde() -> Result<(), String> {
+        assert_eq!("~", RangeSet::new_from_range_char('~'..='~').to_regex());
+        assert_eq!("[}~]", RangeSet::new_from_range_char('}'..='~').to_regex());
+        assert_eq!(
+            "\\u{007f}",
+            RangeSet::new_from_range_char('\u{7F}'..='\u{7F}').to_regex()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_char() -> Result<(), String> {
+        for (c, expected) in [
+            ('a', "a"),
+            ('(', "\\("),
+            ('+', "\\+"),
+            (']', "\\]"),
+            ('.', "\\."),
+            ('$', "\\$"),
+            ('\n', "\\n"),
+        ] {
+            let range = RangeSet::new_from_range_char(c..=c);
+            assert_eq!(expected, range.to_regex());
+            assert_eq!(
+                expected,
+                write_class(&range, false, &RegexOptions::default())
+            );
+        }
+
+        for c in (' '..='~').filter(char::is_ascii_punctuation) {
+            let range = RangeSet::new_from_range_char(c..=c);
+            let regex = range.to_regex();
+            assert!(!regex.starts_with('['), "{}", regex);
+            assert_eq!(range, RangeSet::from_regex(&regex).unwrap(), "{}", regex);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backslash() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\\'..='\\');
+        assert_eq!("\\\\", range.to_regex());
+        assert_eq!(range, RangeSet::from_regex(&range.to_regex()).unwrap());
+
+        let range = range.union(&RangeSet::new_from_range_char('a'..='a'));
+        assert_eq!("[\\\\a]", range.to_regex());
+        assert_eq!(range, RangeSet::from_regex(&range.to_regex()).unwrap());
+        assert_eq!("[^\\\\a]", range.complement().to_regex());
+        assert_eq!(
+            range.complement(),
+            RangeSet::from_regex(&range.complement().to_regex()).unwrap()
+        );
+
+        let posix = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+        let range = RangeSet::new_from_range_char('\\'..='\\');
+        assert_eq!("\\\\", range.to_regex_with(&posix));
+        // Backslash is literal inside POSIX brackets.
+        let range = range.union(&RangeSet::new_from_range_char('a'..='a'));
+        assert_eq!("[\\a]", range.to_regex_with(&posix));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extra_escape() -> Result<(), String> {
+        let options = RegexOptions {
+            extra_escape: vec!['/', '"', ' ', 'a'],
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('/'..='/');
+        assert_eq!("/", range.to_regex());
+        assert_eq!("\\/", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("[\"/ ab]").unwrap();
+        assert_eq!("[ \"/ab]", range.to_regex());
+        assert_eq!(
+            "[\\u{0020}\\\"\\/\\u{0061}b]",
+            range.to_regex_with(&options)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delimiter() -> Result<(), String> {
+        let options = RegexOptions {
+            delimiter: Some('/'),
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('/'..='/');
+        assert_eq!("\\/", range.to_regex_with(&options));
+        assert_eq!("[^\\/]", range.complement().to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("[\\-/a-z]").unwrap();
+        assert_eq!("[/a-z-]", range.to_regex());
+        assert_eq!("[\\/a-z-]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('.'..='0');
+        assert_eq!("[.-0]", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[cfg(all(
+        feature = "unicode-gencat",
+        feature = "unicode-props",
+        feature = "unicode-scripts"
+    ))]
+    #[test]
+    fn test_re2() -> Result<(), String> {
+        let options = RegexOptions {
+            flavor: RegexFlavor::Re2,
+            ..Default::default()
+        };
+
+        let range = RangeSet::<Char>::from_regex("\\p{Greek}").unwrap();
+        assert_eq!("\\p{Greek}", range.to_regex_with(&options));
+        assert_eq!("\\P{Greek}", range.complement().to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("\\p{Currency_Symbol}").unwrap();
+        assert_eq!("\\p{Sc}", range.to_regex_with(&options));
+
+        // These classes changed in Unicode 16, while RE2 follows Unicode 15.
+        for regex in ["\\p{Lu}", "\\p{Nd}", "\\p{Latin}", "\\p{Han}"] {
+            let range = RangeSet::<Char>::from_regex(regex).unwrap();
+            assert!(!range.to_regex_with(&options).contains("\\p"));
+        }
+
+        let range = RangeSet::<Char>::from_regex("\\p{Garay}").unwrap();
+        assert_eq!("\\p{Garay}", range.to_regex());
+        assert_eq!(
+            "[\\x{10d40}-\\x{10d65}\\x{10d69}-\\x{10d85}\\x{10d8e}\\x{10d8f}]",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\p{ASCII_Hex_Digit}").unwrap();
+        assert_eq!("[0-9A-Fa-f]", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("\\s").unwrap();
+        assert_eq!(
+            "[\\t-\\r \\x85\\xa0\\x{1680}\\x{2000}-\\x{200a}\\x{2028}\\x{2029}\\x{202f}\\x{205f}\\x{3000}]",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('\u{7}'..='\u{7}');
+        assert_eq!("\\a", range.to_regex_with(&options));
+        let range = RangeSet::new_from_range_char('é'..='é');
+        assert_eq!("\\xe9", range.to_regex_with(&options));
+        assert_eq!(
+            Ok("[^\\x00-\\x{10ffff}]".to_owned()),
+            RangeSet::empty().to_regex_checked(RegexFlavor::Re2)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_embed_classes() -> Result<(), String> {
+        let options = RegexOptions {
+            embed_classes: true,
+            ..Default::default()
+        };
+
+        let range = convert_from_range(perl_decimal::DECIMAL_NUMBER)
+            .union(&RangeSet::new_from_range_char('-'..='.'));
+        assert_eq!("[\\d.-]", range.to_regex_with(&options));
+        assert_eq!("[^\\d.-]", range.complement().to_regex_with(&options));
+        assert_eq!(range, RangeSet::from_regex("[\\d\\-\\.]").unwrap());
+
+        let range = RangeSet::<Char>::from_regex("[\\sx]").unwrap();
+        assert_eq!("[\\sx]", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("[\\w\\s]").unwrap();
+        assert_eq!("[\\w\\s]", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("[\\d\\w]").unwrap();
+        assert_eq!("\\w", range.to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("[0-9x]").unwrap();
+        assert_eq!("[0-9x]", range.to_regex_with(&options));
+
+        let options = RegexOptions {
+            embed_classes: true,
+            allow_perl_classes: false,
+            allow_unicode_properties: false,
+            ..Default::default()
+        };
+        let range = RangeSet::<Char>::from_regex("[\\sx]").unwrap();
+        assert!(!range.to_regex_with(&options).contains("\\s"));
+        let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+        assert!(range.to_regex_with(&options).starts_with("[0-9"));
+
+        // The Perl classes are ASCII-only in the other flavors.
+        let range = RangeSet::<Char>::from_regex("[\\dx]").unwrap();
+        for (flavor, embedded) in [
+            (RegexFlavor::Rust, true),
+            (RegexFlavor::RustBytes, true),
+            (RegexFlavor::Pcre, false),
+            (RegexFlavor::EcmaScript, false),
+            (RegexFlavor::Re2, false),
+            (RegexFlavor::Posix, false),
+        ] {
+            let options = RegexOptions {
+                flavor,
+                embed_classes: true,
+                ..Default::default()
+            };
+            let regex = range.to_regex_with(&options);
+            assert_eq!(embedded, regex.contains("\\d"), "{:?}: {}", flavor, regex);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hex_byte_escape() -> Result<(), String> {
+        let options = RegexOptions {
+            escape_style: EscapeStyle::HexByte,
+            control_escape_style: ControlEscapeStyle::Hex,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('\n'..='\n');
+        assert_eq!("\\x0a", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('é'..='é');
+        assert_eq!("\\xe9", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{1}'..='\u{1F}');
+        assert_eq!("[\\x01-\\x1f]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('é'..='€');
+        assert_eq!("[\\xe9-\\x{20ac}]", range.to_regex_with(&options));
+        assert_eq!(
+            "[\\xE9-\\x{20AC}]",
+            range.to_regex_with(&RegexOptions {
+                uppercase_hex: true,
+                ..options.clone()
+            })
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-scripts")]
+    #[test]
+    fn test_allow_negation() -> Result<(), String> {
+        let options = RegexOptions {
+            allow_negation: false,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('a'..='a').complement();
+        assert_eq!("[^a]", range.to_regex());
+        assert_eq!("[\\u{0000}-`b-\\u{10ffff}]", range.to_regex_with(&options));
+
+        let range = convert_from_range(perl_decimal::DECIMAL_NUMBER).complement();
+        assert_eq!("\\D", range.to_regex());
+        let regex = range.to_regex_with(&options);
+        assert!(regex.starts_with('[') && !regex.starts_with("[^"));
+        assert_eq!(range, RangeSet::from_regex(&regex).unwrap());
+
+        let range = RangeSet::<Char>::from_regex("\\P{Greek}").unwrap();
+        let regex = range.to_regex_with(&options);
+        assert!(regex.starts_with('[') && !regex.starts_with("[^"));
+        assert_eq!(range, RangeSet::from_regex(&regex).unwrap());
+
+        let options = RegexOptions {
+            allow_negation: false,
+            use_set_ops: true,
+            ..Default::default()
+        };
+        let base = convert_from_range(perl_word::PERL_WORD);
+        let range = base.difference(&RangeSet::new_from_range_char('_'..='_'));
+        assert!(!range.to_regex_delta(&base, &options).contains('^'));
+
+        let options = RegexOptions {
+            allow_negation: false,
+            flavor: RegexFlavor::Posix,
+            ..Default::default()
+        };
+        let range = RangeSet::new_from_range_char('a'..='a').complement();
+        assert!(!range.to_regex_with(&options).starts_with("[^"));
+
+        // The empty class has no direct form.
+        let options = RegexOptions {
+            allow_negation: false,
+            empty_as: EmptyClassStyle::NegatedTotal,
+            ..Default::default()
+        };
+        let range = RangeSet::<Char>::empty();
+        assert_eq!("[^\\u{0000}-\\u{10ffff}]", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_regex_checked() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}');
+        assert_eq!(
+            Ok("[\\u{1f600}-\\u{1f64f}]".to_owned()),
+            range.to_regex_checked(RegexFlavor::Rust)
+        );
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F640}');
+        assert_eq!(
+            Ok("[\\x{1f600}-\\x{1f640}]".to_owned()),
+            range.to_regex_checked(RegexFlavor::Pcre)
+        );
+        assert_eq!(
+            Err(LossyError::UnsupportedChar('\u{1F600}')),
+            range.to_regex_checked(RegexFlavor::Posix)
+        );
+
+        // The escape styles understood by PCRE2 are kept.
+        let range = RangeSet::new_from_range_char('é'..='é');
+        let options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            escape_style: EscapeStyle::NamedUnicode,
+            ..Default::default()
+        };
+        assert_eq!(
+            Ok(range.to_regex_with(&options)),
+            range.to_regex_checked_with(&options)
+        );
+        assert_eq!(
+            Ok("\\N{U+00E9}".to_owned()),
+            range.to_regex_checked_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('\0'..='\u{1F}');
+        assert_eq!(
+            Ok("[\\x00-\\x1f]".to_owned()),
+            range.to_regex_checked(RegexFlavor::Pcre)
+        );
+
+        assert_eq!(
+            Err(LossyError::UnsupportedChar('\0')),
+            range.to_regex_checked(RegexFlavor::Posix)
+        );
+
+        let range = RangeSet::new_from_range_char('a'..='a').complement();
+        assert_eq!(
+            Ok("[^a]".to_owned()),
+            range.to_regex_checked(RegexFlavor::Posix)
+        );
+
+        let range = RangeSet::empty();
+        assert_eq!(
+            Ok("[^\\u{0000}-\\u{10ffff}]".to_owned()),
+            range.to_regex_checked(RegexFlavor::Rust)
+        );
+        assert_eq!(
+            Err(LossyError::EmptyClass),
+            range.to_regex_checked(RegexFlavor::Posix)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_complement_ranges() -> Result<(), String> {
+        let ranges = [
+            RangeSet::empty(),
+            RangeSet::total(),
+            RangeSet::dot(),
+            RangeSet::new_from_range_char('\0'..='a'),
+            RangeSet::new_from_range_char('a'..=char::MAX),
+            RangeSet::new_from_range_char('a'..='z'),
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}'),
+            RangeSet::<Char>::from_regex("[\\x00a-z0-9\\u{10FFFF}]").unwrap(),
+            RangeSet::<Char>::from_regex("\\w").unwrap(),
+        ];
+        for range in ranges {
+            let complement = range.complement();
+            assert_eq!(
+                complement.0.len() / 2,
+                count_complement_ranges(&range, Char::max_value())
+            );
+            let bmp = RangeSet::new_from_range_char('\0'..='\u{FFFF}');
+            assert_eq!(
+                bmp.difference(&range).0.len() / 2,
+                count_complement_ranges(&range.intersection(&bmp), Char::new('\u{FFFF}'))
+            );
+
+            if !range.is_empty() && !range.is_total() {
+                let expected = if complement.0.len() < range.0.len() {
+                    write_class(&complement, true, &RegexOptions::default())
+                } else {
+                    write_class(&range, false, &RegexOptions::default())
+                };
+                assert_eq!(expected, convert_to_regex(&range, &RegexOptions::default()));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-scripts")]
+    #[test]
+    fn test_explain_emission() -> Result<(), String> {
+        assert_eq!(EmissionPlan::Empty, explain_emission(&RangeSet::empty()));
+        assert_eq!(
+            EmissionPlan::DirectBracket { range_count: 1 },
+            explain_emission(&RangeSet::total())
+        );
+        assert_eq!("[\\u{0000}-\\u{10ffff}]", RangeSet::total().to_regex());
+        assert_eq!(EmissionPlan::Dot, explain_emission(&RangeSet::dot()));
+        assert_eq!(".", RangeSet::dot().to_regex());
+        assert_eq!(
+            EmissionPlan::SingleChar { c: 'a' },
+            explain_emission(&RangeSet::new_from_range_char('a'..='a'))
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+        assert_eq!(
+            EmissionPlan::PerlClass {
+                shorthand: "\\d".to_owned()
+            },
+            explain_emission(&range)
+        );
+        assert_eq!(
+            EmissionPlan::PerlClass {
+                shorthand: "\\D".to_owned()
+            },
+            explain_emission(&range.complement())
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\P{Greek}").unwrap();
+        assert_eq!(
+            EmissionPlan::NamedClass {
+                name: "Greek".to_owned(),
+                negated: true
+            },
+            explain_emission(&range)
+        );
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!(
+            EmissionPlan::DirectBracket { range_count: 1 },
+            explain_emission(&range)
+        );
+
+        let range = RangeSet::<Char>::from_regex("[^a-z0-9_]").unwrap();
+        assert_eq!(
+            EmissionPlan::ComplementBracket { range_count: 3 },
+            explain_emission(&range)
+        );
+        assert_eq!("[^0-9_a-z]", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cost_fn() -> Result<(), String> {
+        let options = |cost_fn| RegexOptions {
+            cost_fn,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('b'..=char::MAX);
+        assert_eq!(
+            "[b-\\u{10ffff}]",
+            range.to_regex_with(&options(CostModel::RangeCount))
+        );
+        assert_eq!(
+            "[^\\u{0000}-a]",
+            range.to_regex_with(&options(CostModel::StringLength))
+        );
+        assert_eq!(
+            "[^\\u{0000}-a]",
+            range.to_regex_with(&options(CostModel::TransitionCount))
+        );
+
+        let range = RangeSet::new_from_range_char('\u{80}'..=char::MAX)
+            .union(&RangeSet::new_from_range_char('a'..='a'));
+        let direct = "[a\\u{0080}-\\u{10ffff}]";
+        assert_eq!(direct, range.to_regex_with(&options(CostModel::RangeCount)));
+        assert_eq!(
+            direct,
+            range.to_regex_with(&options(CostModel::StringLength))
+        );
+        assert_eq!(
+            "[^\\u{0000}-`b-\\u{007f}]",
+            range.to_regex_with(&options(CostModel::TransitionCount))
+        );
+
+        assert_eq!(0, count_utf8_sequences(&RangeSet::empty()));
+        assert_eq!(
+            1,
+            count_utf8_sequences(&RangeSet::new_from_range_char('\0'..='\u{7F}'))
+        );
+        assert_eq!(
+            2,
+            count_utf8_sequences(&RangeSet::new_from_range_char('\0'..='\u{7FF}'))
+        );
+        assert_eq!(9, count_utf8_sequences(&RangeSet::total()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_context() -> Result<(), String> {
+        let options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            quote_context: true,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('.'..='.');
+        assert_eq!("\\.", range.to_regex());
+        assert_eq!(".", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('['..='[');
+        assert_eq!("[", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\\'..='\\');
+        assert_eq!("\\E\\\\\\Q", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!("\\E[a-z]\\Q", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape_padding() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\u{5}'..='\u{5}');
+        let with_style = |escape_style| RegexOptions {
+            escape_style,
+            ..Default::default()
+        };
+
+        assert_eq!("\\u{0005}", range.to_regex());
+        assert_eq!(
+            "\\x05",
+            range.to_regex_with(&with_style(EscapeStyle::HexByte))
+        );
+        assert_eq!(
+            "\\u0005",
+            range.to_regex_with(&with_style(EscapeStyle::JavaScriptUnicode))
+        );
+        assert_eq!(
+            range,
+            RangeSet::from_regex(&range.to_regex_with(&with_style(EscapeStyle::JavaScriptUnicode)))
+                .unwrap()
+        );
+
+        let range = RangeSet::new_from_range_char('é'..='€');
+        assert_eq!(
+            "[\\u00e9-\\u20ac]",
+            range.to_regex_with(&with_style(EscapeStyle::JavaScriptUnicode))
+        );
+
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F600}');
+        assert_eq!(
+            "\\u{1f600}",
+            range.to_regex_with(&with_style(EscapeStyle::JavaScriptUnicode))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ecmascript() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_regex("[\\u{b}\\u{8}0-9\\u{e9}]").unwrap();
+        assert_eq!(
+            Ok("[\\u0008\\v0-9\\u00e9]".to_owned()),
+            range.to_regex_checked(RegexFlavor::EcmaScript)
+        );
+
+        let range = RangeSet::new_from_range_char('a'..='a').complement();
+        assert_eq!(
+            Err(LossyError::UnsupportedChar('\u{10000}')),
+            range.to_regex_checked(RegexFlavor::EcmaScript)
+        );
+        assert_eq!(
+            Ok("[]".to_owned()),
+            RangeSet::empty().to_regex_checked(RegexFlavor::EcmaScript)
+        );
+
+        // The surrogates are not members.
+        let range = RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}');
+        assert_eq!(
+            Ok("[\\ud7ff\\ue000]".to_owned()),
+            range.to_regex_checked(RegexFlavor::EcmaScript)
+        );
+        let range = RangeSet::new_from_range_char('\0'..='\u{FFFF}')
+            .difference(&RangeSet::new_from_range_char('a'..='a'));
+        assert_eq!(
+            Ok("[\\u0000-`b-\\ud7ff\\ue000-\\uffff]".to_owned()),
+            range.to_regex_checked(RegexFlavor::EcmaScript)
+        );
+
+        let options = RegexOptions {
+            flavor: RegexFlavor::EcmaScript,
+            ..Default::default()
+        };
+        assert_eq!("[\\s\\S]", RangeSet::total().to_regex_with(&options));
+        assert_eq!("[^\\n]", RangeSet::dot().to_regex_with(&options));
+        let options = RegexOptions {
+            flavor: RegexFlavor::EcmaScript,
+            assume_dotall: false,
+            always_bracket: true,
+            ..Default::default()
+        };
+        assert_eq!("[\\s\\S]", RangeSet::total().to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_regex_delta() -> Result<(), String> {
+        let options = RegexOptions {
+            use_set_ops: true,
+            ..Default::default()
+        };
+        let digit = convert_from_range(perl_decimal::DECIMAL_NUMBER);
+        let word = convert_from_range(perl_word::PERL_WORD);
+
+        let range = digit.union(&RangeSet::new_from_range_char('.'..='.'));
+        assert_eq!("[\\d\\.]", range.to_regex_delta(&digit, &options));
+
+        let range = word.difference(&RangeSet::new_from_range_char('_'..='_'));
+        assert_eq!("[\\w&&[^_]]", range.to_regex_delta(&word, &options));
+
+        let range = word
+            .union(&RangeSet::new_from_range_char('-'..='-'))
+            .difference(&RangeSet::new_from_range_char('a'..='c'));
+        assert_eq!("[\\w\\-&&[^a-c]]", range.to_regex_delta(&word, &options));
+
+        assert_eq!("\\w", word.to_regex_delta(&word, &options));
+
+        let base = RangeSet::new_from_range_char('a'..='z');
+        let range = base
+            .union(&RangeSet::new_from_range_char('&'..='&'))
+            .difference(&RangeSet::new_from_range_char('q'..='q'));
+        assert_eq!("[[a-z]\\&&&[^q]]", range.to_regex_delta(&base, &options));
+        let range = base.union(&RangeSet::new_from_range_char('~'..='~'));
+        assert_eq!("[[a-z]\\~]", range.to_regex_delta(&base, &options));
+
+        let range = word.difference(&RangeSet::new_from_range_char('\n'..='\n'));
+        assert_eq!("\\w", range.to_regex_delta(&word, &options));
+        let dot = RangeSet::<Char>::dot();
+        let range = dot.difference(&RangeSet::new_from_range_char('a'..='a'));
+        assert_eq!("[[^\\n]&&[^a]]", range.to_regex_delta(&dot, &options));
+        assert_eq!(
+            range.to_regex(),
+            range.to_regex_delta(&word, &RegexOptions::default())
+        );
+
+        // `&&` is literal in the other flavors.
+        let range = word.difference(&RangeSet::new_from_range_char('_'..='_'));
+        let options = RegexOptions {
+            flavor: RegexFlavor::Posix,
+            ..options
+        };
+        assert_eq!(
+            range.to_regex_with(&options),
+            range.to_regex_delta(&word, &options)
+        );
+        assert!(!range.to_regex_delta(&word, &options).contains("&&"));
+        let options = RegexOptions {
+            flavor: RegexFlavor::RustBytes,
+            ..options
+        };
+        assert_eq!("(?u:[\\w&&[^_]])", range.to_regex_delta(&word, &options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_uppercase_hex() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\u{e9}'..='\u{e9}');
+        assert_eq!("\\u{00e9}", range.to_regex());
+
+        let options = RegexOptions {
+            uppercase_hex: true,
+            ..Default::default()
+        };
+        assert_eq!("\\u{00E9}", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('\u{1fbca}'..='\u{1fbcf}');
+        assert_eq!("[\\u{1FBCA}-\\u{1FBCF}]", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_class_backspace() -> Result<(), String> {
+        let options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            use_class_backspace: true,
+            ..Default::default()
+        };
+
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('\u{8}')..=Char::new('\u{8}')),
+            AnyRange::from(Char::new('a')..=Char::new('a')),
+        ]);
+        assert_eq!("[\\ba]", range.to_regex_with(&options));
+        assert_eq!("[^\\ba]", range.complement().to_regex_with(&options));
+        assert_eq!("[\\u{0008}a]", range.to_regex());
+
+        let rust_options = RegexOptions {
+            use_class_backspace: true,
+            ..Default::default()
+        };
+        assert_eq!("[\\u{0008}a]", range.to_regex_with(&rust_options));
+
+        let range = RangeSet::new_from_range_char('\u{8}'..='\u{8}');
+        assert_eq!("\\u{0008}", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_braces() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('a')..=Char::new('a')),
+            AnyRange::from(Char::new('{')..=Char::new('{')),
+        ]);
+        assert_eq!("[a{]", range.to_regex());
+        assert_eq!("[^a{]", range.complement().to_regex());
+
+        let options = RegexOptions {
+            flavor: RegexFlavor::EcmaScript,
+            escape_style: EscapeStyle::JavaScriptUnicode,
+            ..Default::default()
+        };
+        assert_eq!("[a\\{]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('{'..='}');
+        assert_eq!("[{-}]", range.to_regex());
+        assert_eq!("[\\{-\\}]", range.to_regex_with(&options));
+
+        let range = RangeSet::new_from_range_char('{'..='{');
+        assert_eq!("\\{", range.to_regex());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-props")]
+    #[test]
+    fn test_fragmented_ranges() -> Result<(), String> {
+        let range = RangeSet(vec![
+            Char::new('a'),
+            Char::new('a'),
+            Char::new('b'),
+            Char::new('b'),
+            Char::new('c'),
+            Char::new('c'),
+            Char::new('d'),
+            Char::new('d'),
+        ]);
+        assert_eq!("[a-d]", range.to_regex());
+
+        let range = RangeSet(vec![
+            Char::new('x'),
+            Char::new('z'),
+            Char::new('a'),
+            Char::new('c'),
+            Char::new('b'),
+            Char::new('f'),
+        ]);
+        assert_eq!("[a-fx-z]", range.to_regex());
+
+        let range = RangeSet(vec![
+            Char::new('0'),
+            Char::new('4'),
+            Char::new('5'),
+            Char::new('9'),
+            Char::new('A'),
+            Char::new('F'),
+            Char::new('a'),
+            Char::new('f'),
+        ]);
+        assert_eq!("\\p{ASCII_Hex_Digit}", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicated_ranges() -> Result<(), String> {
+        let range = RangeSet(vec![
+            Char::new('a'),
+            Char::new('a'),
+            Char::new('a'),
+            Char::new('a'),
+            Char::new('x'),
+            Char::new('z'),
+            Char::new('-'),
+            Char::new('-'),
+            Char::new('x'),
+            Char::new('z'),
+            Char::new('\u{1F600}'),
+            Char::new('\u{1F600}'),
+            Char::new('\u{1F600}'),
+            Char::new('\u{1F600}'),
+        ]);
+        for (options, expected) in [
+            (RegexOptions::default(), "[ax-z\\u{1f600}-]"),
+            (
+                RegexOptions {
+                    flavor: RegexFlavor::Posix,
+                    ..Default::default()
+                },
+                "[ax-z\u{1F600}-]",
+            ),
+            (
+                RegexOptions {
+                    cost_fn: CostModel::StringLength,
+                    embed_classes: true,
+                    ..Default::default()
+                },
+                "[ax-z\\u{1f600}-]",
+            ),
+            (
+                RegexOptions {
+                    ascii_only: true,
+                    ..Default::default()
+                },
+                "(?:[\\-ax-z]|\\ud83d\\ude00)",
+            ),
+        ] {
+            assert_eq!(expected, range.to_regex_with(&options));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_control_escapes() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('\u{C}'..='\u{C}');
+        assert_eq!("\\f", range.to_regex());
+
+        let range = RangeSet::new_from_range_char('\t'..='\r');
+        assert_eq!("[\\t-\\r]", range.to_regex());
+
+        let range = RangeSet::new_from_range_char('\u{7}'..='\u{7}');
+        assert_eq!("\\u{0007}", range.to_regex());
+
+        let pcre_options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            ..Default::default()
+        };
+        assert_eq!("\\a", range.to_regex_with(&pcre_options));
+
+        let range = RangeSet::new_from_range_char('\u{1B}'..='\u{1B}');
+        assert_eq!("\\e", range.to_regex_with(&pcre_options));
+
+        let range = RangeSet::new_from_range_char('\u{B}'..='\u{C}');
+        assert_eq!("[\\v\\f]", range.to_regex());
+        assert_eq!("[\\u{000b}\\f]", range.to_regex_with(&pcre_options));
+
+        let hex_options = RegexOptions {
+            control_escape_style: ControlEscapeStyle::Hex,
+            ..Default::default()
+        };
+        let range = RangeSet::new_from_range_char('\n'..='\n');
+        assert_eq!("\\u{000a}", range.to_regex_with(&hex_options));
+
+        let range = RangeSet::new_from_range_char('\t'..='\r');
+        assert_eq!("[\\u{0009}-\\u{000d}]", range.to_regex_with(&hex_options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alternation_regex() -> Result<(), String> {
+        let sets = [
+            RangeSet::new_from_range_char('x'..='x'),
+            RangeSet::new_from_range_char('a'..='a'),
+            RangeSet::new_from_range_char('c'..='c'),
+        ];
+        assert_eq!("[acx]", RangeSet::alternation_regex(&sets));
+
+        let sets = [
+            RangeSet::<Char>::from_regex("[a-cx-z]").unwrap(),
+            RangeSet::<Char>::from_regex("[0-5A-F]").unwrap(),
+        ];
+        assert_eq!("(?:[a-cx-z]|[0-5A-F])", RangeSet::alternation_regex(&sets));
+
+        let sets = [
+            RangeSet::<Char>::from_regex("[a-cx-z]").unwrap(),
+            RangeSet::<Char>::from_regex("[0-5A-F]").unwrap(),
+            RangeSet::<Char>::from_regex("[d-f]").unwrap(),
+            RangeSet::new_from_range_char('!'..='!'),
+            RangeSet::empty(),
+        ];
+        assert_eq!(
+            "(?:[a-fx-z]|[0-5A-F]|!)",
+            RangeSet::alternation_regex(&sets)
+        );
+
+        assert_eq!("[]", RangeSet::alternation_regex(&[]));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-names")]
+    fn test_unicode_name_escape() -> Result<(), String> {
+        let options = RegexOptions {
+            escape_style: EscapeStyle::UnicodeName,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('α'..='α');
+        assert_eq!(
+            "\\N{GREEK SMALL LETTER ALPHA}",
+            range.to_regex_with(&options)
+        );
+
+        let range = RangeSet::new_from_range_char('\u{E000}'..='\u{E000}');
+        assert_eq!("\\N{U+E000}", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(feature = "unicode-names"))]
+    fn test_unicode_name_escape_fallback() -> Result<(), String> {
+        let options = RegexOptions {
+            escape_style: EscapeStyle::UnicodeName,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('α'..='α');
+        assert_eq!("\\N{U+03B1}", range.to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[cfg(all(
+        feature = "unicode-gencat",
+        feature = "unicode-props",
+        feature = "unicode-scripts"
+    ))]
+    #[test]
+    fn test_optimize_length() -> Result<(), String> {
+        let options = RegexOptions {
+            optimize_length: true,
+            ..Default::default()
+        };
+
+        // The property is found first but the class is shorter.
+        let range = RangeSet::<Char>::from_regex("\\p{ASCII_Hex_Digit}").unwrap();
+        assert_eq!("\\p{ASCII_Hex_Digit}", range.to_regex());
+        assert_eq!("[0-9A-Fa-f]", range.to_regex_with(&options));
+
+        // The Perl classes are embedded even if `embed_classes` is disabled.
+        let range = RangeSet::<Char>::from_regex("[\\d_]").unwrap();
+        assert!(range.to_regex().len() > 1000);
+        assert_eq!("[\\d_]", range.to_regex_with(&options));
+        let range = RangeSet::<Char>::from_regex("[^\\d\\-.]").unwrap();
+        assert_eq!("[^\\d.-]", range.to_regex_with(&options));
+
+        // The Perl class is shorter than the property whatever `prefer_perl_over_property`.
+        let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+        let property_options = RegexOptions {
+            prefer_perl_over_property: false,
+            ..Default::default()
+        };
+        assert_eq!("\\p{Nd}", range.to_regex_with(&property_options));
+        assert_eq!(
+            "\\d",
+            range.to_regex_with(&RegexOptions {
+                optimize_length: true,
+                ..property_options
+            })
+        );
+
+        let range = RangeSet::<Char>::from_regex("\\p{Greek}").unwrap();
+        assert_eq!("\\p{Greek}", range.to_regex_with(&options));
+        let range = RangeSet::<Char>::from_regex("[^a-z]").unwrap();
+        assert_eq!("[^a-z]", range.to_regex_with(&options));
+        assert_eq!(
+            "[\\d]",
+            RangeSet::<Char>::from_regex("\\d")
+                .unwrap()
+                .to_regex_with(&RegexOptions {
+                    always_bracket: true,
+                    ..options.clone()
+                })
+        );
+
+        let options = RegexOptions {
+            optimize_length: true,
+            allow_perl_classes: false,
+            ..Default::default()
+        };
+        let range = RangeSet::<Char>::from_regex("[\\d_]").unwrap();
+        assert_eq!(range.to_regex(), range.to_regex_with(&options));
+
+        // The Perl classes are ASCII-only in RE2 and JavaScript.
+        let digit = RangeSet::<Char>::from_regex("\\d").unwrap();
+        let digit_x = RangeSet::<Char>::from_regex("[\\dx]").unwrap();
+        for flavor in [RegexFlavor::Re2, RegexFlavor::EcmaScript] {
+            let options = RegexOptions {
+                flavor,
+                optimize_length: true,
+                ..Default::default()
+            };
+            for range in [&digit, &digit_x] {
+                let regex = range.to_regex_with(&options);
+                assert!(!regex.contains("\\d"), "{:?}: {}", flavor, regex);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rust_bytes() -> Result<(), String> {
+        let options = RegexOptions {
+            flavor: RegexFlavor::RustBytes,
+            ..Default::default()
+        };
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!("[a-z]", range.to_regex_with(&options));
+        let range = RangeSet::new_from_range_char('\0'..='\u{7F}');
+        assert_eq!("[\\u{0000}-\\u{007f}]", range.to_regex_with(&options));
+        let range = RangeSet::new_from_range_char('\u{7F}'..='\u{80}');
+        assert_eq!("(?u:[\\u{007f}\\u{0080}])", range.to_regex_with(&options));
+        let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}');
+        assert_eq!(
+            "(?u:[\\u{1f600}-\\u{1f64f}])",
+            range.to_regex_with(&options)
+        );
+        let range = RangeSet::new_from_range_char('a'..='z').complement();
+        assert_eq!("(?u:[^a-z])", range.to_regex_with(&options));
+        assert_eq!("(?u:.)", RangeSet::<Char>::dot().to_regex_with(&options));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-gencat")]
+    #[test]
+    fn test_negation_style() -> Result<(), String> {
+        use crate::options::NegationStyle;
+
+        let range = RangeSet::<Char>::from_regex("\\P{L}").unwrap();
+        assert_eq!("\\P{L}", range.to_regex());
+        let options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            negation_style: NegationStyle::CaretInside,
+            ..Default::default()
+        };
+        assert_eq!("\\p{^L}", range.to_regex_with(&options));
+        assert_eq!("\\p{L}", range.complement().to_regex_with(&options));
+
+        let range = RangeSet::<Char>::from_regex("\\P{Sc}").unwrap();
+        let options = RegexOptions {
+            flavor: RegexFlavor::Re2,
+            ..options
+        };
+        assert_eq!("\\p{^Sc}", range.to_regex_with(&options));
+
+        // The `regex` crate rejects `\p{^L}`.
+        let range = RangeSet::<Char>::from_regex("\\P{L}").unwrap();
+        let options = RegexOptions {
+            flavor: RegexFlavor::Rust,
+            ..options
+        };
+        assert_eq!("\\P{L}", range.to_regex_with(&options));
+
+        for flavor in [RegexFlavor::Pcre, RegexFlavor::Re2, RegexFlavor::Rust] {
+            let options = RegexOptions {
+                flavor,
+                ..options.clone()
+            };
+            for range in [&range, &range.complement()] {
+                let regex = range.to_regex_with(&options);
+                assert_eq!(
+                    *range,
+                    RangeSet::<Char>::from_regex(&regex).unwrap(),
+                    "{}",
+                    regex
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,19 @@
+mod binary;
+pub mod builder;
 pub mod char;
+mod emit;
+pub mod options;
+pub mod parser;
 mod tokens;
-use std::ops::{Bound, RangeBounds};
+use std::{
+    collections::BTreeSet,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
 
-use char::{Char, INVALID_MIN, INVALID_SIZE};
-use irange::{integer::Bounded, RangeSet};
-use tokens::identify_character;
+use char::{Char, INVALID_MIN, INVALID_SIZE, VALID_SIZE};
+use irange::{integer::Bounded, range::AnyRange, RangeSet};
+use options::{EmissionPlan, EmptyClassStyle, LossyError, NamedClass, RegexFlavor, RegexOptions};
+use parser::ParseError;
 
 pub use irange;
 
@@ -12,7 +21,8 @@ pub use irange;
 /// 
 /// # Example:
 /// 
-/// ```
+#[cfg_attr(feature = "unicode-props", doc = "```")]
+#[cfg_attr(not(feature = "unicode-props"), doc = "```ignore")]
 /// use regex_charclass::{irange::{RangeSet, range::AnyRange}, char::Char, CharacterClass};
 /// 
 /// let range1 = RangeSet::new_from_range_char('a'..='z');
@@ -30,7 +40,7 @@ pub use irange;
 /// assert_eq!("\\P{ASCII_Hex_Digit}", range2_complement.to_regex());
 /// 
 /// 
-/// assert_eq!(".", range2.union(&range2_complement).to_regex());
+/// assert_eq!("[\\u{0000}-\\u{10ffff}]", range2.union(&range2_complement).to_regex());
 /// assert_eq!("[]", range2.intersection(&range2_complement).to_regex());
 /// 
 /// assert_eq!("[g-z]", range1.difference(&range2).to_regex());
@@ -40,14 +50,210 @@ pub trait CharacterClass: Sized {
 
     fn new_from_range_char<R: RangeBounds<char>>(range: R) -> Self;
 
+    fn new_from_fn(f: impl Fn(char) -> bool) -> Self;
+
+    fn new_from_char(c: char) -> Self;
+
+    fn new_from_chars<I: IntoIterator<Item = char>>(chars: I) -> Self;
+
+    fn from_strings<'a>(strs: impl IntoIterator<Item = &'a str>) -> Self;
+
+    fn from_range_inclusives<I: IntoIterator<Item = RangeInclusive<char>>>(ranges: I) -> Self;
+
+    fn dot() -> Self;
+
+    fn whitespace() -> Self;
+
+    fn ascii_whitespace() -> Self;
+
+    /// Return the class of the `char` accepted by `char::is_alphabetic`, matched by `\p{Alphabetic}`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, char::Char, CharacterClass};
+    ///
+    /// let alphabetic = RangeSet::<Char>::alphabetic();
+    /// assert!(alphabetic.contains_char('é'));
+    /// assert!(!alphabetic.contains_char('1'));
+    /// ```
+    #[cfg(feature = "unicode-props")]
+    #[inline]
+    fn alphabetic() -> Self {
+        let table = tokens::unicode::property_bool::ALPHABETIC;
+        Self::from_range_inclusives(table.iter().map(|&(min, max)| min..=max))
+    }
+
+    /// Return the class of the `char` accepted by `char::is_numeric`, matched by `\p{N}`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, char::Char, CharacterClass};
+    ///
+    /// let numeric = RangeSet::<Char>::numeric();
+    /// assert!(numeric.contains_char('½'));
+    /// assert_eq!("\\p{N}", numeric.to_regex());
+    /// ```
+    #[cfg(feature = "unicode-gencat")]
+    #[inline]
+    fn numeric() -> Self {
+        let table = tokens::unicode::general_category::NUMBER;
+        Self::from_range_inclusives(table.iter().map(|&(min, max)| min..=max))
+    }
+
+    /// Return the class of the `char` accepted by `char::is_alphanumeric`, the union of `alphabetic` and `numeric`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, char::Char, CharacterClass};
+    ///
+    /// let alphanumeric = RangeSet::<Char>::alphanumeric();
+    /// assert!(alphanumeric.contains_char('é'));
+    /// assert!(alphanumeric.contains_char('½'));
+    /// assert!(!alphanumeric.contains_char('_'));
+    /// ```
+    #[cfg(all(feature = "unicode-props", feature = "unicode-gencat"))]
+    #[inline]
+    fn alphanumeric() -> Self {
+        let alphabetic = tokens::unicode::property_bool::ALPHABETIC;
+        let numeric = tokens::unicode::general_category::NUMBER;
+        let table = alphabetic.iter().chain(numeric);
+        Self::from_range_inclusives(table.map(|&(min, max)| min..=max))
+    }
+
+    /// Return the class of the `char` accepted by `char::is_control`, matched by `\p{Cc}`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, char::Char, CharacterClass};
+    ///
+    /// let control = RangeSet::<Char>::control();
+    /// assert!(control.contains_char('\n'));
+    /// assert_eq!("\\p{Cc}", control.to_regex());
+    /// ```
+    #[cfg(feature = "unicode-gencat")]
+    #[inline]
+    fn control() -> Self {
+        let table = tokens::unicode::general_category::CONTROL;
+        Self::from_range_inclusives(table.iter().map(|&(min, max)| min..=max))
+    }
+
     fn get_cardinality(&self) -> u32;
 
+    fn get_cardinality_u64(&self) -> u64;
+
+    fn specificity(&self) -> f64;
+
+    fn density(&self) -> f64;
+
     fn to_regex(&self) -> String;
+
+    fn to_regex_with(&self, options: &RegexOptions) -> String;
+
+    fn to_anchored_regex(&self) -> String;
+
+    fn to_anchored_regex_with(&self, options: &RegexOptions) -> String;
+
+    fn to_regex_delta(&self, base: &Self, options: &RegexOptions) -> String;
+
+    fn to_regex_posix(&self) -> Option<String>;
+
+    fn from_regex(regex: &str) -> Result<Self, ParseError>;
+
+    fn from_extended_regex(regex: &str) -> Result<Self, ParseError>;
+
+    fn alternation_regex(sets: &[Self]) -> String;
+
+    fn to_regex_set_patterns(sets: &[Self]) -> Vec<String>;
+
+    fn all_exact_names(&self) -> Vec<&'static str>;
+
+    fn named_class(&self) -> Option<NamedClass>;
+
+    fn union_preferring_named(sets: &[(&'static str, Self)]) -> (Self, Option<&'static str>);
+
+    fn to_regex_merged(&self, gap: u32) -> String;
+
+    fn contains_char(&self, c: char) -> bool;
+
+    fn contains_all_of_str(&self, s: &str) -> bool;
+
+    fn is_disjoint(&self, other: &Self) -> bool;
+
+    fn symmetric_difference(&self, other: &Self) -> Self;
+
+    fn in_plane(&self, plane: u8) -> Self;
+
+    fn case_fold(&self) -> Self;
+
+    fn simple_case_fold(&self) -> Self;
+
+    fn first_disallowed(&self, s: &str) -> Option<(usize, char)>;
+
+    fn min_utf8_bytes(&self) -> usize;
+
+    fn max_utf8_bytes(&self) -> usize;
+
+    fn range_inclusives(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_;
+
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_;
+
+    fn runs(&self) -> impl Iterator<Item = (char, char, u32)> + '_;
+
+    fn to_btree_set(&self, max_len: u32) -> Option<BTreeSet<char>>;
+
+    /// Return a `char` picked uniformly at random among the ones contained, or `None` if the class is empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use rand::{rngs::StdRng, SeedableRng};
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert!(range.sample(&mut rng).is_some_and(|c| c.is_ascii_lowercase()));
+    /// assert_eq!(None, RangeSet::empty().sample(&mut rng));
+    /// ```
+    #[cfg(feature = "rand")]
+    fn sample<R: rand::Rng>(&self, rng: &mut R) -> Option<char> {
+        let cardinality = self.get_cardinality_u64();
+        if cardinality == 0 {
+            return None;
+        }
+        // Picking an index among all the `char` weights each range by its cardinality.
+        self.nth_char(rng.gen_range(0..cardinality) as u32)
+    }
+
+    fn nth_char(&self, index: u32) -> Option<char>;
+
+    fn rank_char(&self, c: char) -> Option<u32>;
+
+    fn to_regex_checked(&self, flavor: RegexFlavor) -> Result<String, LossyError>;
+
+    fn to_regex_checked_with(&self, options: &RegexOptions) -> Result<String, LossyError>;
+
+    fn stable_hash_hex(&self) -> String;
+
+    fn prefers_class_over_alternation(&self, flavor: RegexFlavor) -> bool;
+
+    fn contains_code_point(&self, cp: u32) -> bool;
+
+    fn to_bytes(&self) -> Vec<u8>;
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+    fn explain_emission(&self) -> EmissionPlan;
 }
 
 impl CharacterClass for RangeSet<Char> {
     /// Create a new instance from the given range of `u32`, return `None` if the `char` codes are invalid.
     ///
+    /// The surrogates U+D800 to U+DFFF are not `char`, so an excluded bound next to them skips the gap.
+    ///
     /// # Example:
     ///
     /// ```
@@ -57,13 +263,19 @@ impl CharacterClass for RangeSet<Char> {
     /// ```
     #[inline]
     fn new_from_range_u32<R: RangeBounds<u32>>(range: R) -> Option<Self> {
-        let min = to_lowerbound_u32(range.start_bound())?;
-        let max = to_upperbound_u32(range.end_bound())?;
+        let min = to_lowerbound_u32(range.start_bound().cloned())?;
+        let max = to_upperbound_u32(range.end_bound().cloned())?;
 
         Some(RangeSet::new_from_range(min..=max))
     }
 
-    /// Create a new instance from the given range of `char`.
+    /// Create a new instance from the given range of `char`, an unbounded end stands for U+10FFFF.
+    ///
+    /// Since the 2,048 surrogates U+D800 to U+DFFF are not `char`, a range over them holds
+    /// 2,048 fewer `char` than its distance: `'a'..` holds `0x10FFFF - 0x61 + 1 - 0x800` of them.
+    ///
+    /// `..` holds every `char`, which `to_regex` writes as the full range `[\u{0000}-\u{10ffff}]`
+    /// since `.` excludes `\n` without the `s` flag, and as `.` only with `RegexOptions::assume_dotall`.
     ///
     /// # Example:
     ///
@@ -71,17 +283,187 @@ impl CharacterClass for RangeSet<Char> {
     /// use regex_charclass::{irange::RangeSet, CharacterClass};
     ///  
     /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!(0x10FFFF - 0x61 + 1 - 0x800, RangeSet::new_from_range_char('a'..).get_cardinality());
     /// ```
     #[inline]
     fn new_from_range_char<R: RangeBounds<char>>(range: R) -> Self {
-        let min = to_lowerbound_char(range.start_bound());
-        let max = to_upperbound_char(range.end_bound());
+        let min = to_lowerbound_u32(range.start_bound().map(|c| *c as u32));
+        let max = to_upperbound_u32(range.end_bound().map(|c| *c as u32));
+
+        match (min, max) {
+            (Some(min), Some(max)) => RangeSet::new_from_range(min..=max),
+            _ => RangeSet::empty(),
+        }
+    }
+
+    /// Create a new instance holding every `char` for which the given predicate returns `true`.
+    ///
+    /// The predicate is called on each of the 1,112,064 valid `char`,
+    /// so this is meant for a one-time setup rather than a hot path.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///  
+    /// let range = RangeSet::new_from_fn(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    /// assert_eq!("[0-9a-z]", range.to_regex());
+    /// ```
+    fn new_from_fn(f: impl Fn(char) -> bool) -> Self {
+        let mut ranges: Vec<Char> = vec![];
+        let mut previous_included = false;
+        for c in '\0'..=char::MAX {
+            let included = f(c);
+            if included {
+                if previous_included {
+                    if let Some(max) = ranges.last_mut() {
+                        *max = Char::new(c);
+                    }
+                } else {
+                    ranges.push(Char::new(c));
+                    ranges.push(Char::new(c));
+                }
+            }
+            previous_included = included;
+        }
+        RangeSet(ranges)
+    }
+
+    /// Create a new instance holding the given `char`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_char('a');
+    /// assert_eq!("a", range.to_regex());
+    /// ```
+    #[inline]
+    fn new_from_char(c: char) -> Self {
+        RangeSet::new_from_range_char(c..=c)
+    }
+
+    /// Create a new instance holding the given `char`, in any order and possibly repeated.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_chars(['x', 'c', 'a', 'b', 'a']);
+    /// assert_eq!("[a-cx]", range.to_regex());
+    /// ```
+    fn new_from_chars<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        let mut chars = chars.into_iter().map(Char::new).collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut ranges: Vec<Char> = Vec::with_capacity(chars.len() * 2);
+        for c in chars {
+            match ranges.last_mut() {
+                Some(max) if *max + Char::one() == c => *max = c,
+                _ => {
+                    ranges.push(c);
+                    ranges.push(c);
+                }
+            }
+        }
+        RangeSet(ranges)
+    }
+
+    /// Create a new instance holding every `char` of the given strings.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::from_strings(["abc", "xyz"]);
+    /// assert_eq!("[a-cx-z]", range.to_regex());
+    /// ```
+    #[inline]
+    fn from_strings<'a>(strs: impl IntoIterator<Item = &'a str>) -> Self {
+        RangeSet::new_from_chars(strs.into_iter().flat_map(str::chars))
+    }
+
+    /// Create a new instance from the given ranges of `char`, the empty ones are ignored.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::from_range_inclusives(['a'..='f', '0'..='9']);
+    /// assert_eq!("[0-9a-f]", range.to_regex());
+    /// ```
+    fn from_range_inclusives<I: IntoIterator<Item = RangeInclusive<char>>>(ranges: I) -> Self {
+        let ranges = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| AnyRange::from(Char::new(*range.start())..=Char::new(*range.end())))
+            .collect::<Vec<_>>();
+        RangeSet::new_from_ranges(&ranges)
+    }
+
+    /// Return the class of every `char` except `\n`, matched by `.` without the `s` flag.
+    ///
+    /// It is written `.`, while the total class is written as the full range,
+    /// unless `RegexOptions::assume_dotall` is enabled in which case this one is written `[^\n]`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///  
+    /// let dot = RangeSet::<Char>::dot();
+    /// assert!(!dot.contains(Char::new('\n')));
+    /// assert_eq!(".", dot.to_regex());
+    /// ```
+    #[inline]
+    fn dot() -> Self {
+        RangeSet::new_from_range_char('\n'..='\n').complement()
+    }
+
+    /// Return the class of the Unicode whitespace, matched by `\s`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let whitespace = RangeSet::<Char>::whitespace();
+    /// assert!(whitespace.contains_char('\u{A0}'));
+    /// assert_eq!("\\s", whitespace.to_regex());
+    /// ```
+    #[inline]
+    fn whitespace() -> Self {
+        tokens::convert_from_range(tokens::unicode::perl_space::WHITE_SPACE)
+    }
 
-        RangeSet::new_from_range(min..=max)
+    /// Return the class of the ASCII whitespace: `\t`, `\n`, `\v`, `\f`, `\r` and space.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let whitespace = RangeSet::<Char>::ascii_whitespace();
+    /// assert!(!whitespace.contains_char('\u{A0}'));
+    /// assert_eq!("[\\t-\\r ]", whitespace.to_regex());
+    /// ```
+    #[inline]
+    fn ascii_whitespace() -> Self {
+        RangeSet::new_from_ranges(&[
+            AnyRange::from(Char::new('\t')..=Char::new('\r')),
+            AnyRange::from(Char::new(' ')..=Char::new(' ')),
+        ])
     }
 
     /// Return the number of possible `char` contained.
     ///
+    /// It saturates at `u32::MAX` for a `RangeSet` with overlapping ranges, see `get_cardinality_u64`.
+    ///
     /// # Example:
     ///
     /// ```
@@ -92,26 +474,78 @@ impl CharacterClass for RangeSet<Char> {
     /// ```
     #[inline]
     fn get_cardinality(&self) -> u32 {
+        u32::try_from(self.get_cardinality_u64()).unwrap_or(u32::MAX)
+    }
+
+    /// Return the number of possible `char` contained, as a `u64` which can't overflow.
+    ///
+    /// Overlapping ranges are counted as many times as they appear and reversed ranges are ignored,
+    /// as they can be built from the public field of `RangeSet`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// assert_eq!(1_112_064, RangeSet::<Char>::total().get_cardinality_u64());
+    /// ```
+    fn get_cardinality_u64(&self) -> u64 {
         let mut cardinality = 0;
         for r in (0..self.0.len()).step_by(2) {
-            let mut minuhend = self.0[r + 1].to_u32();
-            if minuhend >= INVALID_MIN {
-                minuhend -= INVALID_SIZE;
-            }
-            let mut subtrahend = self.0[r].to_u32();
-            if subtrahend >= INVALID_MIN {
-                subtrahend -= INVALID_SIZE;
+            let (minuhend, subtrahend) = (to_index(self.0[r + 1]), to_index(self.0[r]));
+            if minuhend >= subtrahend {
+                cardinality += u64::from(minuhend - subtrahend) + 1;
             }
-            cardinality += minuhend - subtrahend + 1;
         }
         cardinality
     }
 
-    /// Return a valid regular expression character class.
+    /// Return how specific the class is, from `0.0` for the total class to `1.0` for the empty class.
+    ///
+    /// It is computed as `1 - cardinality / 1_112_064`, 1,112,064 being the number of valid `char`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///  
+    /// assert_eq!(0.0, RangeSet::<Char>::total().specificity());
+    /// assert!(RangeSet::new_from_range_char('a'..='z').specificity() > 0.99);
+    /// ```
+    #[inline]
+    fn specificity(&self) -> f64 {
+        1.0 - self.get_cardinality() as f64 / VALID_SIZE as f64
+    }
+
+    /// Return how contiguous the class is, from `1.0` for a single range down to `0.0` for the empty class.
+    ///
+    /// It is computed as the cardinality divided by the number of `char` between the smallest and the greatest member.
     ///
     /// # Example:
     ///
     /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// assert_eq!(1.0, RangeSet::new_from_range_char('a'..='z').density());
+    /// assert_eq!(2.0 / 3.0, RangeSet::<Char>::from_regex("[ac]").unwrap().density());
+    /// ```
+    #[inline]
+    fn density(&self) -> f64 {
+        match (self.0.first(), self.0.last()) {
+            (Some(min), Some(max)) => {
+                self.get_cardinality() as f64
+                    / RangeSet::new_from_range(*min..=*max).get_cardinality() as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Return a valid regular expression character class.
+    ///
+    /// # Example:
+    ///
+    #[cfg_attr(feature = "unicode-props", doc = "```")]
+    #[cfg_attr(not(feature = "unicode-props"), doc = "```ignore")]
     /// use regex_charclass::{irange::{RangeSet, range::AnyRange}, char::Char, CharacterClass};
     ///  
     /// let range = RangeSet::new_from_range_char('a'..='z');
@@ -126,174 +560,1117 @@ impl CharacterClass for RangeSet<Char> {
     /// ```
     #[inline]
     fn to_regex(&self) -> String {
-        let range = self.clone();
-        if self.is_empty() {
-            String::from("[]")
-        } else if range.is_total() {
-            String::from(".")
-        } else if let Some(token) = tokens::identify_class(self) {
-            token.to_owned()
-        } else {
-            convert_to_regex(&range)
-        }
+        self.to_regex_with(&RegexOptions::default())
     }
-}
 
-fn to_lowerbound_u32(bound: Bound<&u32>) -> Option<Char> {
-    match bound {
-        Bound::Included(t) => Char::from_u32(*t),
-        Bound::Excluded(t) => {
-            char::from_u32(*t)?;
-
-            if let Some(c) = Char::from_u32(*t + 1) {
-                Some(c)
-            } else {
-                Some(Char::new('\u{E000}'))
-            }
-        }
-        Bound::Unbounded => Some(Char::min_value()),
+    /// Return a valid regular expression character class rendered with the given options.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::{EscapeStyle, RegexOptions}, CharacterClass};
+    ///
+    /// let options = RegexOptions {
+    ///     escape_style: EscapeStyle::NamedUnicode,
+    ///     ..Default::default()
+    /// };
+    ///  
+    /// let range = RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}');
+    /// assert_eq!("[\\N{U+1F600}-\\N{U+1F64F}]", range.to_regex_with(&options));
+    /// ```
+    #[inline]
+    fn to_regex_with(&self, options: &RegexOptions) -> String {
+        emit::to_regex(self, options)
     }
-}
 
-fn to_upperbound_u32(bound: Bound<&u32>) -> Option<Char> {
-    match bound {
-        Bound::Included(t) => Char::from_u32(*t),
-        Bound::Excluded(t) => {
-            char::from_u32(*t)?;
+    /// Return a regular expression matching exactly one `char` of the class, and nothing else.
+    ///
+    /// The `s` flag is not assumed and the empty class is written `[^\u{0000}-\u{10ffff}]`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!("^[a-z]$", range.to_anchored_regex());
+    /// ```
+    #[inline]
+    fn to_anchored_regex(&self) -> String {
+        let options = RegexOptions {
+            empty_as: EmptyClassStyle::NegatedTotal,
+            ..Default::default()
+        };
+        emit::to_anchored_regex(self, &options)
+    }
 
-            if let Some(c) = Char::from_u32(*t - 1) {
-                Some(c)
-            } else {
-                Some(Char::new('\u{D7FF}'))
+    /// Return a regular expression matching exactly one `char` of the class rendered with the given options.
+    ///
+    /// It is anchored with `\A` and `\z` for `RegexFlavor::Pcre`, where `$` also matches before a final `\n`,
+    /// and with `^` and `$` otherwise. `RegexOptions::assume_dotall` is ignored since no flag is set.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::{RegexFlavor, RegexOptions}, CharacterClass};
+    ///
+    /// let options = RegexOptions {
+    ///     flavor: RegexFlavor::Pcre,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!("\\A[a-z]\\z", range.to_anchored_regex_with(&options));
+    /// ```
+    #[inline]
+    fn to_anchored_regex_with(&self, options: &RegexOptions) -> String {
+        emit::to_anchored_regex(self, options)
+    }
+
+    /// Return a regular expression character class expressing `self` as `base` plus additions and minus removals.
+    ///
+    /// The output uses nested classes and the `&&` operator, so it is only produced when `use_set_ops` is enabled
+    /// for `RegexFlavor::Rust` or `RegexFlavor::RustBytes`, otherwise the plain `to_regex_with` output is returned.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::RegexOptions, CharacterClass};
+    ///
+    /// let options = RegexOptions {
+    ///     use_set_ops: true,
+    ///     ..Default::default()
+    /// };
+    ///  
+    /// let base = RangeSet::new_from_range_char('a'..='z');
+    /// let range = base.difference(&RangeSet::new_from_range_char('q'..='q'));
+    /// assert_eq!("[[a-z]&&[^q]]", range.to_regex_delta(&base, &options));
+    /// ```
+    #[inline]
+    fn to_regex_delta(&self, base: &Self, options: &RegexOptions) -> String {
+        emit::to_regex_delta(self, base, options)
+    }
+
+    /// Return the POSIX bracket class such as `[[:alpha:]]` or `[^[:alpha:]]` equal to the class,
+    /// `None` if there is none.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('0'..='9');
+    /// assert_eq!(Some("[[:digit:]]".to_owned()), range.to_regex_posix());
+    /// assert_eq!(Some("[^[:digit:]]".to_owned()), range.complement().to_regex_posix());
+    /// assert_eq!(None, RangeSet::new_from_range_char('a'..='f').to_regex_posix());
+    /// ```
+    fn to_regex_posix(&self) -> Option<String> {
+        let range = emit::normalize(self);
+        if let Some(class) = tokens::find_posix_class(&tokens::convert_to_range(&range)) {
+            return Some(format!("[[:{}:]]", class));
+        }
+        tokens::find_posix_class(&tokens::convert_to_range(&range.complement()))
+            .map(|class| format!("[^[:{}:]]", class))
+    }
+
+    /// Parse the given regular expression character class.
+    ///
+    /// Bracket classes, including nested and POSIX classes such as `[[:alpha:]0-9]`, escaped `char`,
+    /// Perl classes and Unicode properties are supported.
+    ///
+    /// This stands for `str::parse`, since `FromStr` can't be implemented here for `RangeSet` which is defined in `irange`.
+    ///
+    /// # Example:
+    ///
+    #[cfg_attr(feature = "unicode-props", doc = "```")]
+    #[cfg_attr(not(feature = "unicode-props"), doc = "```ignore")]
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::<Char>::from_regex("[[:digit:]a-f]").unwrap();
+    /// assert_eq!("[0-9a-f]", range.to_regex());
+    ///
+    /// let range = RangeSet::<Char>::from_regex("\\P{ASCII_Hex_Digit}").unwrap();
+    /// assert!(!range.contains(Char::new('a')));
+    ///
+    /// fn load(value: &str) -> Result<RangeSet<Char>, Box<dyn std::error::Error>> {
+    ///     Ok(RangeSet::from_regex(value)?)
+    /// }
+    /// assert_eq!("unterminated class starting at 0", load("[a-z").unwrap_err().to_string());
+    /// ```
+    #[inline]
+    fn from_regex(regex: &str) -> Result<Self, ParseError> {
+        parser::parse(regex, false)
+    }
+
+    /// Parse a character class written in extended mode, as with the `x` flag.
+    ///
+    /// The whitespace and the `#` comments up to the end of the line are ignored around the class,
+    /// but are literal inside brackets as in PCRE.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::<Char>::from_extended_regex("  [a-z ] # lowercase or space\n").unwrap();
+    /// assert_eq!("[ a-z]", range.to_regex());
+    /// ```
+    #[inline]
+    fn from_extended_regex(regex: &str) -> Result<Self, ParseError> {
+        parser::parse(regex, true)
+    }
+
+    /// Return the representation chosen by `to_regex`, to understand why it produced its output.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::EmissionPlan, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!(EmissionPlan::DirectBracket { range_count: 1 }, range.explain_emission());
+    /// assert_eq!(
+    ///     EmissionPlan::ComplementBracket { range_count: 1 },
+    ///     range.complement().explain_emission()
+    /// );
+    /// ```
+    #[inline]
+    fn explain_emission(&self) -> EmissionPlan {
+        emit::explain_emission(self)
+    }
+
+    /// Return a valid regular expression character class for the given flavor,
+    /// or an error if it can't be written faithfully.
+    ///
+    /// The empty class is written `[^\u{0000}-\u{10ffff}]` when the flavor has escapes,
+    /// the code points are escaped as `\x{...}` for `RegexFlavor::Pcre` unless `escape_style` is one PCRE2 understands,
+    /// and for `RegexFlavor::Posix` only the ASCII `char` from U+0001 can be written literally.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::{LossyError, RegexFlavor}, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('é'..='é');
+    /// assert_eq!(Ok("\\u{00e9}".to_owned()), range.to_regex_checked(RegexFlavor::Rust));
+    /// assert_eq!(Err(LossyError::UnsupportedChar('é')), range.to_regex_checked(RegexFlavor::Posix));
+    /// ```
+    #[inline]
+    fn to_regex_checked(&self, flavor: RegexFlavor) -> Result<String, LossyError> {
+        let options = RegexOptions {
+            flavor,
+            ..Default::default()
+        };
+        emit::to_regex_checked(self, &options)
+    }
+
+    /// Return a valid regular expression character class rendered with the given options,
+    /// or an error if it can't be written faithfully.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::{LossyError, RegexOptions}, CharacterClass};
+    ///
+    /// let options = RegexOptions {
+    ///     max_code_point: Some(0xFFFF),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='\u{1F64F}');
+    /// assert_eq!("[a-\\u{ffff}]", range.to_regex_with(&options));
+    /// assert_eq!(Err(LossyError::UnsupportedChar('\u{10000}')), range.to_regex_checked_with(&options));
+    /// ```
+    #[inline]
+    fn to_regex_checked_with(&self, options: &RegexOptions) -> Result<String, LossyError> {
+        emit::to_regex_checked(self, options)
+    }
+
+    /// Return `true` if a character class is preferable to an alternation of literals to match a member.
+    ///
+    /// This is the case unless the class has members written with several code units in the flavor,
+    /// i.e. astral `char` in `RegexFlavor::EcmaScript` which have to be written as surrogate pairs.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, options::RegexFlavor, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('😀'..='😂');
+    /// assert!(range.prefers_class_over_alternation(RegexFlavor::Rust));
+    /// assert!(!range.prefers_class_over_alternation(RegexFlavor::EcmaScript));
+    /// ```
+    #[inline]
+    fn prefers_class_over_alternation(&self, flavor: RegexFlavor) -> bool {
+        flavor != RegexFlavor::EcmaScript || self.max_utf8_bytes() < 4
+    }
+
+    /// Return a hash of the class as 16 hexadecimal digits, meant for snapshot tests.
+    ///
+    /// The hash is the 64-bit FNV-1a of the bounds of the coalesced ranges as little-endian `u32`,
+    /// so it does not change between runs, platforms or versions.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!("a69c0e4390b010de", range.stable_hash_hex());
+    /// ```
+    fn stable_hash_hex(&self) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for bound in emit::normalize(self).0.iter() {
+            for byte in bound.to_u32().to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// Return a compact binary encoding of the class, made of the varint deltas between the bounds.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::<Char>::from_regex("[a-z\\x{370}-\\x{3ff}]").unwrap();
+    /// let bytes = range.to_bytes();
+    /// assert_eq!(Some(range), RangeSet::from_bytes(&bytes));
+    /// ```
+    #[inline]
+    fn to_bytes(&self) -> Vec<u8> {
+        binary::to_bytes(self)
+    }
+
+    /// Create a new instance from bytes written by `to_bytes`, return `None` if they are invalid.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!(Some(range), RangeSet::from_bytes(&[0x61, 0x19]));
+    /// assert_eq!(None, RangeSet::from_bytes(&[0x61]));
+    /// ```
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        binary::from_bytes(bytes)
+    }
+
+    /// Return a regular expression matching a `char` of any of the given classes.
+    ///
+    /// Single `char` are merged in one class, as are classes that overlap or are adjacent,
+    /// the others are kept as the branches of a non-capturing alternation.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let sets = [
+    ///     RangeSet::new_from_range_char('a'..='a'),
+    ///     RangeSet::new_from_range_char('c'..='c'),
+    /// ];
+    /// assert_eq!("[ac]", RangeSet::alternation_regex(&sets));
+    /// ```
+    #[inline]
+    fn alternation_regex(sets: &[Self]) -> String {
+        emit::alternation_regex(sets, &RegexOptions::default())
+    }
+
+    /// Return one unanchored pattern per class, in the same order, to be given to `regex::RegexSet::new`.
+    ///
+    /// The empty class is written `[^\u{0000}-\u{10ffff}]` so that every pattern compiles,
+    /// and `.` is only used for the class of every `char` except `\n` since the `s` flag is not set.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let sets = [
+    ///     RangeSet::new_from_range_char('a'..='z'),
+    ///     RangeSet::new_from_range_char('0'..='9'),
+    ///     RangeSet::empty(),
+    /// ];
+    /// assert_eq!(
+    ///     vec!["[a-z]", "[0-9]", "[^\\u{0000}-\\u{10ffff}]"],
+    ///     RangeSet::to_regex_set_patterns(&sets)
+    /// );
+    /// ```
+    #[inline]
+    fn to_regex_set_patterns(sets: &[Self]) -> Vec<String> {
+        let options = RegexOptions {
+            empty_as: EmptyClassStyle::NegatedTotal,
+            assume_dotall: false,
+            ..Default::default()
+        };
+        sets.iter()
+            .map(|set| emit::to_regex(set, &options))
+            .collect()
+    }
+
+    /// Return every Unicode general category, binary property and script name exactly equal to the class, sorted.
+    ///
+    /// `to_regex` writes only one of them when several apply.
+    ///
+    /// # Example:
+    ///
+    #[cfg_attr(feature = "unicode-scripts", doc = "```")]
+    #[cfg_attr(not(feature = "unicode-scripts"), doc = "```ignore")]
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::<Char>::from_regex("\\p{Greek}").unwrap();
+    /// assert_eq!(vec!["Greek"], range.all_exact_names());
+    /// assert!(RangeSet::new_from_range_char('a'..='z').all_exact_names().is_empty());
+    /// ```
+    fn all_exact_names(&self) -> Vec<&'static str> {
+        tokens::find_all_classes(&tokens::convert_to_range(self))
+    }
+
+    /// Return the Perl class or the Unicode property exactly equal to the class or to its complement,
+    /// as written by `to_regex`.
+    ///
+    /// # Example:
+    ///
+    #[cfg_attr(feature = "unicode-scripts", doc = "```")]
+    #[cfg_attr(not(feature = "unicode-scripts"), doc = "```ignore")]
+    /// use regex_charclass::{
+    ///     char::Char,
+    ///     irange::RangeSet,
+    ///     options::{NamedClass, PerlClass},
+    ///     CharacterClass,
+    /// };
+    ///
+    /// let range = RangeSet::<Char>::from_regex("\\P{Greek}").unwrap();
+    /// assert_eq!(
+    ///     Some(NamedClass::Property { name: "Greek", negated: true }),
+    ///     range.named_class()
+    /// );
+    ///
+    /// let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+    /// assert_eq!(
+    ///     Some(NamedClass::Perl { class: PerlClass::Digit, negated: false }),
+    ///     range.named_class()
+    /// );
+    /// assert_eq!(None, RangeSet::new_from_range_char('a'..='z').named_class());
+    /// ```
+    #[inline]
+    fn named_class(&self) -> Option<NamedClass> {
+        tokens::get_named_class(&emit::normalize(self), &RegexOptions::default())
+    }
+
+    /// Return the union of the given named classes, and a name for it if it is exactly one of them
+    /// or a Unicode general category, binary property or script.
+    ///
+    /// # Example:
+    ///
+    #[cfg_attr(feature = "unicode-gencat", doc = "```")]
+    #[cfg_attr(not(feature = "unicode-gencat"), doc = "```ignore")]
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let (union, name) = RangeSet::union_preferring_named(&[
+    ///     ("Lu", RangeSet::from_regex("\\p{Lu}").unwrap()),
+    ///     ("Ll", RangeSet::from_regex("\\p{Ll}").unwrap()),
+    ///     ("Lt", RangeSet::from_regex("\\p{Lt}").unwrap()),
+    /// ]);
+    /// assert_eq!(Some("LC"), name);
+    /// assert_eq!("\\p{LC}", union.to_regex());
+    /// ```
+    fn union_preferring_named(sets: &[(&'static str, Self)]) -> (Self, Option<&'static str>) {
+        let union = sets
+            .iter()
+            .fold(RangeSet::empty(), |union, (_, set)| union.union(set));
+        let name = sets
+            .iter()
+            .find(|(_, set)| *emit::normalize(set) == union)
+            .map(|(name, _)| *name)
+            .or_else(|| tokens::find_named_class(&tokens::convert_to_range(&union)));
+        (union, name)
+    }
+
+    /// Return a regular expression character class where the ranges separated by at most `gap` `char` are merged.
+    ///
+    /// The result is shorter but over-matches: it also matches the `char` of the merged gaps.
+    /// With a `gap` of `0` it is the same as `to_regex`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::<Char>::from_regex("[a-ce-g]").unwrap();
+    /// assert_eq!("[a-ce-g]", range.to_regex_merged(0));
+    /// assert_eq!("[a-g]", range.to_regex_merged(1));
+    /// ```
+    #[inline]
+    fn to_regex_merged(&self, gap: u32) -> String {
+        emit::to_regex_merged(self, gap, &RegexOptions::default())
+    }
+
+    /// Return `true` if the given `char` is contained.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert!(range.contains_char('m'));
+    /// assert!(!range.contains_char('0'));
+    /// ```
+    #[inline]
+    fn contains_char(&self, c: char) -> bool {
+        self.contains(Char::new(c))
+    }
+
+    /// Return `true` if no `char` is contained in both classes.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='f');
+    /// assert!(range.is_disjoint(&RangeSet::new_from_range_char('g'..='z')));
+    /// assert!(!range.is_disjoint(&RangeSet::new_from_range_char('f'..='z')));
+    /// ```
+    fn is_disjoint(&self, other: &Self) -> bool {
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            if self.0[i + 1] < other.0[j] {
+                i += 2;
+            } else if other.0[j + 1] < self.0[i] {
+                j += 2;
+            } else {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Return the `char` contained in exactly one of the two instances, computed in a single walk over both.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='f');
+    /// let other = RangeSet::new_from_range_char('d'..='i');
+    /// assert_eq!("[a-cg-i]", range.symmetric_difference(&other).to_regex());
+    /// ```
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        // Each range toggles the membership at its minimum and right after its maximum.
+        let toggles = |range: &Self| {
+            range
+                .0
+                .chunks_exact(2)
+                .flat_map(|bounds| {
+                    [
+                        Some(bounds[0]),
+                        (bounds[1] < Char::max_value()).then(|| bounds[1] + Char::one()),
+                    ]
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        let (toggles_a, toggles_b) = (toggles(self), toggles(other));
+
+        let mut ranges: Vec<Char> = Vec::with_capacity(toggles_a.len() + toggles_b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < toggles_a.len() || j < toggles_b.len() {
+            match (toggles_a.get(i), toggles_b.get(j)) {
+                (Some(a), Some(b)) if a == b => {
+                    i += 1;
+                    j += 1;
+                }
+                (Some(a), Some(b)) if a > b => {
+                    ranges.push(*b);
+                    j += 1;
+                }
+                (Some(a), _) => {
+                    ranges.push(*a);
+                    i += 1;
+                }
+                (None, Some(b)) => {
+                    ranges.push(*b);
+                    j += 1;
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+
+        for max in ranges.iter_mut().skip(1).step_by(2) {
+            *max = *max - Char::one();
+        }
+        if ranges.len() % 2 == 1 {
+            ranges.push(Char::max_value());
+        }
+        RangeSet(ranges)
+    }
+
+    /// Return the `char` contained in the given Unicode plane, from 0 for the BMP to 16, empty for a greater plane.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='\u{1F600}');
+    /// assert_eq!("[\\u{10000}-\\u{1f600}]", range.in_plane(1).to_regex());
+    /// ```
+    fn in_plane(&self, plane: u8) -> Self {
+        let start = (plane as u32) << 16;
+        match (Char::from_u32(start), Char::from_u32(start + 0xFFFF)) {
+            (Some(min), Some(max)) => self.intersection(&RangeSet::new_from_range(min..=max)),
+            _ => RangeSet::empty(),
+        }
+    }
+
+    /// Return the class extended with every `char` equivalent to a member under Unicode simple case folding,
+    /// to match it case-insensitively without a flag.
+    ///
+    /// This is the same as `simple_case_fold`, the full case folding can't be applied to a class.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!("[A-Za-z\\u{017f}\\u{212a}]", range.case_fold().to_regex());
+    /// ```
+    #[inline]
+    fn case_fold(&self) -> Self {
+        self.simple_case_fold()
+    }
+
+    /// Return the class extended with every `char` equivalent to a member under Unicode simple case folding.
+    ///
+    /// Only the foldings to a single `char` are applied: the full foldings to several `char`, such as `ß` to `ss`,
+    /// can't be held by a class. For the same reason `İ` (U+0130) and `ı` (U+0131) are left alone,
+    /// since they only fold with the Turkic mappings or to `i̇` (U+0069 U+0307).
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('ß'..='ß');
+    /// assert_eq!("[\\u{00df}\\u{1e9e}]", range.simple_case_fold().to_regex());
+    /// ```
+    #[inline]
+    fn simple_case_fold(&self) -> Self {
+        tokens::case_fold(self)
+    }
+
+    /// Return `true` if every `char` of the given string is contained.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert!(range.contains_all_of_str("hello"));
+    /// assert!(!range.contains_all_of_str("Hello"));
+    /// ```
+    #[inline]
+    fn contains_all_of_str(&self, s: &str) -> bool {
+        self.first_disallowed(s).is_none()
+    }
+
+    /// Return the byte offset and the first `char` of the given string that is not contained.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!(Some((3, '!')), range.first_disallowed("hey!"));
+    /// ```
+    #[inline]
+    fn first_disallowed(&self, s: &str) -> Option<(usize, char)> {
+        s.char_indices().find(|(_, c)| !self.contains_char(*c))
+    }
+
+    /// Return `true` if the given code point is contained, `false` if it is a surrogate or out of range.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert!(range.contains_code_point(0x61));
+    /// assert!(!range.contains_code_point(0xD800));
+    /// ```
+    #[inline]
+    fn contains_code_point(&self, cp: u32) -> bool {
+        char::from_u32(cp).is_some_and(|c| self.contains_char(c))
+    }
+
+    /// Return the smallest number of bytes needed to encode a contained `char` in UTF-8, `0` if empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('é'..='€');
+    /// assert_eq!(2, range.min_utf8_bytes());
+    /// ```
+    #[inline]
+    fn min_utf8_bytes(&self) -> usize {
+        self.0.first().map_or(0, |min| min.to_char().len_utf8())
+    }
+
+    /// Return the largest number of bytes needed to encode a contained `char` in UTF-8, `0` if empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('é'..='€');
+    /// assert_eq!(3, range.max_utf8_bytes());
+    /// ```
+    #[inline]
+    fn max_utf8_bytes(&self) -> usize {
+        self.0.last().map_or(0, |max| max.to_char().len_utf8())
+    }
+
+    /// Return an iterator over the ranges of `char` contained, in order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='z');
+    /// assert_eq!(vec!['a'..='z'], range.range_inclusives().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    fn range_inclusives(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_ {
+        self.0
+            .chunks_exact(2)
+            .map(|bounds| bounds[0].to_char()..=bounds[1].to_char())
+    }
+
+    /// Return an iterator over the `char` contained, in order, without the surrogates which are not `char`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='c');
+    /// assert_eq!(vec!['a', 'b', 'c'], range.iter_chars().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.range_inclusives().flatten()
+    }
+
+    /// Return an iterator over the ranges of `char` contained, in order, with the number of `char` of each.
+    ///
+    /// The surrogates, which are not `char`, are not counted.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}');
+    /// assert_eq!(vec![('\u{D7FF}', '\u{E000}', 2)], range.runs().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    fn runs(&self) -> impl Iterator<Item = (char, char, u32)> + '_ {
+        self.0.chunks_exact(2).map(|bounds| {
+            let (min, max) = (bounds[0], bounds[1]);
+            let count = to_index(max) - to_index(min) + 1;
+            (min.to_char(), max.to_char(), count)
+        })
+    }
+
+    /// Return every `char` contained in a `BTreeSet`, or `None` if there are more than `max_len` of them.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{char::Char, irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('a'..='c');
+    /// let set = range.to_btree_set(100).unwrap();
+    /// assert_eq!(vec!['a', 'b', 'c'], set.into_iter().collect::<Vec<_>>());
+    /// assert_eq!(None, RangeSet::<Char>::dot().to_btree_set(100));
+    /// ```
+    fn to_btree_set(&self, max_len: u32) -> Option<BTreeSet<char>> {
+        if self.get_cardinality_u64() > u64::from(max_len) {
+            return None;
+        }
+        Some(self.iter_chars().collect())
+    }
+
+    /// Return the `char` at the given index in the ordered members, or `None` if it is out of bounds.
+    ///
+    /// The surrogates, which are not `char`, are skipped.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('\u{D7FE}'..='\u{E000}');
+    /// assert_eq!(Some('\u{D7FE}'), range.nth_char(0));
+    /// assert_eq!(Some('\u{E000}'), range.nth_char(2));
+    /// assert_eq!(None, range.nth_char(3));
+    /// ```
+    fn nth_char(&self, mut index: u32) -> Option<char> {
+        for (min, _, count) in self.runs() {
+            if index < count {
+                let index = to_index(Char::new(min)) + index;
+                let code = if index >= INVALID_MIN {
+                    index + INVALID_SIZE
+                } else {
+                    index
+                };
+                return char::from_u32(code);
+            }
+            index -= count;
+        }
+        None
+    }
+
+    /// Return the index of the given `char` in the ordered members, or `None` if it is not contained.
+    ///
+    /// This is the inverse of `nth_char`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regex_charclass::{irange::RangeSet, CharacterClass};
+    ///
+    /// let range = RangeSet::new_from_range_char('\u{D7FE}'..='\u{E000}');
+    /// assert_eq!(Some(2), range.rank_char('\u{E000}'));
+    /// assert_eq!(None, range.rank_char('a'));
+    /// ```
+    fn rank_char(&self, c: char) -> Option<u32> {
+        let c = Char::new(c);
+        let mut rank = 0;
+        for bounds in self.0.chunks_exact(2) {
+            let (min, max) = (bounds[0], bounds[1]);
+            if c < min {
+                return None;
+            } else if c <= max {
+                return Some(rank + to_index(c) - to_index(min));
+            }
+            rank += to_index(max) - to_index(min) + 1;
+        }
+        None
+    }
+}
+
+/// Return the position of the given `char` among the valid ones, skipping the surrogates.
+#[inline]
+fn to_index(c: Char) -> u32 {
+    let c = c.to_u32();
+    if c >= INVALID_MIN {
+        c - INVALID_SIZE
+    } else {
+        c
+    }
+}
+
+fn to_lowerbound_u32(bound: Bound<u32>) -> Option<Char> {
+    match bound {
+        Bound::Included(t) => Char::from_u32(t),
+        Bound::Excluded(t) => {
+            char::from_u32(t)?;
+
+            if t == INVALID_MIN - 1 {
+                Some(Char::new('\u{E000}'))
+            } else {
+                Char::from_u32(t + 1)
             }
         }
         Bound::Unbounded => Some(Char::min_value()),
     }
 }
 
-fn to_lowerbound_char(bound: Bound<&char>) -> Char {
+fn to_upperbound_u32(bound: Bound<u32>) -> Option<Char> {
     match bound {
-        Bound::Included(t) => Char::new(*t),
+        Bound::Included(t) => Char::from_u32(t),
         Bound::Excluded(t) => {
-            if let Some(c) = Char::from_u32(*t as u32 + 1) {
-                c
+            char::from_u32(t)?;
+
+            if t == INVALID_MIN + INVALID_SIZE {
+                Some(Char::new('\u{D7FF}'))
             } else {
-                Char::new('\u{E000}')
+                Char::from_u32(t.checked_sub(1)?)
             }
         }
-        Bound::Unbounded => Char::min_value(),
+        Bound::Unbounded => Some(Char::max_value()),
     }
 }
 
-fn to_upperbound_char(bound: Bound<&char>) -> Char {
-    match bound {
-        Bound::Included(t) => Char::new(*t),
-        Bound::Excluded(t) => {
-            if let Some(c) = Char::from_u32(*t as u32 - 1) {
-                c
-            } else {
-                Char::new('\u{D7FF}')
-            }
-        }
-        Bound::Unbounded => Char::min_value(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_and_total() -> Result<(), String> {
+        let range = RangeSet::<Char>::empty();
+        assert!(range.is_empty());
+        assert_eq!("[]", range.to_regex());
+        assert_eq!(0, range.get_cardinality());
+
+        let range = RangeSet::<Char>::total();
+        assert!(range.is_total());
+        assert_eq!("[\\u{0000}-\\u{10ffff}]", range.to_regex());
+        assert_eq!(1_112_064, range.get_cardinality());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unbounded_ranges() -> Result<(), String> {
+        assert_eq!(
+            RangeSet::new_from_range_char('a'..=char::MAX),
+            RangeSet::new_from_range_char('a'..)
+        );
+        assert_eq!(
+            "[a-\\u{10ffff}]",
+            RangeSet::new_from_range_char('a'..).to_regex()
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('\0'..='z'),
+            RangeSet::new_from_range_char(..='z')
+        );
+        assert!(RangeSet::<Char>::new_from_range_char(..).is_total());
+
+        assert_eq!(
+            Some(RangeSet::new_from_range_char('a'..)),
+            RangeSet::new_from_range_u32(97..)
+        );
+        assert_eq!(
+            Some(RangeSet::new_from_range_char(..'z')),
+            RangeSet::new_from_range_u32(..122)
+        );
+        assert_eq!(
+            Some(RangeSet::total()),
+            RangeSet::<Char>::new_from_range_u32(..)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_bounds() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_range_char('a'..);
+        assert_eq!(0x10FFFF - 'a' as u32 + 1 - 0x800, range.get_cardinality());
+        let total = RangeSet::<Char>::total();
+        assert_eq!(total, RangeSet::<Char>::new_from_range_char(..));
+        assert_eq!(total, RangeSet::<Char>::new_from_range_u32(..).unwrap());
+        assert_eq!("[\\u{0000}-\\u{10ffff}]", total.to_regex());
+        let options = RegexOptions {
+            assume_dotall: true,
+            ..Default::default()
+        };
+        assert_eq!(".", total.to_regex_with(&options));
+
+        for (start, end) in [
+            (Bound::Unbounded, Bound::Unbounded),
+            (Bound::Included('a'), Bound::Unbounded),
+            (Bound::Excluded('a'), Bound::Unbounded),
+            (Bound::Unbounded, Bound::Included('z')),
+            (Bound::Unbounded, Bound::Excluded('z')),
+            (Bound::Excluded('\u{D7FF}'), Bound::Excluded('\u{E001}')),
+            (Bound::Excluded('\u{D7FE}'), Bound::Excluded('\u{E000}')),
+        ] {
+            let range = RangeSet::<Char>::new_from_range_char((start, end));
+            assert_eq!(
+                Some(range),
+                RangeSet::<Char>::new_from_range_u32((
+                    start.map(|c| c as u32),
+                    end.map(|c| c as u32)
+                ))
+            );
+        }
+        assert_eq!(
+            RangeSet::new_from_range_char('\u{E000}'..='\u{E000}'),
+            RangeSet::new_from_range_char((
+                Bound::Excluded('\u{D7FF}'),
+                Bound::Excluded('\u{E001}')
+            ))
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{D7FF}'),
+            RangeSet::new_from_range_char((
+                Bound::Excluded('\u{D7FE}'),
+                Bound::Excluded('\u{E000}')
+            ))
+        );
+
+        assert!(RangeSet::<Char>::new_from_range_char('b'..'b').is_empty());
+        assert!(RangeSet::<Char>::new_from_range_char(..'\0').is_empty());
+        let range =
+            RangeSet::<Char>::new_from_range_char((Bound::Excluded(char::MAX), Bound::Unbounded));
+        assert!(range.is_empty());
+        assert_eq!(None, RangeSet::<Char>::new_from_range_u32(0xD800..));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dot() -> Result<(), String> {
+        let dot = RangeSet::<Char>::dot();
+        assert_eq!(1_112_063, dot.get_cardinality());
+        assert_eq!(".", dot.to_regex());
+        assert_eq!(dot, RangeSet::from_regex("[^\\n]").unwrap());
+        assert_ne!(dot.to_regex(), RangeSet::<Char>::total().to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_specificity() -> Result<(), String> {
+        assert_eq!(1.0, RangeSet::<Char>::empty().specificity());
+        assert_eq!(0.0, RangeSet::<Char>::total().specificity());
+
+        let specificity = RangeSet::new_from_range_char('a'..='z').specificity();
+        assert!(specificity < 1.0);
+        assert!(specificity > 0.9999);
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert!(range.specificity() > range.complement().specificity());
+        Ok(())
     }
-}
 
-fn convert_to_regex(range: &RangeSet<Char>) -> String {
-    let mut sb = String::new();
+    #[test]
+    fn test_cardinality_u64() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<Char>::empty().get_cardinality_u64());
+        assert_eq!(1_112_064, RangeSet::<Char>::total().get_cardinality_u64());
+        assert_eq!(1_112_064, RangeSet::<Char>::total().get_cardinality());
+        assert_eq!(
+            26,
+            RangeSet::new_from_range_char('a'..='z').get_cardinality_u64()
+        );
 
-    let is_complement;
-    let range_to_use;
-    let complement = range.complement();
-    if complement.0.len() < range.0.len() {
-        range_to_use = &complement;
-        is_complement = true;
-    } else {
-        range_to_use = range;
-        is_complement = false;
-    }
-
-    for r in (0..range_to_use.0.len()).step_by(2) {
-        let (min, max) = (range_to_use.0[r], range_to_use.0[r + 1]);
-        if min == max {
-            sb.push_str(get_printable_char(min.to_char()).as_str());
-        } else if min + Char::one() == max {
-            sb.push_str(
-                format!(
-                    "{}{}",
-                    get_printable_char(min.to_char()),
-                    get_printable_char(max.to_char())
-                )
-                .as_str(),
-            );
-        } else {
-            sb.push_str(
-                format!(
-                    "{}-{}",
-                    get_printable_char(min.to_char()),
-                    get_printable_char(max.to_char())
-                )
-                .as_str(),
-            );
-        }
+        let total = RangeSet::<Char>::total();
+        let repeated = RangeSet(total.0.repeat(4000));
+        assert_eq!(4_448_256_000, repeated.get_cardinality_u64());
+        assert_eq!(u32::MAX, repeated.get_cardinality());
+        let reversed = RangeSet(vec![Char::new('z'), Char::new('a')]);
+        assert_eq!(0, reversed.get_cardinality_u64());
+
+        Ok(())
     }
 
-    if is_complement || range_to_use.0.len() > 2 || range_to_use.0[0] != range_to_use.0[1] {
-        if is_complement {
-            return format!("[^{}]", sb);
-        } else {
-            return format!("[{}]", sb);
-        }
+    #[test]
+    fn test_case_fold() -> Result<(), String> {
+        assert!(RangeSet::<Char>::empty().case_fold().is_empty());
+        assert!(RangeSet::<Char>::total().case_fold().is_total());
+
+        let lower = RangeSet::new_from_range_char('a'..='z');
+        let upper = RangeSet::new_from_range_char('A'..='Z');
+        let folded = lower.case_fold();
+        assert!(folded.contains_all_of_str("abcXYZ"));
+        assert_eq!(folded, upper.case_fold());
+        assert_eq!(folded, folded.case_fold());
+        // KELVIN SIGN and LATIN SMALL LETTER LONG S fold to `k` and `s`.
+        assert_eq!(
+            folded,
+            lower
+                .union(&upper)
+                .union(&RangeSet::new_from_chars(['\u{17F}', '\u{212A}']))
+        );
+
+        let range = RangeSet::<Char>::new_from_chars(['é', 'Σ', '1']);
+        assert_eq!(
+            RangeSet::new_from_chars(['é', 'É', 'Σ', 'σ', 'ς', '1']),
+            range.case_fold()
+        );
+
+        Ok(())
     }
 
-    sb
-}
+    #[test]
+    fn test_simple_case_fold() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_chars(['i']);
+        assert_eq!(
+            RangeSet::new_from_chars(['i', 'I']),
+            range.simple_case_fold()
+        );
+        let range = RangeSet::<Char>::new_from_chars(['I']);
+        assert_eq!(
+            RangeSet::new_from_chars(['i', 'I']),
+            range.simple_case_fold()
+        );
 
-fn get_printable_char(character: char) -> String {
-    if ('\u{20}'..'\u{7E}').contains(&character) {
-        if character == '*'
-            || character == '+'
-            || character == '?'
-            || character == '('
-            || character == ')'
-            || character == '['
-            || character == ']'
-            || character == '{'
-            || character == '}'
-            || character == '|'
-            || character == '\\'
-            || character == '-'
-            || character == '^'
-            || character == '.'
-        {
-            format!("\\{}", character)
-        } else {
-            format!("{}", character)
+        // The Turkish dotted and dotless I have no simple folding.
+        for c in ['\u{130}', '\u{131}'] {
+            let range = RangeSet::<Char>::new_from_char(c);
+            assert_eq!(range, range.simple_case_fold());
         }
-    } else if let Some(c) = identify_character(character) {
-        c.to_owned()
-    } else {
-        format!("\\u{{{:04x}}}", character as u32)
+        let range = RangeSet::<Char>::new_from_chars(['i', '\u{130}', '\u{131}']);
+        assert_eq!(
+            RangeSet::new_from_chars(['i', 'I', '\u{130}', '\u{131}']),
+            range.simple_case_fold()
+        );
+
+        // `ß` folds to `ss`, which can't be held, but `ẞ` folds to `ß`.
+        let range = RangeSet::<Char>::new_from_char('ß');
+        assert_eq!(
+            RangeSet::new_from_chars(['ß', 'ẞ']),
+            range.simple_case_fold()
+        );
+        assert_eq!(range.case_fold(), range.simple_case_fold());
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use irange::range::AnyRange;
+    #[test]
+    fn test_whitespace() -> Result<(), String> {
+        let whitespace = RangeSet::<Char>::whitespace();
+        assert_eq!("\\s", whitespace.to_regex());
+        assert_eq!("\\S", whitespace.complement().to_regex());
+        assert_eq!(Ok(whitespace.clone()), RangeSet::from_regex("\\s"));
 
-    use super::*;
+        let ascii_whitespace = RangeSet::<Char>::ascii_whitespace();
+        assert_eq!(
+            RangeSet::new_from_chars(['\t', '\n', '\u{B}', '\u{C}', '\r', ' ']),
+            ascii_whitespace
+        );
+        assert_eq!(6, ascii_whitespace.get_cardinality());
+        assert_eq!(
+            ascii_whitespace,
+            whitespace.intersection(&RangeSet::new_from_range_char('\0'..='\u{7F}'))
+        );
+
+        Ok(())
+    }
 
     #[test]
-    fn test_empty_and_total() -> Result<(), String> {
-        let range = RangeSet::<Char>::empty();
-        assert!(range.is_empty());
-        assert_eq!("[]", range.to_regex());
-        assert_eq!(0, range.get_cardinality());
+    fn test_density() -> Result<(), String> {
+        assert_eq!(0.0, RangeSet::<Char>::empty().density());
+        assert_eq!(1.0, RangeSet::<Char>::total().density());
+        assert_eq!(1.0, RangeSet::new_from_range_char('a'..='z').density());
+        // The surrogates are not counted in the bounding range.
+        let range = RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}');
+        assert_eq!(1.0, range.density());
+
+        let range = RangeSet::<Char>::new_from_chars(('a'..='y').step_by(2));
+        assert_eq!(13.0 / 25.0, range.density());
+        let range = RangeSet::<Char>::new_from_chars(['a', 'z']);
+        assert_eq!(2.0 / 26.0, range.density());
+        assert!(RangeSet::<Char>::dot().density() < 1.0);
 
-        let range = RangeSet::<Char>::total();
-        assert!(range.is_total());
-        assert_eq!(".", range.to_regex());
-        assert_eq!(1_112_064, range.get_cardinality());
         Ok(())
     }
 
@@ -327,6 +1704,47 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-scripts"))]
+    #[test]
+    fn test_double_complement() -> Result<(), String> {
+        let mut ranges = vec![
+            RangeSet::empty(),
+            RangeSet::total(),
+            RangeSet::dot(),
+            RangeSet::new_from_range_char('\0'..='\0'),
+            RangeSet::new_from_range_char('\0'..='z'),
+            RangeSet::new_from_range_char('a'..=char::MAX),
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}'),
+            RangeSet::new_from_range_char('\u{E000}'..=char::MAX),
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('\0')..=Char::new('\0')),
+                AnyRange::from(Char::new(char::MAX)..=Char::new(char::MAX)),
+            ]),
+            RangeSet(vec![
+                Char::new('a'),
+                Char::new('b'),
+                Char::new('c'),
+                Char::new('d'),
+            ]),
+        ];
+        for regex in ["\\d", "\\w", "\\s", "\\p{Greek}", "\\p{Lu}", "[[:punct:]]"] {
+            ranges.push(RangeSet::from_regex(regex).map_err(|err| err.to_string())?);
+        }
+
+        for range in ranges {
+            let double_complement = range.complement().complement();
+            assert_eq!(*emit::normalize(&range), double_complement);
+            assert_eq!(range.to_regex(), double_complement.to_regex());
+            assert_eq!(
+                range.complement().to_regex(),
+                double_complement.complement().to_regex()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-props"))]
     #[test]
     fn test_to_regex() -> Result<(), String> {
         let range = RangeSet::<Char>::new_from_range_char('.'..='.');
@@ -339,6 +1757,604 @@ mod tests {
         ]);
         assert_eq!("\\p{ASCII_Hex_Digit}", range.to_regex());
 
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('\0')..=Char::new('\u{1F}')),
+            AnyRange::from(Char::new('\u{7F}')..=Char::new('\u{9F}')),
+        ]);
+        assert_eq!("\\p{Cc}", range.to_regex());
+        assert_eq!("\\P{Cc}", range.complement().to_regex());
+        assert_eq!(range, RangeSet::from_regex("\\p{Cc}").unwrap());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_all_of_str() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_regex("[a-zé ]").unwrap();
+        assert!(range.contains_all_of_str(""));
+        assert!(range.contains_all_of_str("café au lait"));
+        assert_eq!(None, range.first_disallowed("café au lait"));
+
+        assert!(!range.contains_all_of_str("café crème"));
+        assert_eq!(Some((8, 'è')), range.first_disallowed("café crème"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_char() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_range_char('\u{D000}'..='\u{F000}');
+        assert!(range.contains_char('\u{D7FF}'));
+        assert!(range.contains_char('\u{E000}'));
+        assert!(range.contains_char('\u{F000}'));
+        assert!(!range.contains_char('\u{F001}'));
+
+        let range = RangeSet::<Char>::new_from_range_char('\u{10000}'..);
+        assert!(range.contains_char(char::MAX));
+        assert!(!range.contains_char('\u{FFFF}'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_disjoint() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_range_char('a'..='f');
+        assert!(range.is_disjoint(&RangeSet::new_from_range_char('g'..='z')));
+        assert!(!range.is_disjoint(&RangeSet::new_from_range_char('f'..='z')));
+        assert!(range.is_disjoint(&RangeSet::empty()));
+        assert!(!range.is_disjoint(&RangeSet::total()));
+        assert!(range.is_disjoint(&range.complement()));
+
+        let range = RangeSet::<Char>::from_regex("[0-9a-cx-z]").unwrap();
+        assert!(range.is_disjoint(&RangeSet::<Char>::from_regex("[A-Zd-w]").unwrap()));
+        assert!(!range.is_disjoint(&RangeSet::<Char>::from_regex("[A-Zd-x]").unwrap()));
+        assert!(!range.is_disjoint(&RangeSet::<Char>::from_regex("[5]").unwrap()));
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-scripts"))]
+    #[test]
+    fn test_symmetric_difference() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_range_char('a'..='f');
+        let other = RangeSet::<Char>::new_from_range_char('d'..='i');
+        assert_eq!(
+            RangeSet::<Char>::from_regex("[a-cg-i]").unwrap(),
+            range.symmetric_difference(&other)
+        );
+        assert_eq!(
+            range.symmetric_difference(&other),
+            other.symmetric_difference(&range)
+        );
+        assert!(range.symmetric_difference(&range).is_empty());
+        assert_eq!(range, range.symmetric_difference(&RangeSet::empty()));
+
+        let total = RangeSet::<Char>::total();
+        assert_eq!(range.complement(), range.symmetric_difference(&total));
+        assert!(total.symmetric_difference(&total).is_empty());
+
+        let adjacent = RangeSet::<Char>::new_from_range_char('g'..='z');
+        assert_eq!(
+            RangeSet::<Char>::new_from_range_char('a'..='z'),
+            range.symmetric_difference(&adjacent)
+        );
+
+        for (a, b) in [
+            ("[0-9a-cx-z]", "[5-7b-y]"),
+            ("\\p{Greek}", "\\p{L}"),
+            ("[\\u{D7FF}\\u{E000}]", "[\\u{D7F0}-\\u{E010}]"),
+            ("[\\u{10FFFF}]", "[\\u{10000}-\\u{10FFFF}]"),
+        ] {
+            let a = RangeSet::<Char>::from_regex(a).unwrap();
+            let b = RangeSet::<Char>::from_regex(b).unwrap();
+            assert_eq!(
+                a.union(&b).difference(&a.intersection(&b)),
+                a.symmetric_difference(&b)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-scripts"))]
+    #[test]
+    fn test_named_class() -> Result<(), String> {
+        use options::PerlClass;
+
+        for (regex, expected) in [
+            (
+                "\\p{Lu}",
+                NamedClass::Property {
+                    name: "Lu",
+                    negated: false,
+                },
+            ),
+            (
+                "\\P{Greek}",
+                NamedClass::Property {
+                    name: "Greek",
+                    negated: true,
+                },
+            ),
+            (
+                "\\p{scx=Adlam}",
+                NamedClass::ScriptExtension {
+                    name: "Adlam",
+                    negated: false,
+                },
+            ),
+            (
+                "\\W",
+                NamedClass::Perl {
+                    class: PerlClass::Word,
+                    negated: true,
+                },
+            ),
+        ] {
+            let range = RangeSet::<Char>::from_regex(regex).unwrap();
+            assert_eq!(Some(expected), range.named_class());
+            assert_eq!(regex, expected.to_string());
+            assert_eq!(range.to_regex(), expected.to_string());
+        }
+
+        assert_eq!(None, RangeSet::<Char>::empty().named_class());
+        assert_eq!(None, RangeSet::<Char>::total().named_class());
+        let range = RangeSet::<Char>::new_from_char('\n');
+        assert_eq!(None, range.named_class());
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-scripts"))]
+    #[test]
+    fn test_union_preferring_named() -> Result<(), String> {
+        let named = |name: &'static str| {
+            (
+                name,
+                RangeSet::<Char>::from_regex(&format!("\\p{{{}}}", name)).unwrap(),
+            )
+        };
+
+        let (union, name) =
+            RangeSet::<Char>::union_preferring_named(&[named("Lu"), named("Ll"), named("Lt")]);
+        assert_eq!(Some("LC"), name);
+        assert_eq!(RangeSet::<Char>::from_regex("\\p{LC}").unwrap(), union);
+
+        let (_, name) = RangeSet::<Char>::union_preferring_named(&[named("Lu"), named("Ll")]);
+        assert_eq!(None, name);
+
+        let (union, name) = RangeSet::<Char>::union_preferring_named(&[
+            named("Greek"),
+            ("greek letters", RangeSet::new_from_range_char('α'..='ω')),
+        ]);
+        assert_eq!(Some("Greek"), name);
+        assert_eq!(named("Greek").1, union);
+
+        let (union, name) = RangeSet::<Char>::union_preferring_named(&[]);
+        assert!(union.is_empty());
+        assert_eq!(None, name);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_regex_posix() -> Result<(), String> {
+        for name in [
+            "alpha", "digit", "alnum", "space", "upper", "lower", "punct", "xdigit",
+        ] {
+            let range = RangeSet::<Char>::from_regex(&format!("[[:{}:]]", name)).unwrap();
+            assert_eq!(Some(format!("[[:{}:]]", name)), range.to_regex_posix());
+            assert_eq!(
+                Some(format!("[^[:{}:]]", name)),
+                range.complement().to_regex_posix()
+            );
+        }
+
+        let range = RangeSet::<Char>::from_regex("[[:word:]]").unwrap();
+        assert_eq!(None, range.to_regex_posix());
+        let range = RangeSet::<Char>::from_regex("\\d").unwrap();
+        assert_eq!(None, range.to_regex_posix());
+        assert_eq!(None, RangeSet::<Char>::empty().to_regex_posix());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_plane() -> Result<(), String> {
+        let dot = RangeSet::<Char>::dot();
+        assert_eq!("[\\u{10000}-\\u{1ffff}]", dot.in_plane(1).to_regex());
+        assert_eq!(0x10000, dot.in_plane(1).get_cardinality());
+
+        let bmp = dot.in_plane(0);
+        assert_eq!(0x10000 - 0x800 - 1, bmp.get_cardinality());
+        assert!(bmp.contains_char('\u{D7FF}') && bmp.contains_char('\u{FFFF}'));
+        assert!(!bmp.contains_char('\n') && !bmp.contains_char('\u{10000}'));
+
+        let total = RangeSet::<Char>::total();
+
+        assert_eq!("[\\u{100000}-\\u{10ffff}]", total.in_plane(16).to_regex());
+        assert!(total.in_plane(17).is_empty());
+        assert!(total.in_plane(u8::MAX).is_empty());
+        assert!(RangeSet::<Char>::new_from_range_char('a'..='z')
+            .in_plane(1)
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_code_point() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_range_char('a'..='z');
+        assert!(range.contains_code_point('m' as u32));
+        assert!(!range.contains_code_point('A' as u32));
+
+        let total = RangeSet::<Char>::total();
+        assert!(total.contains_code_point(0x10FFFF));
+        assert!(!total.contains_code_point(0xD800));
+        assert!(!total.contains_code_point(0xDFFF));
+        assert!(!total.contains_code_point(0x110000));
+        assert!(!total.contains_code_point(u32::MAX));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_from_fn() -> Result<(), String> {
+        assert!(RangeSet::<Char>::new_from_fn(|_| false).is_empty());
+        assert!(RangeSet::<Char>::new_from_fn(|_| true).is_total());
+
+        let range = RangeSet::<Char>::new_from_fn(|c| c.is_ascii_digit());
+        assert_eq!(RangeSet::new_from_range_char('0'..='9'), range);
+        assert_eq!("[0-9]", range.to_regex());
+
+        let range = RangeSet::<Char>::new_from_fn(|c| c != '\u{D7FF}' && c != '\u{E000}');
+        assert_eq!(
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}').complement(),
+            range
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_from_chars() -> Result<(), String> {
+        assert!(RangeSet::<Char>::new_from_chars([]).is_empty());
+        assert_eq!(
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{D7FF}'),
+            RangeSet::<Char>::new_from_char('\u{D7FF}')
+        );
+
+        let range = RangeSet::<Char>::new_from_chars(['a', 'b', 'c', 'x']);
+        assert_eq!("[a-cx]", range.to_regex());
+        assert_eq!(
+            range,
+            RangeSet::<Char>::new_from_chars(['x', 'c', 'x', 'b', 'a', 'a'])
+        );
+
+        let range = RangeSet::<Char>::new_from_chars(['\u{E000}', '\u{D7FF}', char::MAX]);
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('\u{D7FF}')..=Char::new('\u{E000}')),
+                AnyRange::from(Char::new(char::MAX)..=Char::new(char::MAX)),
+            ]),
+            range
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_strings() -> Result<(), String> {
+        assert!(RangeSet::<Char>::from_strings([]).is_empty());
+        assert!(RangeSet::<Char>::from_strings(["", ""]).is_empty());
+
+        let range = RangeSet::<Char>::from_strings(["abc", "xyz"]);
+        assert_eq!("[a-cx-z]", range.to_regex());
+
+        let range = RangeSet::<Char>::from_strings(["cab", "bcd", "été", "\u{D7FF}\u{E000}"]);
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('a')..=Char::new('d')),
+                AnyRange::from(Char::new('t')..=Char::new('t')),
+                AnyRange::from(Char::new('é')..=Char::new('é')),
+                AnyRange::from(Char::new('\u{D7FF}')..=Char::new('\u{E000}')),
+            ]),
+            range
+        );
+        assert_eq!("[a-dt\\u{00e9}\\u{d7ff}-\\u{e000}]", range.to_regex());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_range_inclusives() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_range_inclusives(['a'..='f', '0'..='9']);
+        assert_eq!("[0-9a-f]", range.to_regex());
+        assert_eq!(
+            vec!['0'..='9', 'a'..='f'],
+            range.range_inclusives().collect::<Vec<_>>()
+        );
+
+        let range = RangeSet::<Char>::from_range_inclusives(['a'..='m', 'z'..='a', 'n'..='z']);
+        assert_eq!(RangeSet::new_from_range_char('a'..='z'), range);
+        assert!(RangeSet::<Char>::from_range_inclusives([]).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_inclusives() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<Char>::empty().range_inclusives().count());
+
+        let range = RangeSet::<Char>::from_regex("[0-9x]").unwrap();
+        let ranges = range.range_inclusives().collect::<Vec<_>>();
+        assert_eq!(vec!['0'..='9', 'x'..='x'], ranges);
+        assert!(ranges[0].contains(&'9'));
+
+        let range = RangeSet::<Char>::total();
+        assert_eq!(
+            vec!['\0'..=char::MAX],
+            range.range_inclusives().collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_runs() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<Char>::empty().runs().count());
+
+        let range = RangeSet::<Char>::from_regex("[a-cx-z]").unwrap();
+        assert_eq!(
+            vec![('a', 'c', 3), ('x', 'z', 3)],
+            range.runs().collect::<Vec<_>>()
+        );
+
+        let range = RangeSet::<Char>::total();
+        assert_eq!(
+            vec![('\0', char::MAX, 1_112_064)],
+            range.runs().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            range.get_cardinality(),
+            range.runs().map(|(_, _, count)| count).sum::<u32>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_chars() -> Result<(), String> {
+        assert_eq!(0, RangeSet::<Char>::empty().iter_chars().count());
+
+        let range = RangeSet::<Char>::from_regex("[\\u{D7FE}-\\u{E001}x]").unwrap();
+        assert_eq!(
+            vec!['x', '\u{D7FE}', '\u{D7FF}', '\u{E000}', '\u{E001}'],
+            range.iter_chars().collect::<Vec<_>>()
+        );
+        assert!(range.iter_chars().eq(range.iter().map(|c| c.to_char())));
+
+        let total = RangeSet::<Char>::total();
+        assert_eq!(1_112_064, total.iter_chars().count());
+        assert_eq!(Some(char::MAX), total.iter_chars().last());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_btree_set() -> Result<(), String> {
+        let range = RangeSet::<Char>::from_regex("[a-c]").unwrap();
+        assert_eq!(Some(BTreeSet::from(['a', 'b', 'c'])), range.to_btree_set(3));
+        assert_eq!(None, range.to_btree_set(2));
+        let empty = RangeSet::<Char>::empty();
+        assert_eq!(Some(BTreeSet::new()), empty.to_btree_set(0));
+        let dot = RangeSet::<Char>::from_regex(".").unwrap();
+        assert_eq!(None, dot.to_btree_set(1000));
+
+        let range = RangeSet::<Char>::from_regex("[\\u{D7FF}\\u{E000}]").unwrap();
+        assert_eq!(
+            Some(BTreeSet::from(['\u{D7FF}', '\u{E000}'])),
+            range.to_btree_set(2)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() -> Result<(), String> {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(None, RangeSet::<Char>::empty().sample(&mut rng));
+        let range = RangeSet::new_from_range_char('x'..='x');
+        assert_eq!(Some('x'), range.sample(&mut rng));
+
+        let range = RangeSet::<Char>::from_regex("[\\u{D7FF}\\u{E000}]").unwrap();
+        let samples = (0..100)
+            .map(|_| range.sample(&mut rng).unwrap())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(BTreeSet::from(['\u{D7FF}', '\u{E000}']), samples);
+
+        // `a` is one of the four `char`, so it should be picked about a quarter of the time.
+        let range = RangeSet::<Char>::from_regex("[ab-d]").unwrap();
+        let count = (0..4000)
+            .filter(|_| range.sample(&mut rng) == Some('a'))
+            .count();
+        assert!((800..1200).contains(&count), "{}", count);
+
+        let range = RangeSet::<Char>::total();
+        for _ in 0..1000 {
+            let c = range.sample(&mut rng).unwrap();
+            assert!(range.contains(Char::new(c)));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-props", feature = "unicode-gencat"))]
+    #[test]
+    fn test_char_predicates() -> Result<(), String> {
+        let alphabetic = RangeSet::<Char>::alphabetic();
+        let numeric = RangeSet::<Char>::numeric();
+        let alnum = RangeSet::<Char>::alphanumeric();
+        let control = RangeSet::<Char>::control();
+        // The standard library may follow a newer version of Unicode than the tables.
+        let unassigned = RangeSet::<Char>::from_regex("\\p{Cn}").unwrap();
+        for c in (0..=0x10FFFF)
+            .step_by(31)
+            .filter_map(char::from_u32)
+            .filter(|c| !unassigned.contains_char(*c))
+        {
+            assert_eq!(c.is_alphabetic(), alphabetic.contains_char(c), "{:?}", c);
+            assert_eq!(c.is_numeric(), numeric.contains_char(c), "{:?}", c);
+            assert_eq!(c.is_alphanumeric(), alnum.contains_char(c), "{:?}", c);
+            assert_eq!(c.is_control(), control.contains_char(c), "{:?}", c);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nth_char() -> Result<(), String> {
+        assert_eq!(None, RangeSet::<Char>::empty().nth_char(0));
+
+        let range = RangeSet::<Char>::from_regex("[a-cx\\u{D7FF}\\u{E000}]").unwrap();
+        let chars = (0..range.get_cardinality())
+            .map(|i| range.nth_char(i).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(range.iter_chars().collect::<Vec<_>>(), chars);
+        assert_eq!(None, range.nth_char(range.get_cardinality()));
+
+        let total = RangeSet::<Char>::total();
+        assert_eq!(Some('\0'), total.nth_char(0));
+        assert_eq!(Some('\u{E000}'), total.nth_char(0xD800));
+        assert_eq!(Some(char::MAX), total.nth_char(1_112_063));
+        assert_eq!(None, total.nth_char(1_112_064));
+        assert_eq!(None, total.nth_char(u32::MAX));
+
+        for i in 0..range.get_cardinality() {
+            assert_eq!(Some(i), range.rank_char(range.nth_char(i).unwrap()));
+        }
+        assert_eq!(None, range.rank_char('d'));
+        assert_eq!(None, range.rank_char('\0'));
+        assert_eq!(Some(0xD800), total.rank_char('\u{E000}'));
+        assert_eq!(Some(1_112_063), total.rank_char(char::MAX));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefers_class_over_alternation() -> Result<(), String> {
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert!(range.prefers_class_over_alternation(RegexFlavor::Rust));
+        assert!(range.prefers_class_over_alternation(RegexFlavor::EcmaScript));
+
+        let range = range.union(&RangeSet::new_from_range_char('😀'..='😀'));
+        assert!(range.prefers_class_over_alternation(RegexFlavor::Rust));
+        assert!(range.prefers_class_over_alternation(RegexFlavor::Pcre));
+        assert!(!range.prefers_class_over_alternation(RegexFlavor::EcmaScript));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stable_hash_hex() -> Result<(), String> {
+        assert_eq!(
+            "cbf29ce484222325",
+            RangeSet::<Char>::empty().stable_hash_hex()
+        );
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!("a69c0e4390b010de", range.stable_hash_hex());
+        assert_eq!(
+            range.stable_hash_hex(),
+            RangeSet::<Char>::from_regex("[a-mn-z]")
+                .unwrap()
+                .stable_hash_hex()
+        );
+        assert_ne!(
+            range.stable_hash_hex(),
+            RangeSet::new_from_range_char('a'..='y').stable_hash_hex()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_utf8_bytes() -> Result<(), String> {
+        let range = RangeSet::<Char>::empty();
+        assert_eq!(0, range.min_utf8_bytes());
+        assert_eq!(0, range.max_utf8_bytes());
+
+        let range = RangeSet::new_from_range_char('a'..='z');
+        assert_eq!(1, range.min_utf8_bytes());
+        assert_eq!(1, range.max_utf8_bytes());
+
+        let range = range.union(&RangeSet::new_from_range_char('€'..='€'));
+        assert_eq!(1, range.min_utf8_bytes());
+        assert_eq!(3, range.max_utf8_bytes());
+
+        let range = range.union(&RangeSet::new_from_range_char('😀'..='😀'));
+        assert_eq!(1, range.min_utf8_bytes());
+        assert_eq!(4, range.max_utf8_bytes());
+
+        let range = RangeSet::<Char>::total();
+        assert_eq!(1, range.min_utf8_bytes());
+        assert_eq!(4, range.max_utf8_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_emoji() -> Result<(), String> {
+        let range = RangeSet::<Char>::new_from_ranges(&[
+            AnyRange::from(Char::new('\u{231A}')..=Char::new('\u{231B}')),
+            AnyRange::from(Char::new('\u{1F600}')..=Char::new('\u{1F64F}')),
+        ]);
+        assert!(!range.is_empty());
+
+        let emoji_presentation = RangeSet::<Char>::from_regex("\\p{Emoji_Presentation}");
+        if cfg!(feature = "emoji") {
+            let emoji_presentation = emoji_presentation.unwrap();
+            assert!(range.difference(&emoji_presentation).is_empty());
+            assert_eq!("\\p{Emoji_Presentation}", emoji_presentation.to_regex());
+            assert_eq!(
+                "\\P{Emoji_Presentation}",
+                emoji_presentation.complement().to_regex()
+            );
+        } else {
+            assert!(emoji_presentation.is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unicode_blocks() -> Result<(), String> {
+        let emoticons = RangeSet::<Char>::from_regex("\\p{Block=Emoticons}");
+        if cfg!(feature = "unicode-blocks") {
+            let emoticons = emoticons.unwrap();
+            assert_eq!(
+                RangeSet::new_from_range_char('\u{1F600}'..='\u{1F64F}'),
+                emoticons
+            );
+            assert_eq!(
+                Ok(emoticons.clone()),
+                RangeSet::<Char>::from_regex("\\p{InEmoticons}")
+            );
+            assert_eq!("[\\u{1f600}-\\u{1f64f}]", emoticons.to_regex());
+            assert_eq!(
+                "\\p{Block=Emoticons}",
+                emoticons.to_regex_with(&RegexOptions {
+                    flavor: RegexFlavor::Pcre,
+                    ..Default::default()
+                })
+            );
+        } else {
+            assert!(emoticons.is_err());
+        }
+
         Ok(())
     }
 
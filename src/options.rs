@@ -0,0 +1,368 @@
+use std::fmt::Display;
+
+/// Options used by `to_regex_with` to render a character class.
+///
+/// # Example:
+///
+/// ```
+/// use regex_charclass::{irange::RangeSet, options::{EscapeStyle, RegexOptions}, CharacterClass};
+///
+/// let options = RegexOptions {
+///     escape_style: EscapeStyle::NamedUnicode,
+///     ..Default::default()
+/// };
+///
+/// let range = RangeSet::new_from_range_char('é'..='é');
+/// assert_eq!("\\N{U+00E9}", range.to_regex_with(&options));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegexOptions {
+    /// The syntax used for code points without a printable representation.
+    pub escape_style: EscapeStyle,
+    /// Write the hexadecimal digits of escaped code points in uppercase, `\u{00E9}` instead of `\u{00e9}`.
+    ///
+    /// `EscapeStyle::NamedUnicode` always uses uppercase, following the `U+` notation.
+    pub uppercase_hex: bool,
+    /// Allow nested classes and the `&&` intersection operator, as supported by the `regex` crate.
+    ///
+    /// Only for `RegexFlavor::Rust` and `RegexFlavor::RustBytes`.
+    pub use_set_ops: bool,
+    /// The regular expression engine the output is written for.
+    pub flavor: RegexFlavor,
+    /// Write U+0008 as `\b` inside brackets, where the flavor reads it as backspace rather than a word boundary.
+    pub use_class_backspace: bool,
+    /// Whether control characters are written with their named escapes such as `\n` or with hexadecimal escapes.
+    pub control_escape_style: ControlEscapeStyle,
+    /// How the empty class, which matches nothing, is written.
+    pub empty_as: EmptyClassStyle,
+    /// How the negated Unicode properties are written, `\P{L}` or `\p{^L}`.
+    pub negation_style: NegationStyle,
+    /// Additional `char` to escape, such as `/` for `sed` or `"` for a shell string.
+    ///
+    /// ASCII punctuation is escaped with a backslash, anything else according to `escape_style`.
+    /// It does not apply to `RegexFlavor::Posix` since backslash is literal inside brackets.
+    pub extra_escape: Vec<char>,
+    /// Write the `\d`, `\w` and `\s` classes contained in a larger class with their shorthand, such as `[\d\-\.]`.
+    ///
+    /// Only for `RegexFlavor::Rust` and `RegexFlavor::RustBytes`, where these classes are not ASCII-only.
+    pub embed_classes: bool,
+    /// Allow the negated forms `[^...]`, `\P{...}`, `\D`, `\S` and `\W`, even if the direct form is longer when disabled.
+    ///
+    /// The empty class, which has no direct form, is still written `[^...]` with `EmptyClassStyle::NegatedTotal`.
+    pub allow_negation: bool,
+    /// Allow the Perl classes `\d`, `\s` and `\w` and their negated forms, including within `embed_classes`.
+    pub allow_perl_classes: bool,
+    /// Allow the Unicode properties such as `\p{Greek}`, for engines without Unicode support.
+    pub allow_unicode_properties: bool,
+    /// Write `\d`, `\s` and `\w` rather than the equal Unicode property, such as `\p{White_Space}` for `\s`.
+    ///
+    /// The meaning of the Perl classes depends on the engine (ASCII or Unicode) while the properties do not.
+    pub prefer_perl_over_property: bool,
+    /// How the direct form `[...]` and the negated form `[^...]` are compared to pick the cheaper.
+    pub cost_fn: CostModel,
+    /// The output is inserted between `\Q` and `\E`, as supported by `RegexFlavor::Pcre` but not by the `regex` crate.
+    ///
+    /// A single `char` is written as is, except `\` which would start `\E`,
+    /// any other class is written between `\E` and `\Q` to leave the quote.
+    pub quote_context: bool,
+    /// The output is used with the `s` flag set, so that `.` matches every `char` including `\n`.
+    ///
+    /// Disabled by default, so that the output is correct without any flag: the total class is written
+    /// as the full range and `.` is used for the class of every `char` except `\n` instead.
+    /// When enabled the total class is written `.`, and the class without `\n` is written `[^\n]`.
+    /// It does not apply to `RegexFlavor::Posix` where `.` always matches `\n`,
+    /// nor to `RegexFlavor::EcmaScript` where `.` never matches `\r` and the line separators
+    /// and the total class is written `[\s\S]`.
+    pub assume_dotall: bool,
+    /// Write every class between brackets, such as `[\d]` or `[a]`, so that it can be concatenated without care.
+    ///
+    /// The total class is then written as the full range rather than `.`.
+    /// It does not apply to `RegexFlavor::Posix`, where a single `^` can't be written between brackets.
+    pub always_bracket: bool,
+    /// The delimiter of the literal the output is embedded in, such as `/` for `/regex/` in JavaScript or `m//` in Perl.
+    ///
+    /// It is escaped like the `char` of `extra_escape`.
+    pub delimiter: Option<char>,
+    /// The greatest code point the engine can match, such as `0xFFFF` for an engine limited to the BMP.
+    ///
+    /// The class is clipped to the code points up to it before being written, and the negated forms
+    /// are relative to them. `to_regex_checked_with` returns an error instead if a member is dropped.
+    pub max_code_point: Option<u32>,
+    /// Write only ASCII, for JavaScript without the `u` flag: code points above U+007F are escaped as `\uXXXX`,
+    /// astral ones as UTF-16 surrogate pairs in an alternation, and the Unicode properties are written as ranges.
+    pub ascii_only: bool,
+    /// Write every candidate form, the Unicode property, the Perl class, the class embedding the Perl classes
+    /// and the direct and negated classes, and keep the shortest, rather than the first that applies.
+    ///
+    /// On a tie the forms are preferred in that order. This ignores `cost_fn` and `prefer_perl_over_property`,
+    /// and is slower since every form is written.
+    pub optimize_length: bool,
+}
+
+impl Default for RegexOptions {
+    fn default() -> Self {
+        Self {
+            escape_style: EscapeStyle::default(),
+            uppercase_hex: false,
+            use_set_ops: false,
+            flavor: RegexFlavor::default(),
+            use_class_backspace: false,
+            control_escape_style: ControlEscapeStyle::default(),
+            empty_as: EmptyClassStyle::default(),
+            extra_escape: vec![],
+            embed_classes: false,
+            allow_negation: true,
+            allow_perl_classes: true,
+            allow_unicode_properties: true,
+            prefer_perl_over_property: true,
+            cost_fn: CostModel::default(),
+            quote_context: false,
+            assume_dotall: false,
+            always_bracket: false,
+            delimiter: None,
+            max_code_point: None,
+            ascii_only: false,
+            optimize_length: false,
+            negation_style: NegationStyle::default(),
+        }
+    }
+}
+
+/// The syntax used to escape a code point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EscapeStyle {
+    /// `\u{00e9}`, as understood by the `regex` crate.
+    #[default]
+    RustUnicode,
+    /// `\N{U+00E9}`, as understood by Perl and PCRE2.
+    NamedUnicode,
+    /// `\xe9` for code points up to U+00FF and `\x{20ac}` above, as understood by PCRE2 and the `regex` crate.
+    #[doc(alias = "PcreHex")]
+    HexByte,
+    /// `\u00e9` for code points up to U+FFFF and `\u{1f600}` above, as understood by JavaScript with the `u` flag.
+    JavaScriptUnicode,
+    /// `\N{LATIN SMALL LETTER E WITH ACUTE}`, as understood by Perl.
+    ///
+    /// Code points without a name fall back to `\N{U+...}`, as do all of them without the `unicode-names` feature.
+    UnicodeName,
+}
+
+/// The syntax used to escape control characters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ControlEscapeStyle {
+    /// `\t`, `\n`, `\v`, `\f` and `\r`, plus `\a` and `\e` for PCRE.
+    #[default]
+    Named,
+    /// Every control character is escaped according to `escape_style`.
+    Hex,
+}
+
+/// The syntax used for the empty class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EmptyClassStyle {
+    /// `[]`, which is rejected by most engines including the `regex` crate.
+    #[default]
+    Brackets,
+    /// `[^\u{0000}-\u{10ffff}]`, the complement of every code point, according to `escape_style`.
+    ///
+    /// It is written even if `RegexOptions::allow_negation` is disabled.
+    NegatedTotal,
+}
+
+/// The syntax used for the negated Unicode properties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NegationStyle {
+    /// `\P{L}`, as understood by every flavor with Unicode properties.
+    #[default]
+    CapitalP,
+    /// `\p{^L}`, as understood by Perl, PCRE2 and RE2 but not by the `regex` crate.
+    ///
+    /// Only for `RegexFlavor::Pcre` and `RegexFlavor::Re2`, the other flavors write `CapitalP`.
+    CaretInside,
+}
+
+/// The cost used to choose between the direct and the negated form of a class, the direct form wins ties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CostModel {
+    /// The number of ranges written.
+    #[default]
+    RangeCount,
+    /// The length in bytes of the output.
+    StringLength,
+    /// The number of UTF-8 byte sequences needed to match the ranges written, as compiled by most automata based engines.
+    TransitionCount,
+}
+
+/// The regular expression engine the output is written for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RegexFlavor {
+    /// The `regex` crate.
+    #[default]
+    Rust,
+    /// The `regex` crate matching bytes with `regex::bytes::Regex`.
+    ///
+    /// The classes are written as for `Rust` but wrapped in `(?u:...)` unless they only match ASCII,
+    /// so their members, astral or not, match their UTF-8 encodings even if Unicode mode is disabled around them.
+    /// Bytes that are not valid UTF-8 are never matched, even by a negated class.
+    RustBytes,
+    /// PCRE2 and Perl.
+    Pcre,
+    /// POSIX bracket expressions, as used by `grep -E` and `awk`.
+    ///
+    /// Backslash is literal inside brackets, so `]` is placed first, `-` last and `^` anywhere but first.
+    Posix,
+    /// JavaScript without the `u` flag, matching UTF-16 code units.
+    ///
+    /// The Unicode properties and Perl classes are not used since they are unsupported or ASCII-only,
+    /// except `\w` for the ASCII word class,
+    /// and `EscapeStyle::JavaScriptUnicode` should be used since `\u{...}` requires the `u` flag.
+    /// The total class is written `[\s\S]`, which does not depend on the `s` flag.
+    EcmaScript,
+    /// RE2 and Go's `regexp`.
+    ///
+    /// Code points are escaped with `\x{...}` whatever the `escape_style`,
+    /// the Perl classes are not used since they are ASCII-only, except `\w` for the ASCII word class,
+    /// and the Unicode properties are limited to the abbreviated general categories and the scripts
+    /// whose ranges are the same in Unicode 15, which RE2 follows, and in the Unicode 16 tables of this crate.
+    Re2,
+}
+
+impl RegexFlavor {
+    #[inline]
+    pub(crate) fn supports_class_backspace(self) -> bool {
+        matches!(self, RegexFlavor::Pcre | RegexFlavor::EcmaScript)
+    }
+
+    /// The Perl classes `\d`, `\s` and `\w` are the Unicode ones, rather than ASCII-only.
+    #[inline]
+    pub(crate) fn has_unicode_perl_classes(self) -> bool {
+        matches!(self, RegexFlavor::Rust | RegexFlavor::RustBytes)
+    }
+
+    /// The nested classes can be combined with `&&`, `--` and `~~`.
+    #[inline]
+    pub(crate) fn supports_set_operations(self) -> bool {
+        matches!(self, RegexFlavor::Rust | RegexFlavor::RustBytes)
+    }
+
+    /// The negated Unicode properties can be written `\p{^L}`.
+    #[inline]
+    pub(crate) fn supports_caret_negation(self) -> bool {
+        matches!(self, RegexFlavor::Pcre | RegexFlavor::Re2)
+    }
+
+    /// JavaScript's `v` flag requires most punctuation, such as `{` and `}`, to be escaped inside brackets.
+    #[inline]
+    pub(crate) fn allows_literal_punctuation_in_class(self) -> bool {
+        !matches!(self, RegexFlavor::EcmaScript)
+    }
+}
+
+/// The representation chosen by `to_regex` with the default options, as returned by `explain_emission`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EmissionPlan {
+    /// The empty class, written `[]`.
+    Empty,
+    /// Every `char` except `\n`, written `.`.
+    Dot,
+    /// A single `char`, written alone.
+    SingleChar { c: char },
+    /// A Perl class such as `\d` or `\W`.
+    PerlClass { shorthand: String },
+    /// A Unicode property such as `\p{Greek}`, or `\P{Greek}` if negated.
+    NamedClass { name: String, negated: bool },
+    /// The ranges written between brackets, `[...]`.
+    DirectBracket { range_count: usize },
+    /// The ranges of the complement written between negated brackets, `[^...]`.
+    ComplementBracket { range_count: usize },
+}
+
+/// A Perl class, as written `\d`, `\s` or `\w`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PerlClass {
+    /// `\d`, the Unicode decimal numbers.
+    Digit,
+    /// `\s`, the Unicode white spaces.
+    Space,
+    /// `\w`, the Unicode word characters.
+    Word,
+}
+
+impl PerlClass {
+    /// Return the letter of the class, in uppercase if negated.
+    #[inline]
+    pub fn letter(self, negated: bool) -> char {
+        let letter = match self {
+            PerlClass::Digit => 'd',
+            PerlClass::Space => 's',
+            PerlClass::Word => 'w',
+        };
+        if negated {
+            letter.to_ascii_uppercase()
+        } else {
+            letter
+        }
+    }
+}
+
+/// A named class exactly equal to a class, as returned by `named_class`.
+///
+/// It is written by `Display` as in `to_regex`, such as `\D` or `\p{Lu}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NamedClass {
+    /// A Perl class such as `\d`, or `\D` if negated.
+    Perl { class: PerlClass, negated: bool },
+    /// A general category, binary property or script such as `\p{Greek}`, or `\P{Greek}` if negated.
+    ///
+    /// The general categories are given by their abbreviation, such as `Lu`.
+    Property { name: &'static str, negated: bool },
+    /// A Script_Extensions property such as `\p{scx=Adlam}`, or `\P{scx=Adlam}` if negated.
+    ScriptExtension { name: &'static str, negated: bool },
+    /// A Unicode block such as `\p{Block=Basic_Latin}`, only written for `RegexFlavor::Pcre`.
+    Block { name: &'static str, negated: bool },
+}
+
+impl NamedClass {
+    /// Write the class with the given syntax for the negated Unicode properties.
+    pub(crate) fn to_string_with(self, negation_style: NegationStyle) -> String {
+        let (key, name, negated) = match self {
+            NamedClass::Perl { class, negated } => return format!("\\{}", class.letter(negated)),
+            NamedClass::Property { name, negated } => ("", name, negated),
+            NamedClass::ScriptExtension { name, negated } => ("scx=", name, negated),
+            NamedClass::Block { name, negated } => ("Block=", name, negated),
+        };
+        match (negated, negation_style) {
+            (false, _) => format!("\\p{{{}{}}}", key, name),
+            (true, NegationStyle::CapitalP) => format!("\\P{{{}{}}}", key, name),
+            (true, NegationStyle::CaretInside) => format!("\\p{{^{}{}}}", key, name),
+        }
+    }
+}
+
+impl Display for NamedClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_string_with(NegationStyle::CapitalP))
+    }
+}
+
+/// An error returned when a class can't be faithfully written for the target flavor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LossyError {
+    /// The empty class, which has no valid syntax in the flavor.
+    EmptyClass,
+    /// A `char` that would have to be written literally but that the flavor can't match reliably.
+    UnsupportedChar(char),
+}
+
+impl Display for LossyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LossyError::EmptyClass => write!(f, "the empty class can't be written"),
+            LossyError::UnsupportedChar(c) => {
+                write!(f, "U+{:04X} can't be written", *c as u32)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LossyError {}
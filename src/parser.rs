@@ -0,0 +1,573 @@
+use std::fmt::Display;
+
+use irange::RangeSet;
+
+use crate::{char::Char, tokens};
+
+/// An error returned when a regular expression character class can't be parsed.
+///
+/// Each variant holds the byte offset at which the error was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A character that is not allowed at this position.
+    UnexpectedChar(usize),
+    /// The input ended before the class was complete.
+    UnexpectedEnd(usize),
+    /// A `[` without its closing `]`.
+    UnterminatedClass(usize),
+    /// A `\p{...}` or `[:...:]` name that is not known.
+    UnknownProperty(usize),
+    /// An escape sequence that is not supported or not a valid `char`.
+    InvalidEscape(usize),
+    /// A range whose start is greater than its end.
+    InvalidRange(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedChar(offset) => write!(f, "unexpected char at {}", offset),
+            ParseError::UnexpectedEnd(offset) => write!(f, "unexpected end at {}", offset),
+            ParseError::UnterminatedClass(offset) => {
+                write!(f, "unterminated class starting at {}", offset)
+            }
+            ParseError::UnknownProperty(offset) => write!(f, "unknown property at {}", offset),
+            ParseError::InvalidEscape(offset) => write!(f, "invalid escape at {}", offset),
+            ParseError::InvalidRange(offset) => write!(f, "invalid range at {}", offset),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Either a single `char`, which can be the bound of a range, or a set of `char`.
+enum Item {
+    Char(char),
+    Set(RangeSet<Char>),
+}
+
+impl Item {
+    fn into_range_set(self) -> RangeSet<Char> {
+        match self {
+            Item::Char(c) => RangeSet::new_from_range(Char::new(c)..=Char::new(c)),
+            Item::Set(set) => set,
+        }
+    }
+}
+
+/// Parse a class, ignoring the whitespace and the `#` comments around it if `extended`, as with the `x` flag.
+///
+/// Inside brackets, whitespace and `#` are literal as in PCRE, unlike the `regex` crate which ignores them there too.
+pub(crate) fn parse(regex: &str, extended: bool) -> Result<RangeSet<Char>, ParseError> {
+    let mut parser = Parser {
+        regex,
+        position: 0,
+        extended,
+    };
+    parser.skip_insignificant();
+    let range = parser.parse_atom()?;
+    parser.skip_insignificant();
+    if parser.position < regex.len() {
+        return Err(ParseError::UnexpectedChar(parser.position));
+    }
+    Ok(range)
+}
+
+struct Parser<'a> {
+    regex: &'a str,
+    position: usize,
+    extended: bool,
+}
+
+impl Parser<'_> {
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.regex[self.position..].chars().next()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    #[inline]
+    fn starts_with(&self, pattern: &str) -> bool {
+        self.regex[self.position..].starts_with(pattern)
+    }
+
+    /// Skip the whitespace and the comments up to the end of the line, if in extended mode.
+    fn skip_insignificant(&mut self) {
+        if !self.extended {
+            return;
+        }
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('#') => {
+                    self.position = match self.regex[self.position..].find('\n') {
+                        Some(length) => self.position + length + 1,
+                        None => self.regex.len(),
+                    };
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<RangeSet<Char>, ParseError> {
+        let start = self.position;
+        match self.next() {
+            None => Err(ParseError::UnexpectedEnd(start)),
+            Some('.') => {
+                Ok(RangeSet::new_from_range(Char::new('\n')..=Char::new('\n')).complement())
+            }
+            Some('[') => self.parse_class(start),
+            Some('\\') => Ok(self.parse_escape(start, false)?.into_range_set()),
+            Some('*' | '+' | '?' | '(' | ')' | '{' | '|' | '^' | '$') => {
+                Err(ParseError::UnexpectedChar(start))
+            }
+            Some(c) => Ok(Item::Char(c).into_range_set()),
+        }
+    }
+
+    /// Parse a bracket class, the opening `[` at `start` being already consumed.
+    fn parse_class(&mut self, start: usize) -> Result<RangeSet<Char>, ParseError> {
+        let negated = if self.peek() == Some('^') {
+            self.next();
+            true
+        } else {
+            false
+        };
+
+        let mut range = self.parse_class_union(start)?;
+        while self.starts_with("&&") {
+            self.position += 2;
+            range = range.intersection(&self.parse_class_union(start)?);
+        }
+        if self.next() != Some(']') {
+            return Err(ParseError::UnterminatedClass(start));
+        }
+
+        if negated {
+            Ok(range.complement())
+        } else {
+            Ok(range)
+        }
+    }
+
+    /// Parse the items of a bracket class up to the closing `]` or a `&&` operator.
+    fn parse_class_union(&mut self, start: usize) -> Result<RangeSet<Char>, ParseError> {
+        let mut range = RangeSet::empty();
+        loop {
+            if self.peek().is_none() {
+                return Err(ParseError::UnterminatedClass(start));
+            } else if self.peek() == Some(']') || self.starts_with("&&") {
+                return Ok(range);
+            }
+
+            let item = self.parse_class_item()?;
+            let item = match item {
+                Item::Char(min) if self.starts_with("-") && !self.starts_with("-]") => {
+                    let offset = self.position;
+                    self.position += 1;
+                    let max = match self.parse_class_item()? {
+                        Item::Char(max) => max,
+                        Item::Set(_) => return Err(ParseError::InvalidRange(offset)),
+                    };
+                    if min > max {
+                        return Err(ParseError::InvalidRange(offset));
+                    }
+                    Item::Set(RangeSet::new_from_range(Char::new(min)..=Char::new(max)))
+                }
+                item => item,
+            };
+            range = range.union(&item.into_range_set());
+        }
+    }
+
+    fn parse_class_item(&mut self) -> Result<Item, ParseError> {
+        let start = self.position;
+        match self.next() {
+            None => Err(ParseError::UnexpectedEnd(start)),
+            Some('[') if self.starts_with(":") => self.parse_posix_class(start),
+            Some('[') => Ok(Item::Set(self.parse_class(start)?)),
+            Some('\\') => self.parse_escape(start, true),
+            Some(c) => Ok(Item::Char(c)),
+        }
+    }
+
+    /// Parse a POSIX class such as `[:alpha:]` or `[:^alpha:]`, the opening `[` at `start` being already consumed.
+    fn parse_posix_class(&mut self, start: usize) -> Result<Item, ParseError> {
+        self.position += 1;
+        let negated = if self.peek() == Some('^') {
+            self.next();
+            true
+        } else {
+            false
+        };
+
+        let name_start = self.position;
+        let Some(length) = self.regex[name_start..].find(":]") else {
+            return Err(ParseError::UnterminatedClass(start));
+        };
+        self.position += length + 2;
+
+        let Some(ranges) = tokens::get_posix_class(&self.regex[name_start..name_start + length])
+        else {
+            return Err(ParseError::UnknownProperty(name_start));
+        };
+        let range = tokens::convert_from_range(ranges);
+        if negated {
+            Ok(Item::Set(range.complement()))
+        } else {
+            Ok(Item::Set(range))
+        }
+    }
+
+    /// Parse an escape sequence, the `\` at `start` being already consumed.
+    fn parse_escape(&mut self, start: usize, in_class: bool) -> Result<Item, ParseError> {
+        let Some(c) = self.next() else {
+            return Err(ParseError::UnexpectedEnd(self.position));
+        };
+        match c {
+            'd' | 's' | 'w' | 'D' | 'S' | 'W' => {
+                let ranges = tokens::get_perl_class_by_letter(c.to_ascii_lowercase())
+                    .ok_or(ParseError::InvalidEscape(start))?;
+                let range = tokens::convert_from_range(ranges);
+                if c.is_ascii_uppercase() {
+                    Ok(Item::Set(range.complement()))
+                } else {
+                    Ok(Item::Set(range))
+                }
+            }
+            'p' | 'P' => {
+                let mut name_start = self.position;
+                let name = if self.peek() == Some('{') {
+                    name_start += 1;
+                    let Some(length) = self.regex[name_start..].find('}') else {
+                        return Err(ParseError::UnexpectedEnd(self.regex.len()));
+                    };
+                    self.position = name_start + length + 1;
+                    &self.regex[name_start..name_start + length]
+                } else {
+                    let Some(letter) = self.next() else {
+                        return Err(ParseError::UnexpectedEnd(self.position));
+                    };
+                    &self.regex[name_start..name_start + letter.len_utf8()]
+                };
+
+                // `\p{^L}` is the same as `\P{L}`.
+                let (name, negated) = match name.strip_prefix('^') {
+                    Some(name) => (name, c == 'p'),
+                    None => (name, c == 'P'),
+                };
+                let Some(ranges) = tokens::get_class_by_name(name) else {
+                    return Err(ParseError::UnknownProperty(name_start));
+                };
+                let range = tokens::convert_from_range(ranges);
+                if negated {
+                    Ok(Item::Set(range.complement()))
+                } else {
+                    Ok(Item::Set(range))
+                }
+            }
+            'n' => Ok(Item::Char('\n')),
+            'r' => Ok(Item::Char('\r')),
+            't' => Ok(Item::Char('\t')),
+            'v' => Ok(Item::Char('\u{B}')),
+            'f' => Ok(Item::Char('\u{C}')),
+            'a' => Ok(Item::Char('\u{7}')),
+            'e' => Ok(Item::Char('\u{1B}')),
+            'b' if in_class => Ok(Item::Char('\u{8}')),
+            'u' | 'x' => {
+                let code = if self.peek() == Some('{') {
+                    self.next();
+                    let code = self.parse_hex(start, 1, 8)?;
+                    if self.next() != Some('}') {
+                        return Err(ParseError::InvalidEscape(start));
+                    }
+                    code
+                } else if c == 'u' {
+                    self.parse_hex(start, 4, 4)?
+                } else {
+                    self.parse_hex(start, 2, 2)?
+                };
+                Ok(Item::Char(
+                    char::from_u32(code).ok_or(ParseError::InvalidEscape(start))?,
+                ))
+            }
+            'N' => {
+                if !self.starts_with("{U+") {
+                    return Err(ParseError::InvalidEscape(start));
+                }
+                self.position += 3;
+                let code = self.parse_hex(start, 1, 8)?;
+                if self.next() != Some('}') {
+                    return Err(ParseError::InvalidEscape(start));
+                }
+                Ok(Item::Char(
+                    char::from_u32(code).ok_or(ParseError::InvalidEscape(start))?,
+                ))
+            }
+            c if c.is_ascii() && !c.is_ascii_alphanumeric() => Ok(Item::Char(c)),
+            _ => Err(ParseError::InvalidEscape(start)),
+        }
+    }
+
+    /// Parse between `min` and `max` hexadecimal digits.
+    fn parse_hex(&mut self, start: usize, min: usize, max: usize) -> Result<u32, ParseError> {
+        let digits = self.regex[self.position..]
+            .chars()
+            .take(max)
+            .take_while(char::is_ascii_hexdigit)
+            .count();
+        if digits < min {
+            return Err(ParseError::InvalidEscape(start));
+        }
+        let code = u32::from_str_radix(&self.regex[self.position..self.position + digits], 16)
+            .map_err(|_| ParseError::InvalidEscape(start))?;
+        self.position += digits;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use irange::range::AnyRange;
+
+    use crate::CharacterClass;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), ParseError> {
+        assert_eq!(RangeSet::empty(), RangeSet::<Char>::from_regex("[]")?);
+        assert_eq!(RangeSet::dot(), RangeSet::<Char>::from_regex(".")?);
+        assert_eq!(
+            RangeSet::new_from_range_char('a'..='z'),
+            RangeSet::<Char>::from_regex("[a-z]")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('.'..='.'),
+            RangeSet::<Char>::from_regex("\\.")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('\u{ab}'..='\u{ab}'),
+            RangeSet::<Char>::from_regex("\\u{00ab}")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('a'..='z').complement(),
+            RangeSet::<Char>::from_regex("[^a-z]")?
+        );
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('-')..=Char::new('-')),
+                AnyRange::from(Char::new('0')..=Char::new('9')),
+                AnyRange::from(Char::new('a')..=Char::new('a')),
+            ]),
+            RangeSet::<Char>::from_regex("[0-9a-]")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('a'..='e'),
+            RangeSet::<Char>::from_regex("[a-z&&[^f-z]]")?
+        );
+
+        Ok(())
+    }
+
+    #[cfg(all(
+        feature = "unicode-gencat",
+        feature = "unicode-props",
+        feature = "unicode-scripts"
+    ))]
+    #[test]
+    fn test_round_trip() -> Result<(), ParseError> {
+        use crate::options::{ControlEscapeStyle, EmptyClassStyle, EscapeStyle, RegexOptions};
+
+        let mut ranges = vec![
+            RangeSet::empty(),
+            RangeSet::total(),
+            RangeSet::dot(),
+            RangeSet::new_from_range_char('\0'..='\0'),
+            RangeSet::new_from_range_char('\u{ab}'..='\u{ab}'),
+            RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}'),
+            RangeSet::new_from_range_char('\u{10000}'..=char::MAX),
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('\t')..=Char::new('\r')),
+                AnyRange::from(Char::new('-')..=Char::new('.')),
+                AnyRange::from(Char::new('[')..=Char::new('^')),
+                AnyRange::from(Char::new('{')..=Char::new('}')),
+            ]),
+        ];
+        for regex in [
+            "\\d",
+            "\\s",
+            "\\w",
+            "\\p{Greek}",
+            "\\p{Lu}",
+            "\\p{scx=Adlam}",
+            "\\p{ASCII_Hex_Digit}",
+            "[\\p{Han}a-z]",
+        ] {
+            ranges.push(RangeSet::<Char>::from_regex(regex)?);
+        }
+        let complements = ranges
+            .iter()
+            .map(|range| range.complement())
+            .collect::<Vec<_>>();
+        ranges.extend(complements);
+
+        let options = [
+            RegexOptions::default(),
+            RegexOptions {
+                escape_style: EscapeStyle::HexByte,
+                ..Default::default()
+            },
+            RegexOptions {
+                escape_style: EscapeStyle::HexByte,
+                uppercase_hex: true,
+                ..Default::default()
+            },
+            RegexOptions {
+                escape_style: EscapeStyle::JavaScriptUnicode,
+                control_escape_style: ControlEscapeStyle::Hex,
+                ..Default::default()
+            },
+            RegexOptions {
+                escape_style: EscapeStyle::NamedUnicode,
+                empty_as: EmptyClassStyle::NegatedTotal,
+                ..Default::default()
+            },
+            RegexOptions {
+                always_bracket: true,
+                embed_classes: true,
+                ..Default::default()
+            },
+        ];
+        for range in ranges {
+            for options in &options {
+                let regex = range.to_regex_with(options);
+                assert_eq!(range, RangeSet::<Char>::from_regex(&regex)?, "{}", regex);
+            }
+        }
+
+        assert_eq!(
+            RangeSet::<Char>::from_regex("\\p{Greek}")?.complement(),
+            RangeSet::<Char>::from_regex("\\P{Greek}")?
+        );
+        assert_eq!(
+            RangeSet::<Char>::from_regex("\\P{Greek}")?,
+            RangeSet::<Char>::from_regex("\\p{^Greek}")?
+        );
+        assert_eq!(
+            RangeSet::<Char>::from_regex("\\p{Greek}")?,
+            RangeSet::<Char>::from_regex("\\P{^Greek}")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_posix_class() -> Result<(), ParseError> {
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('0')..=Char::new('9')),
+                AnyRange::from(Char::new('a')..=Char::new('f')),
+            ]),
+            RangeSet::<Char>::from_regex("[[:digit:]a-f]")?
+        );
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('\t')..=Char::new('\r')),
+                AnyRange::from(Char::new(' ')..=Char::new(' ')),
+            ])
+            .complement(),
+            RangeSet::<Char>::from_regex("[[:^space:]]")?
+        );
+        assert_eq!(
+            RangeSet::new_from_ranges(&[
+                AnyRange::from(Char::new('0')..=Char::new('9')),
+                AnyRange::from(Char::new('A')..=Char::new('Z')),
+                AnyRange::from(Char::new('a')..=Char::new('z')),
+            ]),
+            RangeSet::<Char>::from_regex("[[:alpha:]0-9]")?
+        );
+        assert_eq!(
+            Err(ParseError::UnknownProperty(3)),
+            RangeSet::<Char>::from_regex("[[:alphabet:]]")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() -> Result<(), ParseError> {
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd(0)),
+            RangeSet::<Char>::from_regex("")
+        );
+        assert_eq!(
+            Err(ParseError::UnterminatedClass(0)),
+            RangeSet::<Char>::from_regex("[a-z")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar(1)),
+            RangeSet::<Char>::from_regex("ab")
+        );
+        assert_eq!(
+            Err(ParseError::UnknownProperty(3)),
+            RangeSet::<Char>::from_regex("\\p{Unknown}")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidEscape(0)),
+            RangeSet::<Char>::from_regex("\\u{d800}")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidRange(2)),
+            RangeSet::<Char>::from_regex("[z-a]")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_extended() -> Result<(), ParseError> {
+        let regex = "
+            # The hexadecimal digits.
+            [0-9 a-f]  # With a space.
+        ";
+        let range = RangeSet::<Char>::from_extended_regex(regex)?;
+        assert_eq!("[ 0-9a-f]", range.to_regex());
+        assert_eq!(
+            RangeSet::new_from_range_char('#'..='#'),
+            RangeSet::<Char>::from_extended_regex("\\# # An escaped `#`.")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char(' '..=' '),
+            RangeSet::<Char>::from_extended_regex("\\ ")?
+        );
+        assert_eq!(
+            RangeSet::new_from_range_char('#'..='#'),
+            RangeSet::<Char>::from_extended_regex("[#]")?
+        );
+
+        assert_eq!(
+            Err(ParseError::UnexpectedChar(1)),
+            RangeSet::<Char>::from_regex("a # comment")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd(11)),
+            RangeSet::<Char>::from_extended_regex(" # comment\n")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedChar(2)),
+            RangeSet::<Char>::from_extended_regex("a b")
+        );
+
+        Ok(())
+    }
+}
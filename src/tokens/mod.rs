@@ -1,88 +1,441 @@
-use irange::RangeSet;
+use std::cmp::Ordering;
+
+use irange::{range::AnyRange, RangeSet};
 use once_cell::sync::Lazy;
-use unicode::{general_category, perl_decimal, perl_space, perl_word, property_bool, script};
+#[cfg(feature = "unicode-blocks")]
+use unicode::block;
+#[cfg(feature = "emoji")]
+use unicode::emoji;
+#[cfg(feature = "unicode-gencat")]
+use unicode::general_category;
+#[cfg(feature = "unicode-props")]
+use unicode::property_bool;
+use unicode::{case_folding_simple, perl_decimal, perl_space, perl_word};
+#[cfg(feature = "unicode-scripts")]
+use unicode::{script, script_extension};
 
-use crate::{Char, CharacterClass};
+use crate::{
+    options::{
+        ControlEscapeStyle, NamedClass, NegationStyle, PerlClass, RegexFlavor, RegexOptions,
+    },
+    Char, CharacterClass,
+};
 
-mod unicode;
+mod aliases;
+mod posix;
+#[cfg(any(feature = "unicode-gencat", feature = "unicode-scripts"))]
+mod re2;
+pub(crate) mod unicode;
 
-type ClassesCollection = Vec<(usize, &'static [(char, char)], &'static str)>;
+/// The number of ranges, the first and the last range of a class, cheaper to compare than the ranges.
+type Discriminator = (usize, (char, char), (char, char));
 
-static CLASSES_COLLECTION: Lazy<ClassesCollection> = Lazy::new(|| {
-    let mut collection = Vec::with_capacity(
-        general_category::BY_NAME.len() + property_bool::BY_NAME.len() + script::BY_NAME.len(),
-    );
+type ClassesCollection = Vec<(Discriminator, &'static [(char, char)], &'static str)>;
 
-    for (name, value) in general_category::BY_NAME {
-        collection.push((value.len(), *value, *name));
-    }
+type ClassesTable = &'static [(&'static str, &'static [(char, char)])];
 
-    for (name, value) in property_bool::BY_NAME {
-        collection.push((value.len(), *value, *name));
-    }
+/// The tables of the classes written by name, in the order they are looked up by name without a key.
+const CLASSES_TABLES: &[ClassesTable] = &[
+    #[cfg(feature = "unicode-gencat")]
+    general_category::BY_NAME,
+    #[cfg(feature = "unicode-props")]
+    property_bool::BY_NAME,
+    #[cfg(feature = "emoji")]
+    emoji::BY_NAME,
+    #[cfg(feature = "unicode-scripts")]
+    script::BY_NAME,
+];
 
-    for (name, value) in script::BY_NAME {
-        collection.push((value.len(), *value, *name));
-    }
+static CLASSES_COLLECTION: Lazy<ClassesCollection> = Lazy::new(|| {
+    let mut collection = CLASSES_TABLES
+        .iter()
+        .flat_map(|table| table.iter())
+        .map(|(name, value)| (get_discriminator(value), *value, *name))
+        .collect::<Vec<_>>();
 
     collection.sort_unstable_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
     collection
 });
 
-pub(super) fn identify_class(this: &RangeSet<Char>) -> Option<String> {
+#[inline]
+fn get_discriminator(ranges: &[(char, char)]) -> Discriminator {
+    (
+        ranges.len(),
+        ranges.first().copied().unwrap_or_default(),
+        ranges.last().copied().unwrap_or_default(),
+    )
+}
+
+/// Compare a class of `CLASSES_COLLECTION` with the given ranges, by their discriminators first.
+#[inline]
+fn compare_class(
+    (discriminator, ranges_cmp, _): &(Discriminator, &'static [(char, char)], &'static str),
+    query: &(Discriminator, &[(char, char)]),
+) -> Ordering {
+    discriminator
+        .cmp(&query.0)
+        .then_with(|| ranges_cmp.cmp(&query.1))
+}
+
+pub(super) fn identify_class(this: &RangeSet<Char>, options: &RegexOptions) -> Option<String> {
     if this.get_cardinality() == 1 {
-        if let Some(character) = identify_character(this.iter().next()?.to_char()) {
+        if let Some(character) = identify_character(this.iter().next()?.to_char(), options) {
             return Some(character.to_owned());
         }
     }
 
-    let char = convert_to_range(this);
-    if let Some(perl_class) = get_perl_class(&char) {
-        return Some(perl_class.to_owned());
+    if matches!(options.flavor, RegexFlavor::EcmaScript | RegexFlavor::Re2) {
+        if let Some(class) = identify_ascii_word(this, options) {
+            return Some(class.to_owned());
+        }
+    }
+
+    if options.flavor == RegexFlavor::EcmaScript {
+        return None;
+    }
+
+    let negation_style = if options.flavor.supports_caret_negation() {
+        options.negation_style
+    } else {
+        NegationStyle::CapitalP
+    };
+
+    if options.flavor == RegexFlavor::Re2 {
+        if !options.allow_unicode_properties {
+            return None;
+        }
+        if let Some(class) = find_re2_class(&convert_to_range(this)) {
+            return Some(format!("\\p{{{}}}", class));
+        }
+        if !options.allow_negation {
+            return None;
+        }
+        return find_re2_class(&convert_to_range(&this.complement())).map(|name| {
+            NamedClass::Property {
+                name,
+                negated: true,
+            }
+            .to_string_with(negation_style)
+        });
+    }
+
+    get_named_class(this, options).map(|class| class.to_string_with(negation_style))
+}
+
+/// Return `\w` or `\W` if the given range is the ASCII word class or its complement, for the flavors where `\w` is ASCII-only.
+fn identify_ascii_word(this: &RangeSet<Char>, options: &RegexOptions) -> Option<&'static str> {
+    if !options.allow_perl_classes {
+        return None;
     }
-    if let Some(class) = find_class(char.as_slice()) {
-        return Some(format!("\\p{{{}}}", class));
+    let word = get_posix_class("word")?;
+    if convert_to_range(this) == word {
+        Some("\\w")
+    } else if options.allow_negation && convert_to_range(&this.complement()) == word {
+        Some("\\W")
+    } else {
+        None
     }
+}
 
-    let this = this.complement();
-    let char = convert_to_range(&this);
-    if let Some(perl_class) = get_perl_class(&char) {
-        return Some(perl_class.to_uppercase());
+/// Return the Perl class or the Unicode property equal to the given range, or to its complement if negation is allowed.
+pub(crate) fn get_named_class(this: &RangeSet<Char>, options: &RegexOptions) -> Option<NamedClass> {
+    if let Some(class) = identify_named_class(&convert_to_range(this), false, options) {
+        return Some(class);
     }
-    if let Some(class) = find_class(char.as_slice()) {
-        return Some(format!("\\P{{{}}}", class));
+
+    if !options.allow_negation {
+        return None;
     }
 
-    None
+    identify_named_class(&convert_to_range(&this.complement()), true, options)
+}
+
+/// Return the Perl class or the Unicode property with exactly the given ranges, in the order preferred by `options`.
+fn identify_named_class(
+    ranges: &[(char, char)],
+    negated: bool,
+    options: &RegexOptions,
+) -> Option<NamedClass> {
+    let perl_class = || {
+        if !options.allow_perl_classes {
+            return None;
+        }
+        get_perl_class(ranges).map(|class| NamedClass::Perl { class, negated })
+    };
+    let property = || {
+        if !options.allow_unicode_properties {
+            return None;
+        }
+        find_class(ranges)
+            .map(|class| NamedClass::Property {
+                name: abbreviate_class(class),
+                negated,
+            })
+            .or_else(|| {
+                // Perl writes `\w` as a property too, unlike the other flavors.
+                (options.flavor == RegexFlavor::Pcre && is_perl_word(ranges)).then_some(
+                    NamedClass::Property {
+                        name: "Word",
+                        negated,
+                    },
+                )
+            })
+            .or_else(|| {
+                find_script_extension(ranges)
+                    .map(|name| NamedClass::ScriptExtension { name, negated })
+            })
+            .or_else(|| {
+                if options.flavor != RegexFlavor::Pcre {
+                    return None;
+                }
+                find_block(ranges).map(|name| NamedClass::Block { name, negated })
+            })
+    };
+
+    if options.prefer_perl_over_property {
+        perl_class().or_else(property)
+    } else {
+        property().or_else(perl_class)
+    }
 }
 
 #[inline]
 fn find_class(ranges: &[(char, char)]) -> Option<&'static str> {
+    let query = (get_discriminator(ranges), ranges);
     CLASSES_COLLECTION
-        .binary_search_by(|(len, ranges_cmp, _)| {
-            len.cmp(&ranges.len()).then_with(|| ranges_cmp.cmp(&ranges))
-        })
+        .binary_search_by(|class| compare_class(class, &query))
         .ok()
         .map(|index| CLASSES_COLLECTION[index].2)
 }
 
+/// Return the name of the Script_Extensions property with exactly the given ranges.
+///
+/// It is only looked up after `CLASSES_COLLECTION`, so a script with the same ranges is preferred.
+#[cfg(feature = "unicode-scripts")]
 #[inline]
-pub(super) fn identify_character(this: char) -> Option<&'static str> {
-    if this == '\n' {
-        Some("\\n")
-    } else if this == '\r' {
-        Some("\\r")
-    } else if this == '\t' {
-        Some("\\t")
-    } else if this == '\u{B}' {
-        Some("\\v")
-    } else {
+fn find_script_extension(ranges: &[(char, char)]) -> Option<&'static str> {
+    script_extension::BY_NAME
+        .iter()
+        .find(|(_, ranges_cmp)| *ranges_cmp == ranges)
+        .map(|(name, _)| *name)
+}
+
+#[cfg(not(feature = "unicode-scripts"))]
+#[inline]
+fn find_script_extension(_: &[(char, char)]) -> Option<&'static str> {
+    None
+}
+
+/// Return the name of the Unicode block with exactly the given ranges, only written for Perl.
+#[cfg(feature = "unicode-blocks")]
+#[inline]
+fn find_block(ranges: &[(char, char)]) -> Option<&'static str> {
+    block::BY_NAME
+        .iter()
+        .find(|(_, ranges_cmp)| *ranges_cmp == ranges)
+        .map(|(name, _)| *name)
+}
+
+#[cfg(not(feature = "unicode-blocks"))]
+#[inline]
+fn find_block(_: &[(char, char)]) -> Option<&'static str> {
+    None
+}
+
+/// Return the general category abbreviation or the script with exactly the given ranges understood by RE2.
+///
+/// The Perl classes are not used since they are ASCII-only in RE2.
+#[cfg(any(feature = "unicode-gencat", feature = "unicode-scripts"))]
+#[cfg_attr(not(feature = "unicode-gencat"), allow(clippy::unnecessary_find_map))]
+fn find_re2_class(ranges: &[(char, char)]) -> Option<&'static str> {
+    find_all_classes(ranges).into_iter().find_map(|name| {
+        #[cfg(feature = "unicode-gencat")]
+        if general_category::BY_NAME.iter().any(|(n, _)| *n == name) {
+            let abbreviation = abbreviate_class(name);
+            return re2::GENERAL_CATEGORY
+                .contains(&abbreviation)
+                .then_some(abbreviation);
+        }
+        #[cfg(feature = "unicode-scripts")]
+        if script::BY_NAME.iter().any(|(n, _)| *n == name) {
+            return (!re2::UNSUPPORTED_SCRIPT.contains(&name)).then_some(name);
+        }
         None
+    })
+}
+
+#[cfg(not(any(feature = "unicode-gencat", feature = "unicode-scripts")))]
+#[inline]
+fn find_re2_class(_: &[(char, char)]) -> Option<&'static str> {
+    None
+}
+
+/// Return the abbreviation of the given general category name, or the name itself for other classes.
+#[inline]
+fn abbreviate_class(name: &'static str) -> &'static str {
+    aliases::GENERAL_CATEGORY
+        .binary_search_by(|(long, _)| long.cmp(&name))
+        .map_or(name, |index| aliases::GENERAL_CATEGORY[index].1)
+}
+
+/// Return the name, abbreviated for general categories, of a class of `CLASSES_COLLECTION` with exactly the given ranges.
+#[inline]
+pub(crate) fn find_named_class(ranges: &[(char, char)]) -> Option<&'static str> {
+    find_class(ranges).map(abbreviate_class)
+}
+
+/// Return the name of every class of `CLASSES_COLLECTION` with exactly the given ranges, sorted.
+pub(crate) fn find_all_classes(ranges: &[(char, char)]) -> Vec<&'static str> {
+    let query = (get_discriminator(ranges), ranges);
+    let start =
+        CLASSES_COLLECTION.partition_point(|class| compare_class(class, &query) == Ordering::Less);
+    let mut names = CLASSES_COLLECTION[start..]
+        .iter()
+        .take_while(|(_, ranges_cmp, _)| *ranges_cmp == ranges)
+        .map(|(_, _, name)| *name)
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+#[inline]
+pub(super) fn identify_character(this: char, options: &RegexOptions) -> Option<&'static str> {
+    if options.control_escape_style == ControlEscapeStyle::Hex {
+        return None;
+    }
+    match (this, options.flavor) {
+        ('\n', _) => Some("\\n"),
+        ('\r', _) => Some("\\r"),
+        ('\t', _) => Some("\\t"),
+        ('\u{C}', _) => Some("\\f"),
+        // `\v` is the vertical whitespace class in PCRE.
+        ('\u{B}', RegexFlavor::Rust | RegexFlavor::EcmaScript | RegexFlavor::Re2) => Some("\\v"),
+        ('\u{7}', RegexFlavor::Pcre | RegexFlavor::Re2) => Some("\\a"),
+        ('\u{1B}', RegexFlavor::Pcre) => Some("\\e"),
+        _ => None,
+    }
+}
+
+/// Return the given range with every `char` equivalent to a member under Unicode simple case folding.
+pub(crate) fn case_fold(range: &RangeSet<Char>) -> RangeSet<Char> {
+    let equivalents = case_folding_simple::CASE_FOLDING_SIMPLE
+        .iter()
+        .filter(|(c, _)| range.contains(Char::new(*c)))
+        .flat_map(|(_, equivalents)| equivalents.iter())
+        .map(|c| AnyRange::from(Char::new(*c)..=Char::new(*c)))
+        .collect::<Vec<_>>();
+    range.union(&RangeSet::new_from_ranges(&equivalents))
+}
+
+/// Return the ranges of the Unicode property with the given name.
+///
+/// The name is matched ignoring case, spaces, `-` and `_`, and can be prefixed with `gc=`, `sc=` or `scx=`.
+/// General categories can also be given by their abbreviation, such as `Lu`.
+pub(crate) fn get_class_by_name(name: &str) -> Option<&'static [(char, char)]> {
+    let (key, value) = match name.split_once('=') {
+        Some((key, value)) => (Some(normalize_name(key)), value),
+        None => (None, name),
+    };
+    let mut value = normalize_name(value);
+    if matches!(key.as_deref(), None | Some("gc" | "generalcategory")) {
+        if let Some((long, _)) = aliases::GENERAL_CATEGORY
+            .iter()
+            .find(|(_, short)| normalize_name(short) == value)
+        {
+            value = normalize_name(long);
+        }
+    }
+
+    let tables: &[ClassesTable] = match key.as_deref() {
+        None => CLASSES_TABLES,
+        #[cfg(feature = "unicode-gencat")]
+        Some("gc" | "generalcategory") => &[general_category::BY_NAME],
+        #[cfg(feature = "unicode-scripts")]
+        Some("sc" | "script") => &[script::BY_NAME],
+        #[cfg(feature = "unicode-scripts")]
+        Some("scx" | "scriptextensions") => &[script_extension::BY_NAME],
+        #[cfg(feature = "unicode-blocks")]
+        Some("blk" | "block") => &[block::BY_NAME],
+        Some(_) => return None,
+    };
+
+    let ranges = tables
+        .iter()
+        .flat_map(|table| table.iter())
+        .find(|(name, _)| normalize_name(name) == value)
+        .map(|(_, ranges)| *ranges);
+    // Perl also writes the blocks with the `In` prefix, such as `\p{InBasic_Latin}`.
+    #[cfg(feature = "unicode-blocks")]
+    if ranges.is_none() && key.is_none() {
+        if let Some(value) = value.strip_prefix("in") {
+            return block::BY_NAME
+                .iter()
+                .find(|(name, _)| normalize_name(name) == value)
+                .map(|(_, ranges)| *ranges);
+        }
+    }
+    ranges
+}
+
+/// Return the ranges of the POSIX class with the given name, such as `alpha`.
+#[inline]
+pub(crate) fn get_posix_class(name: &str) -> Option<&'static [(char, char)]> {
+    posix::BY_NAME
+        .binary_search_by(|(n, _)| n.cmp(&name))
+        .ok()
+        .map(|index| posix::BY_NAME[index].1)
+}
+
+/// Return the name of the POSIX class written in place of the given ranges, such as `blank`.
+#[inline]
+pub(crate) fn identify_posix_class(ranges: &[(char, char)]) -> Option<&'static str> {
+    posix::EMITTED
+        .iter()
+        .find(|(_, class)| *class == ranges)
+        .map(|(name, _)| *name)
+}
+
+/// Return the name of the standard POSIX class with exactly the given ranges, `ascii` and `word` are not standard.
+#[inline]
+pub(crate) fn find_posix_class(ranges: &[(char, char)]) -> Option<&'static str> {
+    posix::BY_NAME
+        .iter()
+        .filter(|(name, _)| !matches!(*name, "ascii" | "word"))
+        .find(|(_, class)| *class == ranges)
+        .map(|(name, _)| *name)
+}
+
+/// Return the ranges of the Perl class with the given letter, such as `d` for `\d`.
+#[inline]
+pub(crate) fn get_perl_class_by_letter(letter: char) -> Option<&'static [(char, char)]> {
+    match letter {
+        'd' => Some(perl_decimal::DECIMAL_NUMBER),
+        's' => Some(perl_space::WHITE_SPACE),
+        'w' => Some(perl_word::PERL_WORD),
+        _ => None,
     }
 }
 
 #[inline]
-fn convert_to_range(range_set: &RangeSet<Char>) -> Vec<(char, char)> {
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[inline]
+pub(crate) fn convert_from_range(ranges: &[(char, char)]) -> RangeSet<Char> {
+    RangeSet::new_from_ranges(
+        &ranges
+            .iter()
+            .map(|(min, max)| AnyRange::from(Char::new(*min)..=Char::new(*max)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[inline]
+pub(crate) fn convert_to_range(range_set: &RangeSet<Char>) -> Vec<(char, char)> {
     range_set
         .0
         .chunks_exact(2)
@@ -91,13 +444,13 @@ fn convert_to_range(range_set: &RangeSet<Char>) -> Vec<(char, char)> {
 }
 
 #[inline]
-fn get_perl_class(range: &[(char, char)]) -> Option<&'static str> {
+fn get_perl_class(range: &[(char, char)]) -> Option<PerlClass> {
     if is_perl_decimal(range) {
-        Some("\\d")
+        Some(PerlClass::Digit)
     } else if is_perl_space(range) {
-        Some("\\s")
+        Some(PerlClass::Space)
     } else if is_perl_word(range) {
-        Some("\\w")
+        Some(PerlClass::Word)
     } else {
         None
     }
@@ -117,3 +470,319 @@ fn is_perl_space(range: &[(char, char)]) -> bool {
 fn is_perl_decimal(range: &[(char, char)]) -> bool {
     perl_decimal::DECIMAL_NUMBER == range
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_class() -> Result<(), String> {
+        for (_, ranges, _) in CLASSES_COLLECTION.iter() {
+            let expected = CLASSES_COLLECTION
+                .iter()
+                .filter(|(_, ranges_cmp, _)| ranges_cmp == ranges)
+                .map(|(_, _, name)| *name)
+                .collect::<Vec<_>>();
+            let name = find_class(ranges).ok_or("class not found")?;
+            assert!(expected.contains(&name), "{}", name);
+        }
+        for ranges in [&[][..], &[('a', 'z')], &[('0', '9'), ('a', 'z')]] {
+            assert_eq!(None, find_class(ranges));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_all_classes() -> Result<(), String> {
+        for (_, ranges, name) in CLASSES_COLLECTION.iter() {
+            let names = find_all_classes(ranges);
+            assert!(names.contains(name));
+            assert!(names.windows(2).all(|window| window[0] < window[1]));
+        }
+        assert!(find_all_classes(&[('a', 'z')]).is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-gencat", feature = "unicode-props"))]
+    #[test]
+    fn test_prefer_perl_over_property() -> Result<(), String> {
+        let space = convert_from_range(perl_space::WHITE_SPACE);
+        let decimal = convert_from_range(perl_decimal::DECIMAL_NUMBER);
+        let word = convert_from_range(perl_word::PERL_WORD);
+
+        let options = RegexOptions::default();
+        assert_eq!(Some("\\s".to_owned()), identify_class(&space, &options));
+        assert_eq!(
+            Some("\\S".to_owned()),
+            identify_class(&space.complement(), &options)
+        );
+        assert_eq!(Some("\\d".to_owned()), identify_class(&decimal, &options));
+
+        let options = RegexOptions {
+            prefer_perl_over_property: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Some("\\p{White_Space}".to_owned()),
+            identify_class(&space, &options)
+        );
+        assert_eq!(
+            Some("\\P{White_Space}".to_owned()),
+            identify_class(&space.complement(), &options)
+        );
+        assert_eq!(
+            Some("\\p{Nd}".to_owned()),
+            identify_class(&decimal, &options)
+        );
+        // There is no property equal to `\w`.
+        assert_eq!(Some("\\w".to_owned()), identify_class(&word, &options));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_word() -> Result<(), String> {
+        let word = convert_from_range(perl_word::PERL_WORD);
+        let ascii_word = convert_from_range(get_posix_class("word").unwrap());
+
+        for flavor in [RegexFlavor::Rust, RegexFlavor::Pcre] {
+            let options = RegexOptions {
+                flavor,
+                ..Default::default()
+            };
+            assert_eq!(Some("\\w".to_owned()), identify_class(&word, &options));
+            assert_eq!(
+                Some("\\W".to_owned()),
+                identify_class(&word.complement(), &options)
+            );
+            assert_eq!(None, identify_class(&ascii_word, &options));
+        }
+
+        let options = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            prefer_perl_over_property: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Some("\\p{Word}".to_owned()),
+            identify_class(&word, &options)
+        );
+        assert_eq!(
+            Some("\\P{Word}".to_owned()),
+            identify_class(&word.complement(), &options)
+        );
+        // The `regex` crate has no `Word` property.
+        let options = RegexOptions {
+            prefer_perl_over_property: false,
+            ..Default::default()
+        };
+        assert_eq!(Some("\\w".to_owned()), identify_class(&word, &options));
+
+        // `\w` is ASCII-only in JavaScript and RE2.
+        for flavor in [RegexFlavor::EcmaScript, RegexFlavor::Re2] {
+            let options = RegexOptions {
+                flavor,
+                ..Default::default()
+            };
+            assert_eq!(
+                Some("\\w".to_owned()),
+                identify_class(&ascii_word, &options)
+            );
+            assert_eq!(
+                Some("\\W".to_owned()),
+                identify_class(&ascii_word.complement(), &options)
+            );
+            assert_eq!(None, identify_class(&word, &options));
+            let options = RegexOptions {
+                flavor,
+                allow_perl_classes: false,
+                ..Default::default()
+            };
+            assert_eq!(None, identify_class(&ascii_word, &options));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-props", feature = "unicode-scripts"))]
+    #[test]
+    fn test_allow_named_classes() -> Result<(), String> {
+        let space = convert_from_range(perl_space::WHITE_SPACE);
+        let word = convert_from_range(perl_word::PERL_WORD);
+        let greek = convert_from_range(get_class_by_name("Greek").unwrap());
+
+        let options = RegexOptions {
+            allow_perl_classes: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Some("\\p{White_Space}".to_owned()),
+            identify_class(&space, &options)
+        );
+        assert_eq!(None, identify_class(&word, &options));
+        assert_eq!(
+            Some("\\p{Greek}".to_owned()),
+            identify_class(&greek, &options)
+        );
+
+        let options = RegexOptions {
+            allow_unicode_properties: false,
+            ..Default::default()
+        };
+        assert_eq!(Some("\\s".to_owned()), identify_class(&space, &options));
+        assert_eq!(None, identify_class(&greek, &options));
+        assert_eq!(None, identify_class(&greek.complement(), &options));
+
+        let options = RegexOptions {
+            flavor: RegexFlavor::Re2,
+            allow_unicode_properties: false,
+            ..Default::default()
+        };
+        assert_eq!(None, identify_class(&greek, &options));
+        assert_eq!(
+            Some("\\n".to_owned()),
+            identify_class(&RangeSet::new_from_range_char('\n'..='\n'), &options)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "unicode-blocks", feature = "unicode-scripts"))]
+    #[test]
+    fn test_blocks() -> Result<(), String> {
+        let basic_latin = convert_from_range(get_class_by_name("Block=Basic_Latin").unwrap());
+        assert_eq!(RangeSet::new_from_range_char('\0'..='\u{7F}'), basic_latin);
+        assert_eq!(
+            Some(block::BASIC_LATIN),
+            get_class_by_name("blk=basic latin")
+        );
+        assert_eq!(Some(block::EMOTICONS), get_class_by_name("InEmoticons"));
+        assert_eq!(Some(script::INHERITED), get_class_by_name("Inherited"));
+        assert_eq!(None, get_class_by_name("Block=Greek"));
+        assert_eq!(None, get_class_by_name("InGreek"));
+
+        let pcre = RegexOptions {
+            flavor: RegexFlavor::Pcre,
+            ..Default::default()
+        };
+        let emoticons = convert_from_range(block::EMOTICONS);
+        assert_eq!(
+            Some("\\p{Block=Emoticons}".to_owned()),
+            identify_class(&emoticons, &pcre)
+        );
+        assert_eq!(
+            Some("\\P{Block=Emoticons}".to_owned()),
+            identify_class(&emoticons.complement(), &pcre)
+        );
+        // The block is written when no script has the same ranges.
+        let range = convert_from_range(get_class_by_name("Block=Greek_and_Coptic").unwrap());
+        assert_eq!(
+            Some("\\p{Block=Greek_And_Coptic}".to_owned()),
+            identify_class(&range, &pcre)
+        );
+        // The script is preferred over the block with the same ranges.
+        let range = convert_from_range(get_class_by_name("Block=Braille_Patterns").unwrap());
+        assert_eq!(convert_from_range(script::BRAILLE), range);
+        assert_eq!(
+            Some("\\p{Braille}".to_owned()),
+            identify_class(&range, &pcre)
+        );
+
+        // The `regex` crate does not support the blocks.
+        let options = RegexOptions::default();
+        assert_eq!(None, identify_class(&emoticons, &options));
+        assert_eq!(None, identify_class(&basic_latin, &options));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-scripts")]
+    #[test]
+    fn test_script_extensions() -> Result<(), String> {
+        let options = RegexOptions::default();
+
+        let adlam = convert_from_range(get_class_by_name("scx=Adlam").unwrap());
+        assert_ne!(
+            adlam,
+            convert_from_range(get_class_by_name("sc=Adlam").unwrap())
+        );
+        assert_eq!(
+            Some("\\p{scx=Adlam}".to_owned()),
+            identify_class(&adlam, &options)
+        );
+        assert_eq!(
+            Some("\\P{scx=Adlam}".to_owned()),
+            identify_class(&adlam.complement(), &options)
+        );
+
+        let ahom = convert_from_range(get_class_by_name("Script_Extensions=Ahom").unwrap());
+        assert_eq!(
+            ahom,
+            convert_from_range(get_class_by_name("sc=Ahom").unwrap())
+        );
+        assert_eq!(
+            Some("\\p{Ahom}".to_owned()),
+            identify_class(&ahom, &options)
+        );
+
+        // The prolonged sound mark is shared between Hiragana and Katakana.
+        let hiragana = convert_from_range(get_class_by_name("scx=Hiragana").unwrap());
+        assert!(hiragana.contains(Char::new('\u{30FC}')));
+        let hiragana_script = convert_from_range(get_class_by_name("sc=Hiragana").unwrap());
+        assert!(!hiragana_script.contains(Char::new('\u{30FC}')));
+        assert_eq!(
+            Some("\\p{scx=Hiragana}".to_owned()),
+            identify_class(&hiragana, &options)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-gencat")]
+    #[test]
+    fn test_general_category_abbreviations() -> Result<(), String> {
+        assert!(aliases::GENERAL_CATEGORY
+            .windows(2)
+            .all(|window| window[0].0 < window[1].0));
+        for (long, short) in aliases::GENERAL_CATEGORY {
+            assert!(general_category::BY_NAME
+                .iter()
+                .any(|(name, _)| name == long));
+            assert_eq!(get_class_by_name(long), get_class_by_name(short));
+            assert_eq!(
+                get_class_by_name(long),
+                get_class_by_name(&format!("gc={}", short))
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "unicode-props")]
+    #[test]
+    fn test_hex_digit() -> Result<(), String> {
+        let hex_digit = convert_from_range(property_bool::HEX_DIGIT);
+        let ascii_hex_digit = convert_from_range(property_bool::ASCII_HEX_DIGIT);
+        assert_ne!(hex_digit, ascii_hex_digit);
+        assert!(hex_digit.contains(Char::new('\u{FF10}')));
+        assert!(!ascii_hex_digit.contains(Char::new('\u{FF10}')));
+
+        let options = RegexOptions::default();
+        assert_eq!(
+            Some("\\p{Hex_Digit}".to_owned()),
+            identify_class(&hex_digit, &options)
+        );
+        assert_eq!(
+            Some("\\p{ASCII_Hex_Digit}".to_owned()),
+            identify_class(&ascii_hex_digit, &options)
+        );
+        assert_eq!(
+            Some("\\P{Hex_Digit}".to_owned()),
+            identify_class(&hex_digit.complement(), &options)
+        );
+
+        Ok(())
+    }
+}
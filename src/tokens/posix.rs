@@ -0,0 +1,56 @@
+//! The POSIX bracket classes, as defined for the ASCII range.
+
+pub const BY_NAME: &[(&str, &[(char, char)])] = &[
+    ("alnum", ALNUM),
+    ("alpha", ALPHA),
+    ("ascii", ASCII),
+    ("blank", BLANK),
+    ("cntrl", CNTRL),
+    ("digit", DIGIT),
+    ("graph", GRAPH),
+    ("lower", LOWER),
+    ("print", PRINT),
+    ("punct", PUNCT),
+    ("space", SPACE),
+    ("upper", UPPER),
+    ("word", WORD),
+    ("xdigit", XDIGIT),
+];
+
+/// The classes written by name in POSIX bracket expressions.
+pub const EMITTED: &[(&str, &[(char, char)])] = &[
+    ("alnum", ALNUM),
+    ("blank", BLANK),
+    ("cntrl", CNTRL),
+    ("graph", GRAPH),
+    ("print", PRINT),
+    ("punct", PUNCT),
+];
+
+pub const ALNUM: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('a', 'z')];
+
+pub const ALPHA: &[(char, char)] = &[('A', 'Z'), ('a', 'z')];
+
+pub const ASCII: &[(char, char)] = &[('\0', '\x7F')];
+
+pub const BLANK: &[(char, char)] = &[('\t', '\t'), (' ', ' ')];
+
+pub const CNTRL: &[(char, char)] = &[('\0', '\x1F'), ('\x7F', '\x7F')];
+
+pub const DIGIT: &[(char, char)] = &[('0', '9')];
+
+pub const GRAPH: &[(char, char)] = &[('!', '~')];
+
+pub const LOWER: &[(char, char)] = &[('a', 'z')];
+
+pub const PRINT: &[(char, char)] = &[(' ', '~')];
+
+pub const PUNCT: &[(char, char)] = &[('!', '/'), (':', '@'), ('[', '`'), ('{', '~')];
+
+pub const SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+pub const UPPER: &[(char, char)] = &[('A', 'Z')];
+
+pub const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+
+pub const XDIGIT: &[(char, char)] = &[('0', '9'), ('A', 'F'), ('a', 'f')];
@@ -0,0 +1,30 @@
+//! The Unicode properties understood by RE2 and Go's `regexp`, which follow Unicode 15.
+
+/// The general categories, by abbreviation only, whose ranges are the same in Unicode 15 and 16.
+#[cfg(feature = "unicode-gencat")]
+pub const GENERAL_CATEGORY: &[&str] = &[
+    "Cc", "Cf", "Co", "Cs", "Lt", "Me", "Nl", "No", "Pc", "Pe", "Pf", "Pi", "Ps", "Sc", "Sk", "Z",
+    "Zl", "Zp", "Zs",
+];
+
+/// The scripts added or changed in Unicode 16.
+#[cfg(feature = "unicode-scripts")]
+pub const UNSUPPORTED_SCRIPT: &[&str] = &[
+    "Arabic",
+    "Balinese",
+    "Common",
+    "Cyrillic",
+    "Egyptian_Hieroglyphs",
+    "Garay",
+    "Gurung_Khema",
+    "Han",
+    "Kawi",
+    "Khitan_Small_Script",
+    "Kirat_Rai",
+    "Latin",
+    "Myanmar",
+    "Ol_Onal",
+    "Sunuwar",
+    "Todhri",
+    "Tulu_Tigalari",
+];
@@ -0,0 +1,1569 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   generate-blocks /tmp/ucd-16.0.0
+//
+// Unicode version: 16.0.0.
+//
+// generate-blocks.rs is run by generate-classes.sh.
+
+pub const BY_NAME: &'static [(&'static str, &'static [(char, char)])] = &[
+  ("Adlam", ADLAM), ("Aegean_Numbers", AEGEAN_NUMBERS), ("Ahom", AHOM),
+  ("Alchemical_Symbols", ALCHEMICAL_SYMBOLS),
+  ("Alphabetic_Presentation_Forms", ALPHABETIC_PRESENTATION_FORMS),
+  ("Anatolian_Hieroglyphs", ANATOLIAN_HIEROGLYPHS),
+  ("Ancient_Greek_Musical_Notation", ANCIENT_GREEK_MUSICAL_NOTATION),
+  ("Ancient_Greek_Numbers", ANCIENT_GREEK_NUMBERS),
+  ("Ancient_Symbols", ANCIENT_SYMBOLS), ("Arabic", ARABIC),
+  ("Arabic_Extended_A", ARABIC_EXTENDED_A),
+  ("Arabic_Extended_B", ARABIC_EXTENDED_B),
+  ("Arabic_Extended_C", ARABIC_EXTENDED_C),
+  ("Arabic_Mathematical_Alphabetic_Symbols", ARABIC_MATHEMATICAL_ALPHABETIC_SYMBOLS),
+  ("Arabic_Presentation_Forms_A", ARABIC_PRESENTATION_FORMS_A),
+  ("Arabic_Presentation_Forms_B", ARABIC_PRESENTATION_FORMS_B),
+  ("Arabic_Supplement", ARABIC_SUPPLEMENT), ("Armenian", ARMENIAN),
+  ("Arrows", ARROWS), ("Avestan", AVESTAN), ("Balinese", BALINESE),
+  ("Bamum", BAMUM), ("Bamum_Supplement", BAMUM_SUPPLEMENT),
+  ("Basic_Latin", BASIC_LATIN), ("Bassa_Vah", BASSA_VAH), ("Batak", BATAK),
+  ("Bengali", BENGALI), ("Bhaiksuki", BHAIKSUKI),
+  ("Block_Elements", BLOCK_ELEMENTS), ("Bopomofo", BOPOMOFO),
+  ("Bopomofo_Extended", BOPOMOFO_EXTENDED), ("Box_Drawing", BOX_DRAWING),
+  ("Brahmi", BRAHMI), ("Braille_Patterns", BRAILLE_PATTERNS),
+  ("Buginese", BUGINESE), ("Buhid", BUHID),
+  ("Byzantine_Musical_Symbols", BYZANTINE_MUSICAL_SYMBOLS),
+  ("CJK_Compatibility", CJK_COMPATIBILITY),
+  ("CJK_Compatibility_Forms", CJK_COMPATIBILITY_FORMS),
+  ("CJK_Compatibility_Ideographs", CJK_COMPATIBILITY_IDEOGRAPHS),
+  ("CJK_Compatibility_Ideographs_Supplement", CJK_COMPATIBILITY_IDEOGRAPHS_SUPPLEMENT),
+  ("CJK_Radicals_Supplement", CJK_RADICALS_SUPPLEMENT),
+  ("CJK_Strokes", CJK_STROKES),
+  ("CJK_Symbols_And_Punctuation", CJK_SYMBOLS_AND_PUNCTUATION),
+  ("CJK_Unified_Ideographs", CJK_UNIFIED_IDEOGRAPHS),
+  ("CJK_Unified_Ideographs_Extension_A", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_A),
+  ("CJK_Unified_Ideographs_Extension_B", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_B),
+  ("CJK_Unified_Ideographs_Extension_C", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_C),
+  ("CJK_Unified_Ideographs_Extension_D", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_D),
+  ("CJK_Unified_Ideographs_Extension_E", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_E),
+  ("CJK_Unified_Ideographs_Extension_F", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_F),
+  ("CJK_Unified_Ideographs_Extension_G", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_G),
+  ("CJK_Unified_Ideographs_Extension_H", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_H),
+  ("CJK_Unified_Ideographs_Extension_I", CJK_UNIFIED_IDEOGRAPHS_EXTENSION_I),
+  ("Carian", CARIAN), ("Caucasian_Albanian", CAUCASIAN_ALBANIAN),
+  ("Chakma", CHAKMA), ("Cham", CHAM), ("Cherokee", CHEROKEE),
+  ("Cherokee_Supplement", CHEROKEE_SUPPLEMENT),
+  ("Chess_Symbols", CHESS_SYMBOLS), ("Chorasmian", CHORASMIAN),
+  ("Combining_Diacritical_Marks", COMBINING_DIACRITICAL_MARKS),
+  ("Combining_Diacritical_Marks_Extended", COMBINING_DIACRITICAL_MARKS_EXTENDED),
+  ("Combining_Diacritical_Marks_For_Symbols", COMBINING_DIACRITICAL_MARKS_FOR_SYMBOLS),
+  ("Combining_Diacritical_Marks_Supplement", COMBINING_DIACRITICAL_MARKS_SUPPLEMENT),
+  ("Combining_Half_Marks", COMBINING_HALF_MARKS),
+  ("Common_Indic_Number_Forms", COMMON_INDIC_NUMBER_FORMS),
+  ("Control_Pictures", CONTROL_PICTURES), ("Coptic", COPTIC),
+  ("Coptic_Epact_Numbers", COPTIC_EPACT_NUMBERS),
+  ("Counting_Rod_Numerals", COUNTING_ROD_NUMERALS), ("Cuneiform", CUNEIFORM),
+  ("Cuneiform_Numbers_And_Punctuation", CUNEIFORM_NUMBERS_AND_PUNCTUATION),
+  ("Currency_Symbols", CURRENCY_SYMBOLS),
+  ("Cypriot_Syllabary", CYPRIOT_SYLLABARY), ("Cypro_Minoan", CYPRO_MINOAN),
+  ("Cyrillic", CYRILLIC), ("Cyrillic_Extended_A", CYRILLIC_EXTENDED_A),
+  ("Cyrillic_Extended_B", CYRILLIC_EXTENDED_B),
+  ("Cyrillic_Extended_C", CYRILLIC_EXTENDED_C),
+  ("Cyrillic_Extended_D", CYRILLIC_EXTENDED_D),
+  ("Cyrillic_Supplement", CYRILLIC_SUPPLEMENT), ("Deseret", DESERET),
+  ("Devanagari", DEVANAGARI), ("Devanagari_Extended", DEVANAGARI_EXTENDED),
+  ("Devanagari_Extended_A", DEVANAGARI_EXTENDED_A), ("Dingbats", DINGBATS),
+  ("Dives_Akuru", DIVES_AKURU), ("Dogra", DOGRA),
+  ("Domino_Tiles", DOMINO_TILES), ("Duployan", DUPLOYAN),
+  ("Early_Dynastic_Cuneiform", EARLY_DYNASTIC_CUNEIFORM),
+  ("Egyptian_Hieroglyph_Format_Controls", EGYPTIAN_HIEROGLYPH_FORMAT_CONTROLS),
+  ("Egyptian_Hieroglyphs", EGYPTIAN_HIEROGLYPHS),
+  ("Egyptian_Hieroglyphs_Extended_A", EGYPTIAN_HIEROGLYPHS_EXTENDED_A),
+  ("Elbasan", ELBASAN), ("Elymaic", ELYMAIC), ("Emoticons", EMOTICONS),
+  ("Enclosed_Alphanumeric_Supplement", ENCLOSED_ALPHANUMERIC_SUPPLEMENT),
+  ("Enclosed_Alphanumerics", ENCLOSED_ALPHANUMERICS),
+  ("Enclosed_CJK_Letters_And_Months", ENCLOSED_CJK_LETTERS_AND_MONTHS),
+  ("Enclosed_Ideographic_Supplement", ENCLOSED_IDEOGRAPHIC_SUPPLEMENT),
+  ("Ethiopic", ETHIOPIC), ("Ethiopic_Extended", ETHIOPIC_EXTENDED),
+  ("Ethiopic_Extended_A", ETHIOPIC_EXTENDED_A),
+  ("Ethiopic_Extended_B", ETHIOPIC_EXTENDED_B),
+  ("Ethiopic_Supplement", ETHIOPIC_SUPPLEMENT), ("Garay", GARAY),
+  ("General_Punctuation", GENERAL_PUNCTUATION),
+  ("Geometric_Shapes", GEOMETRIC_SHAPES),
+  ("Geometric_Shapes_Extended", GEOMETRIC_SHAPES_EXTENDED),
+  ("Georgian", GEORGIAN), ("Georgian_Extended", GEORGIAN_EXTENDED),
+  ("Georgian_Supplement", GEORGIAN_SUPPLEMENT), ("Glagolitic", GLAGOLITIC),
+  ("Glagolitic_Supplement", GLAGOLITIC_SUPPLEMENT), ("Gothic", GOTHIC),
+  ("Grantha", GRANTHA), ("Greek_And_Coptic", GREEK_AND_COPTIC),
+  ("Greek_Extended", GREEK_EXTENDED), ("Gujarati", GUJARATI),
+  ("Gunjala_Gondi", GUNJALA_GONDI), ("Gurmukhi", GURMUKHI),
+  ("Gurung_Khema", GURUNG_KHEMA),
+  ("Halfwidth_And_Fullwidth_Forms", HALFWIDTH_AND_FULLWIDTH_FORMS),
+  ("Hangul_Compatibility_Jamo", HANGUL_COMPATIBILITY_JAMO),
+  ("Hangul_Jamo", HANGUL_JAMO),
+  ("Hangul_Jamo_Extended_A", HANGUL_JAMO_EXTENDED_A),
+  ("Hangul_Jamo_Extended_B", HANGUL_JAMO_EXTENDED_B),
+  ("Hangul_Syllables", HANGUL_SYLLABLES),
+  ("Hanifi_Rohingya", HANIFI_ROHINGYA), ("Hanunoo", HANUNOO),
+  ("Hatran", HATRAN), ("Hebrew", HEBREW), ("Hiragana", HIRAGANA),
+  ("IPA_Extensions", IPA_EXTENSIONS),
+  ("Ideographic_Description_Characters", IDEOGRAPHIC_DESCRIPTION_CHARACTERS),
+  ("Ideographic_Symbols_And_Punctuation", IDEOGRAPHIC_SYMBOLS_AND_PUNCTUATION),
+  ("Imperial_Aramaic", IMPERIAL_ARAMAIC),
+  ("Indic_Siyaq_Numbers", INDIC_SIYAQ_NUMBERS),
+  ("Inscriptional_Pahlavi", INSCRIPTIONAL_PAHLAVI),
+  ("Inscriptional_Parthian", INSCRIPTIONAL_PARTHIAN), ("Javanese", JAVANESE),
+  ("Kaithi", KAITHI), ("Kaktovik_Numerals", KAKTOVIK_NUMERALS),
+  ("Kana_Extended_A", KANA_EXTENDED_A), ("Kana_Extended_B", KANA_EXTENDED_B),
+  ("Kana_Supplement", KANA_SUPPLEMENT), ("Kanbun", KANBUN),
+  ("Kangxi_Radicals", KANGXI_RADICALS), ("Kannada", KANNADA),
+  ("Katakana", KATAKANA),
+  ("Katakana_Phonetic_Extensions", KATAKANA_PHONETIC_EXTENSIONS),
+  ("Kawi", KAWI), ("Kayah_Li", KAYAH_LI), ("Kharoshthi", KHAROSHTHI),
+  ("Khitan_Small_Script", KHITAN_SMALL_SCRIPT), ("Khmer", KHMER),
+  ("Khmer_Symbols", KHMER_SYMBOLS), ("Khojki", KHOJKI),
+  ("Khudawadi", KHUDAWADI), ("Kirat_Rai", KIRAT_RAI), ("Lao", LAO),
+  ("Latin_1_Supplement", LATIN_1_SUPPLEMENT),
+  ("Latin_Extended_A", LATIN_EXTENDED_A),
+  ("Latin_Extended_Additional", LATIN_EXTENDED_ADDITIONAL),
+  ("Latin_Extended_B", LATIN_EXTENDED_B),
+  ("Latin_Extended_C", LATIN_EXTENDED_C),
+  ("Latin_Extended_D", LATIN_EXTENDED_D),
+  ("Latin_Extended_E", LATIN_EXTENDED_E),
+  ("Latin_Extended_F", LATIN_EXTENDED_F),
+  ("Latin_Extended_G", LATIN_EXTENDED_G), ("Lepcha", LEPCHA),
+  ("Letterlike_Symbols", LETTERLIKE_SYMBOLS), ("Limbu", LIMBU),
+  ("Linear_A", LINEAR_A), ("Linear_B_Ideograms", LINEAR_B_IDEOGRAMS),
+  ("Linear_B_Syllabary", LINEAR_B_SYLLABARY), ("Lisu", LISU),
+  ("Lisu_Supplement", LISU_SUPPLEMENT), ("Lycian", LYCIAN),
+  ("Lydian", LYDIAN), ("Mahajani", MAHAJANI),
+  ("Mahjong_Tiles", MAHJONG_TILES), ("Makasar", MAKASAR),
+  ("Malayalam", MALAYALAM), ("Mandaic", MANDAIC), ("Manichaean", MANICHAEAN),
+  ("Marchen", MARCHEN), ("Masaram_Gondi", MASARAM_GONDI),
+  ("Mathematical_Alphanumeric_Symbols", MATHEMATICAL_ALPHANUMERIC_SYMBOLS),
+  ("Mathematical_Operators", MATHEMATICAL_OPERATORS),
+  ("Mayan_Numerals", MAYAN_NUMERALS), ("Medefaidrin", MEDEFAIDRIN),
+  ("Meetei_Mayek", MEETEI_MAYEK),
+  ("Meetei_Mayek_Extensions", MEETEI_MAYEK_EXTENSIONS),
+  ("Mende_Kikakui", MENDE_KIKAKUI), ("Meroitic_Cursive", MEROITIC_CURSIVE),
+  ("Meroitic_Hieroglyphs", MEROITIC_HIEROGLYPHS), ("Miao", MIAO),
+  ("Miscellaneous_Mathematical_Symbols_A", MISCELLANEOUS_MATHEMATICAL_SYMBOLS_A),
+  ("Miscellaneous_Mathematical_Symbols_B", MISCELLANEOUS_MATHEMATICAL_SYMBOLS_B),
+  ("Miscellaneous_Symbols", MISCELLANEOUS_SYMBOLS),
+  ("Miscellaneous_Symbols_And_Arrows", MISCELLANEOUS_SYMBOLS_AND_ARROWS),
+  ("Miscellaneous_Symbols_And_Pictographs", MISCELLANEOUS_SYMBOLS_AND_PICTOGRAPHS),
+  ("Miscellaneous_Technical", MISCELLANEOUS_TECHNICAL), ("Modi", MODI),
+  ("Modifier_Tone_Letters", MODIFIER_TONE_LETTERS), ("Mongolian", MONGOLIAN),
+  ("Mongolian_Supplement", MONGOLIAN_SUPPLEMENT), ("Mro", MRO),
+  ("Multani", MULTANI), ("Musical_Symbols", MUSICAL_SYMBOLS),
+  ("Myanmar", MYANMAR), ("Myanmar_Extended_A", MYANMAR_EXTENDED_A),
+  ("Myanmar_Extended_B", MYANMAR_EXTENDED_B),
+  ("Myanmar_Extended_C", MYANMAR_EXTENDED_C), ("NKo", NKO),
+  ("Nabataean", NABATAEAN), ("Nag_Mundari", NAG_MUNDARI),
+  ("Nandinagari", NANDINAGARI), ("New_Tai_Lue", NEW_TAI_LUE), ("Newa", NEWA),
+  ("Number_Forms", NUMBER_FORMS), ("Nushu", NUSHU),
+  ("Nyiakeng_Puachue_Hmong", NYIAKENG_PUACHUE_HMONG), ("Ogham", OGHAM),
+  ("Ol_Chiki", OL_CHIKI), ("Ol_Onal", OL_ONAL),
+  ("Old_Hungarian", OLD_HUNGARIAN), ("Old_Italic", OLD_ITALIC),
+  ("Old_North_Arabian", OLD_NORTH_ARABIAN), ("Old_Permic", OLD_PERMIC),
+  ("Old_Persian", OLD_PERSIAN), ("Old_Sogdian", OLD_SOGDIAN),
+  ("Old_South_Arabian", OLD_SOUTH_ARABIAN), ("Old_Turkic", OLD_TURKIC),
+  ("Old_Uyghur", OLD_UYGHUR),
+  ("Optical_Character_Recognition", OPTICAL_CHARACTER_RECOGNITION),
+  ("Oriya", ORIYA), ("Ornamental_Dingbats", ORNAMENTAL_DINGBATS),
+  ("Osage", OSAGE), ("Osmanya", OSMANYA),
+  ("Ottoman_Siyaq_Numbers", OTTOMAN_SIYAQ_NUMBERS),
+  ("Pahawh_Hmong", PAHAWH_HMONG), ("Palmyrene", PALMYRENE),
+  ("Pau_Cin_Hau", PAU_CIN_HAU), ("Phags_Pa", PHAGS_PA),
+  ("Phaistos_Disc", PHAISTOS_DISC), ("Phoenician", PHOENICIAN),
+  ("Phonetic_Extensions", PHONETIC_EXTENSIONS),
+  ("Phonetic_Extensions_Supplement", PHONETIC_EXTENSIONS_SUPPLEMENT),
+  ("Playing_Cards", PLAYING_CARDS), ("Private_Use_Area", PRIVATE_USE_AREA),
+  ("Psalter_Pahlavi", PSALTER_PAHLAVI), ("Rejang", REJANG),
+  ("Rumi_Numeral_Symbols", RUMI_NUMERAL_SYMBOLS), ("Runic", RUNIC),
+  ("Samaritan", SAMARITAN), ("Saurashtra", SAURASHTRA), ("Sharada", SHARADA),
+  ("Shavian", SHAVIAN),
+  ("Shorthand_Format_Controls", SHORTHAND_FORMAT_CONTROLS),
+  ("Siddham", SIDDHAM), ("Sinhala", SINHALA),
+  ("Sinhala_Archaic_Numbers", SINHALA_ARCHAIC_NUMBERS),
+  ("Small_Form_Variants", SMALL_FORM_VARIANTS),
+  ("Small_Kana_Extension", SMALL_KANA_EXTENSION), ("Sogdian", SOGDIAN),
+  ("Sora_Sompeng", SORA_SOMPENG), ("Soyombo", SOYOMBO),
+  ("Spacing_Modifier_Letters", SPACING_MODIFIER_LETTERS),
+  ("Specials", SPECIALS), ("Sundanese", SUNDANESE),
+  ("Sundanese_Supplement", SUNDANESE_SUPPLEMENT), ("Sunuwar", SUNUWAR),
+  ("Superscripts_And_Subscripts", SUPERSCRIPTS_AND_SUBSCRIPTS),
+  ("Supplemental_Arrows_A", SUPPLEMENTAL_ARROWS_A),
+  ("Supplemental_Arrows_B", SUPPLEMENTAL_ARROWS_B),
+  ("Supplemental_Arrows_C", SUPPLEMENTAL_ARROWS_C),
+  ("Supplemental_Mathematical_Operators", SUPPLEMENTAL_MATHEMATICAL_OPERATORS),
+  ("Supplemental_Punctuation", SUPPLEMENTAL_PUNCTUATION),
+  ("Supplemental_Symbols_And_Pictographs", SUPPLEMENTAL_SYMBOLS_AND_PICTOGRAPHS),
+  ("Supplementary_Private_Use_Area_A", SUPPLEMENTARY_PRIVATE_USE_AREA_A),
+  ("Supplementary_Private_Use_Area_B", SUPPLEMENTARY_PRIVATE_USE_AREA_B),
+  ("Sutton_SignWriting", SUTTON_SIGNWRITING), ("Syloti_Nagri", SYLOTI_NAGRI),
+  ("Symbols_And_Pictographs_Extended_A", SYMBOLS_AND_PICTOGRAPHS_EXTENDED_A),
+  ("Symbols_For_Legacy_Computing", SYMBOLS_FOR_LEGACY_COMPUTING),
+  ("Symbols_For_Legacy_Computing_Supplement", SYMBOLS_FOR_LEGACY_COMPUTING_SUPPLEMENT),
+  ("Syriac", SYRIAC), ("Syriac_Supplement", SYRIAC_SUPPLEMENT),
+  ("Tagalog", TAGALOG), ("Tagbanwa", TAGBANWA), ("Tags", TAGS),
+  ("Tai_Le", TAI_LE), ("Tai_Tham", TAI_THAM), ("Tai_Viet", TAI_VIET),
+  ("Tai_Xuan_Jing_Symbols", TAI_XUAN_JING_SYMBOLS), ("Takri", TAKRI),
+  ("Tamil", TAMIL), ("Tamil_Supplement", TAMIL_SUPPLEMENT),
+  ("Tangsa", TANGSA), ("Tangut", TANGUT),
+  ("Tangut_Components", TANGUT_COMPONENTS),
+  ("Tangut_Supplement", TANGUT_SUPPLEMENT), ("Telugu", TELUGU),
+  ("Thaana", THAANA), ("Thai", THAI), ("Tibetan", TIBETAN),
+  ("Tifinagh", TIFINAGH), ("Tirhuta", TIRHUTA), ("Todhri", TODHRI),
+  ("Toto", TOTO), ("Transport_And_Map_Symbols", TRANSPORT_AND_MAP_SYMBOLS),
+  ("Tulu_Tigalari", TULU_TIGALARI), ("Ugaritic", UGARITIC),
+  ("Unified_Canadian_Aboriginal_Syllabics", UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS),
+  ("Unified_Canadian_Aboriginal_Syllabics_Extended", UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS_EXTENDED),
+  ("Unified_Canadian_Aboriginal_Syllabics_Extended_A", UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS_EXTENDED_A),
+  ("Vai", VAI), ("Variation_Selectors", VARIATION_SELECTORS),
+  ("Variation_Selectors_Supplement", VARIATION_SELECTORS_SUPPLEMENT),
+  ("Vedic_Extensions", VEDIC_EXTENSIONS), ("Vertical_Forms", VERTICAL_FORMS),
+  ("Vithkuqi", VITHKUQI), ("Wancho", WANCHO), ("Warang_Citi", WARANG_CITI),
+  ("Yezidi", YEZIDI), ("Yi_Radicals", YI_RADICALS),
+  ("Yi_Syllables", YI_SYLLABLES),
+  ("Yijing_Hexagram_Symbols", YIJING_HEXAGRAM_SYMBOLS),
+  ("Zanabazar_Square", ZANABAZAR_SQUARE),
+  ("Znamenny_Musical_Notation", ZNAMENNY_MUSICAL_NOTATION),
+];
+
+pub const ADLAM: &'static [(char, char)] = &[
+  ('𞤀', '𞥟'),
+];
+
+pub const AEGEAN_NUMBERS: &'static [(char, char)] = &[
+  ('𐄀', '𐄿'),
+];
+
+pub const AHOM: &'static [(char, char)] = &[
+  ('𑜀', '\u{1174f}'),
+];
+
+pub const ALCHEMICAL_SYMBOLS: &'static [(char, char)] = &[
+  ('🜀', '🝿'),
+];
+
+pub const ALPHABETIC_PRESENTATION_FORMS: &'static [(char, char)] = &[
+  ('ﬀ', 'ﭏ'),
+];
+
+pub const ANATOLIAN_HIEROGLYPHS: &'static [(char, char)] = &[
+  ('𔐀', '\u{1467f}'),
+];
+
+pub const ANCIENT_GREEK_MUSICAL_NOTATION: &'static [(char, char)] = &[
+  ('𝈀', '\u{1d24f}'),
+];
+
+pub const ANCIENT_GREEK_NUMBERS: &'static [(char, char)] = &[
+  ('𐅀', '\u{1018f}'),
+];
+
+pub const ANCIENT_SYMBOLS: &'static [(char, char)] = &[
+  ('𐆐', '\u{101cf}'),
+];
+
+pub const ARABIC: &'static [(char, char)] = &[
+  ('\u{600}', 'ۿ'),
+];
+
+pub const ARABIC_EXTENDED_A: &'static [(char, char)] = &[
+  ('ࢠ', '\u{8ff}'),
+];
+
+pub const ARABIC_EXTENDED_B: &'static [(char, char)] = &[
+  ('ࡰ', '\u{89f}'),
+];
+
+pub const ARABIC_EXTENDED_C: &'static [(char, char)] = &[
+  ('\u{10ec0}', '\u{10eff}'),
+];
+
+pub const ARABIC_MATHEMATICAL_ALPHABETIC_SYMBOLS: &'static [(char, char)] = &[
+  ('𞸀', '\u{1eeff}'),
+];
+
+pub const ARABIC_PRESENTATION_FORMS_A: &'static [(char, char)] = &[
+  ('ﭐ', '﷿'),
+];
+
+pub const ARABIC_PRESENTATION_FORMS_B: &'static [(char, char)] = &[
+  ('ﹰ', '\u{feff}'),
+];
+
+pub const ARABIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ݐ', 'ݿ'),
+];
+
+pub const ARMENIAN: &'static [(char, char)] = &[
+  ('\u{530}', '֏'),
+];
+
+pub const ARROWS: &'static [(char, char)] = &[
+  ('←', '⇿'),
+];
+
+pub const AVESTAN: &'static [(char, char)] = &[
+  ('𐬀', '𐬿'),
+];
+
+pub const BALINESE: &'static [(char, char)] = &[
+  ('\u{1b00}', '᭿'),
+];
+
+pub const BAMUM: &'static [(char, char)] = &[
+  ('ꚠ', '\u{a6ff}'),
+];
+
+pub const BAMUM_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𖠀', '\u{16a3f}'),
+];
+
+pub const BASIC_LATIN: &'static [(char, char)] = &[
+  ('\0', '\u{7f}'),
+];
+
+pub const BASSA_VAH: &'static [(char, char)] = &[
+  ('𖫐', '\u{16aff}'),
+];
+
+pub const BATAK: &'static [(char, char)] = &[
+  ('ᯀ', '᯿'),
+];
+
+pub const BENGALI: &'static [(char, char)] = &[
+  ('ঀ', '\u{9ff}'),
+];
+
+pub const BHAIKSUKI: &'static [(char, char)] = &[
+  ('𑰀', '\u{11c6f}'),
+];
+
+pub const BLOCK_ELEMENTS: &'static [(char, char)] = &[
+  ('▀', '▟'),
+];
+
+pub const BOPOMOFO: &'static [(char, char)] = &[
+  ('\u{3100}', 'ㄯ'),
+];
+
+pub const BOPOMOFO_EXTENDED: &'static [(char, char)] = &[
+  ('ㆠ', 'ㆿ'),
+];
+
+pub const BOX_DRAWING: &'static [(char, char)] = &[
+  ('─', '╿'),
+];
+
+pub const BRAHMI: &'static [(char, char)] = &[
+  ('𑀀', '\u{1107f}'),
+];
+
+pub const BRAILLE_PATTERNS: &'static [(char, char)] = &[
+  ('⠀', '⣿'),
+];
+
+pub const BUGINESE: &'static [(char, char)] = &[
+  ('ᨀ', '᨟'),
+];
+
+pub const BUHID: &'static [(char, char)] = &[
+  ('ᝀ', '\u{175f}'),
+];
+
+pub const BYZANTINE_MUSICAL_SYMBOLS: &'static [(char, char)] = &[
+  ('𝀀', '\u{1d0ff}'),
+];
+
+pub const CJK_COMPATIBILITY: &'static [(char, char)] = &[
+  ('㌀', '㏿'),
+];
+
+pub const CJK_COMPATIBILITY_FORMS: &'static [(char, char)] = &[
+  ('︰', '﹏'),
+];
+
+pub const CJK_COMPATIBILITY_IDEOGRAPHS: &'static [(char, char)] = &[
+  ('豈', '\u{faff}'),
+];
+
+pub const CJK_COMPATIBILITY_IDEOGRAPHS_SUPPLEMENT: &'static [(char, char)] = &[
+  ('丽', '\u{2fa1f}'),
+];
+
+pub const CJK_RADICALS_SUPPLEMENT: &'static [(char, char)] = &[
+  ('⺀', '\u{2eff}'),
+];
+
+pub const CJK_STROKES: &'static [(char, char)] = &[
+  ('㇀', '㇯'),
+];
+
+pub const CJK_SYMBOLS_AND_PUNCTUATION: &'static [(char, char)] = &[
+  ('\u{3000}', '〿'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS: &'static [(char, char)] = &[
+  ('一', '鿿'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_A: &'static [(char, char)] = &[
+  ('㐀', '䶿'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_B: &'static [(char, char)] = &[
+  ('𠀀', '𪛟'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_C: &'static [(char, char)] = &[
+  ('𪜀', '𫜿'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_D: &'static [(char, char)] = &[
+  ('𫝀', '\u{2b81f}'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_E: &'static [(char, char)] = &[
+  ('𫠠', '\u{2ceaf}'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_F: &'static [(char, char)] = &[
+  ('𬺰', '\u{2ebef}'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_G: &'static [(char, char)] = &[
+  ('𰀀', '\u{3134f}'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_H: &'static [(char, char)] = &[
+  ('𱍐', '𲎯'),
+];
+
+pub const CJK_UNIFIED_IDEOGRAPHS_EXTENSION_I: &'static [(char, char)] = &[
+  ('𮯰', '\u{2ee5f}'),
+];
+
+pub const CARIAN: &'static [(char, char)] = &[
+  ('𐊠', '\u{102df}'),
+];
+
+pub const CAUCASIAN_ALBANIAN: &'static [(char, char)] = &[
+  ('𐔰', '𐕯'),
+];
+
+pub const CHAKMA: &'static [(char, char)] = &[
+  ('\u{11100}', '\u{1114f}'),
+];
+
+pub const CHAM: &'static [(char, char)] = &[
+  ('ꨀ', '꩟'),
+];
+
+pub const CHEROKEE: &'static [(char, char)] = &[
+  ('Ꭰ', '\u{13ff}'),
+];
+
+pub const CHEROKEE_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ꭰ', 'ꮿ'),
+];
+
+pub const CHESS_SYMBOLS: &'static [(char, char)] = &[
+  ('🨀', '\u{1fa6f}'),
+];
+
+pub const CHORASMIAN: &'static [(char, char)] = &[
+  ('𐾰', '\u{10fdf}'),
+];
+
+pub const COMBINING_DIACRITICAL_MARKS: &'static [(char, char)] = &[
+  ('\u{300}', '\u{36f}'),
+];
+
+pub const COMBINING_DIACRITICAL_MARKS_EXTENDED: &'static [(char, char)] = &[
+  ('\u{1ab0}', '\u{1aff}'),
+];
+
+pub const COMBINING_DIACRITICAL_MARKS_FOR_SYMBOLS: &'static [(char, char)] = &[
+  ('\u{20d0}', '\u{20ff}'),
+];
+
+pub const COMBINING_DIACRITICAL_MARKS_SUPPLEMENT: &'static [(char, char)] = &[
+  ('\u{1dc0}', '\u{1dff}'),
+];
+
+pub const COMBINING_HALF_MARKS: &'static [(char, char)] = &[
+  ('\u{fe20}', '\u{fe2f}'),
+];
+
+pub const COMMON_INDIC_NUMBER_FORMS: &'static [(char, char)] = &[
+  ('꠰', '\u{a83f}'),
+];
+
+pub const CONTROL_PICTURES: &'static [(char, char)] = &[
+  ('␀', '\u{243f}'),
+];
+
+pub const COPTIC: &'static [(char, char)] = &[
+  ('Ⲁ', '⳿'),
+];
+
+pub const COPTIC_EPACT_NUMBERS: &'static [(char, char)] = &[
+  ('\u{102e0}', '\u{102ff}'),
+];
+
+pub const COUNTING_ROD_NUMERALS: &'static [(char, char)] = &[
+  ('𝍠', '\u{1d37f}'),
+];
+
+pub const CUNEIFORM: &'static [(char, char)] = &[
+  ('𒀀', '\u{123ff}'),
+];
+
+pub const CUNEIFORM_NUMBERS_AND_PUNCTUATION: &'static [(char, char)] = &[
+  ('𒐀', '\u{1247f}'),
+];
+
+pub const CURRENCY_SYMBOLS: &'static [(char, char)] = &[
+  ('₠', '\u{20cf}'),
+];
+
+pub const CYPRIOT_SYLLABARY: &'static [(char, char)] = &[
+  ('𐠀', '𐠿'),
+];
+
+pub const CYPRO_MINOAN: &'static [(char, char)] = &[
+  ('𒾐', '\u{12fff}'),
+];
+
+pub const CYRILLIC: &'static [(char, char)] = &[
+  ('Ѐ', 'ӿ'),
+];
+
+pub const CYRILLIC_EXTENDED_A: &'static [(char, char)] = &[
+  ('\u{2de0}', '\u{2dff}'),
+];
+
+pub const CYRILLIC_EXTENDED_B: &'static [(char, char)] = &[
+  ('Ꙁ', '\u{a69f}'),
+];
+
+pub const CYRILLIC_EXTENDED_C: &'static [(char, char)] = &[
+  ('ᲀ', '\u{1c8f}'),
+];
+
+pub const CYRILLIC_EXTENDED_D: &'static [(char, char)] = &[
+  ('𞀰', '\u{1e08f}'),
+];
+
+pub const CYRILLIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('Ԁ', 'ԯ'),
+];
+
+pub const DESERET: &'static [(char, char)] = &[
+  ('𐐀', '𐑏'),
+];
+
+pub const DEVANAGARI: &'static [(char, char)] = &[
+  ('\u{900}', 'ॿ'),
+];
+
+pub const DEVANAGARI_EXTENDED: &'static [(char, char)] = &[
+  ('\u{a8e0}', '\u{a8ff}'),
+];
+
+pub const DEVANAGARI_EXTENDED_A: &'static [(char, char)] = &[
+  ('𑬀', '\u{11b5f}'),
+];
+
+pub const DINGBATS: &'static [(char, char)] = &[
+  ('✀', '➿'),
+];
+
+pub const DIVES_AKURU: &'static [(char, char)] = &[
+  ('𑤀', '\u{1195f}'),
+];
+
+pub const DOGRA: &'static [(char, char)] = &[
+  ('𑠀', '\u{1184f}'),
+];
+
+pub const DOMINO_TILES: &'static [(char, char)] = &[
+  ('🀰', '\u{1f09f}'),
+];
+
+pub const DUPLOYAN: &'static [(char, char)] = &[
+  ('𛰀', '𛲟'),
+];
+
+pub const EARLY_DYNASTIC_CUNEIFORM: &'static [(char, char)] = &[
+  ('𒒀', '\u{1254f}'),
+];
+
+pub const EGYPTIAN_HIEROGLYPH_FORMAT_CONTROLS: &'static [(char, char)] = &[
+  ('\u{13430}', '\u{1345f}'),
+];
+
+pub const EGYPTIAN_HIEROGLYPHS: &'static [(char, char)] = &[
+  ('𓀀', '𓐯'),
+];
+
+pub const EGYPTIAN_HIEROGLYPHS_EXTENDED_A: &'static [(char, char)] = &[
+  ('𓑠', '\u{143ff}'),
+];
+
+pub const ELBASAN: &'static [(char, char)] = &[
+  ('𐔀', '\u{1052f}'),
+];
+
+pub const ELYMAIC: &'static [(char, char)] = &[
+  ('𐿠', '\u{10fff}'),
+];
+
+pub const EMOTICONS: &'static [(char, char)] = &[
+  ('😀', '🙏'),
+];
+
+pub const ENCLOSED_ALPHANUMERIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('🄀', '🇿'),
+];
+
+pub const ENCLOSED_ALPHANUMERICS: &'static [(char, char)] = &[
+  ('①', '⓿'),
+];
+
+pub const ENCLOSED_CJK_LETTERS_AND_MONTHS: &'static [(char, char)] = &[
+  ('㈀', '㋿'),
+];
+
+pub const ENCLOSED_IDEOGRAPHIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('🈀', '\u{1f2ff}'),
+];
+
+pub const ETHIOPIC: &'static [(char, char)] = &[
+  ('ሀ', '\u{137f}'),
+];
+
+pub const ETHIOPIC_EXTENDED: &'static [(char, char)] = &[
+  ('ⶀ', '\u{2ddf}'),
+];
+
+pub const ETHIOPIC_EXTENDED_A: &'static [(char, char)] = &[
+  ('\u{ab00}', '\u{ab2f}'),
+];
+
+pub const ETHIOPIC_EXTENDED_B: &'static [(char, char)] = &[
+  ('𞟠', '\u{1e7ff}'),
+];
+
+pub const ETHIOPIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ᎀ', '\u{139f}'),
+];
+
+pub const GARAY: &'static [(char, char)] = &[
+  ('𐵀', '𐶏'),
+];
+
+pub const GENERAL_PUNCTUATION: &'static [(char, char)] = &[
+  ('\u{2000}', '\u{206f}'),
+];
+
+pub const GEOMETRIC_SHAPES: &'static [(char, char)] = &[
+  ('■', '◿'),
+];
+
+pub const GEOMETRIC_SHAPES_EXTENDED: &'static [(char, char)] = &[
+  ('🞀', '\u{1f7ff}'),
+];
+
+pub const GEORGIAN: &'static [(char, char)] = &[
+  ('Ⴀ', 'ჿ'),
+];
+
+pub const GEORGIAN_EXTENDED: &'static [(char, char)] = &[
+  ('Ა', 'Ჿ'),
+];
+
+pub const GEORGIAN_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ⴀ', '\u{2d2f}'),
+];
+
+pub const GLAGOLITIC: &'static [(char, char)] = &[
+  ('Ⰰ', 'ⱟ'),
+];
+
+pub const GLAGOLITIC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('\u{1e000}', '\u{1e02f}'),
+];
+
+pub const GOTHIC: &'static [(char, char)] = &[
+  ('𐌰', '\u{1034f}'),
+];
+
+pub const GRANTHA: &'static [(char, char)] = &[
+  ('\u{11300}', '\u{1137f}'),
+];
+
+pub const GREEK_AND_COPTIC: &'static [(char, char)] = &[
+  ('Ͱ', 'Ͽ'),
+];
+
+pub const GREEK_EXTENDED: &'static [(char, char)] = &[
+  ('ἀ', '\u{1fff}'),
+];
+
+pub const GUJARATI: &'static [(char, char)] = &[
+  ('\u{a80}', '\u{aff}'),
+];
+
+pub const GUNJALA_GONDI: &'static [(char, char)] = &[
+  ('𑵠', '\u{11daf}'),
+];
+
+pub const GURMUKHI: &'static [(char, char)] = &[
+  ('\u{a00}', '\u{a7f}'),
+];
+
+pub const GURUNG_KHEMA: &'static [(char, char)] = &[
+  ('𖄀', '\u{1613f}'),
+];
+
+pub const HALFWIDTH_AND_FULLWIDTH_FORMS: &'static [(char, char)] = &[
+  ('\u{ff00}', '\u{ffef}'),
+];
+
+pub const HANGUL_COMPATIBILITY_JAMO: &'static [(char, char)] = &[
+  ('\u{3130}', '\u{318f}'),
+];
+
+pub const HANGUL_JAMO: &'static [(char, char)] = &[
+  ('ᄀ', 'ᇿ'),
+];
+
+pub const HANGUL_JAMO_EXTENDED_A: &'static [(char, char)] = &[
+  ('ꥠ', '\u{a97f}'),
+];
+
+pub const HANGUL_JAMO_EXTENDED_B: &'static [(char, char)] = &[
+  ('ힰ', '\u{d7ff}'),
+];
+
+pub const HANGUL_SYLLABLES: &'static [(char, char)] = &[
+  ('가', '\u{d7af}'),
+];
+
+pub const HANIFI_ROHINGYA: &'static [(char, char)] = &[
+  ('𐴀', '\u{10d3f}'),
+];
+
+pub const HANUNOO: &'static [(char, char)] = &[
+  ('ᜠ', '\u{173f}'),
+];
+
+pub const HATRAN: &'static [(char, char)] = &[
+  ('𐣠', '𐣿'),
+];
+
+pub const HEBREW: &'static [(char, char)] = &[
+  ('\u{590}', '\u{5ff}'),
+];
+
+pub const HIRAGANA: &'static [(char, char)] = &[
+  ('\u{3040}', 'ゟ'),
+];
+
+pub const IPA_EXTENSIONS: &'static [(char, char)] = &[
+  ('ɐ', 'ʯ'),
+];
+
+pub const IDEOGRAPHIC_DESCRIPTION_CHARACTERS: &'static [(char, char)] = &[
+  ('⿰', '⿿'),
+];
+
+pub const IDEOGRAPHIC_SYMBOLS_AND_PUNCTUATION: &'static [(char, char)] = &[
+  ('𖿠', '\u{16fff}'),
+];
+
+pub const IMPERIAL_ARAMAIC: &'static [(char, char)] = &[
+  ('𐡀', '𐡟'),
+];
+
+pub const INDIC_SIYAQ_NUMBERS: &'static [(char, char)] = &[
+  ('\u{1ec70}', '\u{1ecbf}'),
+];
+
+pub const INSCRIPTIONAL_PAHLAVI: &'static [(char, char)] = &[
+  ('𐭠', '𐭿'),
+];
+
+pub const INSCRIPTIONAL_PARTHIAN: &'static [(char, char)] = &[
+  ('𐭀', '𐭟'),
+];
+
+pub const JAVANESE: &'static [(char, char)] = &[
+  ('\u{a980}', '꧟'),
+];
+
+pub const KAITHI: &'static [(char, char)] = &[
+  ('\u{11080}', '\u{110cf}'),
+];
+
+pub const KAKTOVIK_NUMERALS: &'static [(char, char)] = &[
+  ('𝋀', '\u{1d2df}'),
+];
+
+pub const KANA_EXTENDED_A: &'static [(char, char)] = &[
+  ('𛄀', '\u{1b12f}'),
+];
+
+pub const KANA_EXTENDED_B: &'static [(char, char)] = &[
+  ('𚿰', '\u{1afff}'),
+];
+
+pub const KANA_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𛀀', '𛃿'),
+];
+
+pub const KANBUN: &'static [(char, char)] = &[
+  ('㆐', '㆟'),
+];
+
+pub const KANGXI_RADICALS: &'static [(char, char)] = &[
+  ('⼀', '\u{2fdf}'),
+];
+
+pub const KANNADA: &'static [(char, char)] = &[
+  ('ಀ', '\u{cff}'),
+];
+
+pub const KATAKANA: &'static [(char, char)] = &[
+  ('゠', 'ヿ'),
+];
+
+pub const KATAKANA_PHONETIC_EXTENSIONS: &'static [(char, char)] = &[
+  ('ㇰ', 'ㇿ'),
+];
+
+pub const KAWI: &'static [(char, char)] = &[
+  ('\u{11f00}', '\u{11f5f}'),
+];
+
+pub const KAYAH_LI: &'static [(char, char)] = &[
+  ('꤀', '꤯'),
+];
+
+pub const KHAROSHTHI: &'static [(char, char)] = &[
+  ('𐨀', '\u{10a5f}'),
+];
+
+pub const KHITAN_SMALL_SCRIPT: &'static [(char, char)] = &[
+  ('𘬀', '𘳿'),
+];
+
+pub const KHMER: &'static [(char, char)] = &[
+  ('ក', '\u{17ff}'),
+];
+
+pub const KHMER_SYMBOLS: &'static [(char, char)] = &[
+  ('᧠', '᧿'),
+];
+
+pub const KHOJKI: &'static [(char, char)] = &[
+  ('𑈀', '\u{1124f}'),
+];
+
+pub const KHUDAWADI: &'static [(char, char)] = &[
+  ('𑊰', '\u{112ff}'),
+];
+
+pub const KIRAT_RAI: &'static [(char, char)] = &[
+  ('𖵀', '\u{16d7f}'),
+];
+
+pub const LAO: &'static [(char, char)] = &[
+  ('\u{e80}', '\u{eff}'),
+];
+
+pub const LATIN_1_SUPPLEMENT: &'static [(char, char)] = &[
+  ('\u{80}', 'ÿ'),
+];
+
+pub const LATIN_EXTENDED_A: &'static [(char, char)] = &[
+  ('Ā', 'ſ'),
+];
+
+pub const LATIN_EXTENDED_ADDITIONAL: &'static [(char, char)] = &[
+  ('Ḁ', 'ỿ'),
+];
+
+pub const LATIN_EXTENDED_B: &'static [(char, char)] = &[
+  ('ƀ', 'ɏ'),
+];
+
+pub const LATIN_EXTENDED_C: &'static [(char, char)] = &[
+  ('Ⱡ', 'Ɀ'),
+];
+
+pub const LATIN_EXTENDED_D: &'static [(char, char)] = &[
+  ('꜠', 'ꟿ'),
+];
+
+pub const LATIN_EXTENDED_E: &'static [(char, char)] = &[
+  ('ꬰ', '\u{ab6f}'),
+];
+
+pub const LATIN_EXTENDED_F: &'static [(char, char)] = &[
+  ('𐞀', '\u{107bf}'),
+];
+
+pub const LATIN_EXTENDED_G: &'static [(char, char)] = &[
+  ('𝼀', '\u{1dfff}'),
+];
+
+pub const LEPCHA: &'static [(char, char)] = &[
+  ('ᰀ', 'ᱏ'),
+];
+
+pub const LETTERLIKE_SYMBOLS: &'static [(char, char)] = &[
+  ('℀', '⅏'),
+];
+
+pub const LIMBU: &'static [(char, char)] = &[
+  ('ᤀ', '᥏'),
+];
+
+pub const LINEAR_A: &'static [(char, char)] = &[
+  ('𐘀', '\u{1077f}'),
+];
+
+pub const LINEAR_B_IDEOGRAMS: &'static [(char, char)] = &[
+  ('𐂀', '\u{100ff}'),
+];
+
+pub const LINEAR_B_SYLLABARY: &'static [(char, char)] = &[
+  ('𐀀', '\u{1007f}'),
+];
+
+pub const LISU: &'static [(char, char)] = &[
+  ('ꓐ', '꓿'),
+];
+
+pub const LISU_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𑾰', '\u{11fbf}'),
+];
+
+pub const LYCIAN: &'static [(char, char)] = &[
+  ('𐊀', '\u{1029f}'),
+];
+
+pub const LYDIAN: &'static [(char, char)] = &[
+  ('𐤠', '𐤿'),
+];
+
+pub const MAHAJANI: &'static [(char, char)] = &[
+  ('𑅐', '\u{1117f}'),
+];
+
+pub const MAHJONG_TILES: &'static [(char, char)] = &[
+  ('🀀', '\u{1f02f}'),
+];
+
+pub const MAKASAR: &'static [(char, char)] = &[
+  ('𑻠', '\u{11eff}'),
+];
+
+pub const MALAYALAM: &'static [(char, char)] = &[
+  ('\u{d00}', 'ൿ'),
+];
+
+pub const MANDAIC: &'static [(char, char)] = &[
+  ('ࡀ', '\u{85f}'),
+];
+
+pub const MANICHAEAN: &'static [(char, char)] = &[
+  ('𐫀', '\u{10aff}'),
+];
+
+pub const MARCHEN: &'static [(char, char)] = &[
+  ('𑱰', '\u{11cbf}'),
+];
+
+pub const MASARAM_GONDI: &'static [(char, char)] = &[
+  ('𑴀', '\u{11d5f}'),
+];
+
+pub const MATHEMATICAL_ALPHANUMERIC_SYMBOLS: &'static [(char, char)] = &[
+  ('𝐀', '𝟿'),
+];
+
+pub const MATHEMATICAL_OPERATORS: &'static [(char, char)] = &[
+  ('∀', '⋿'),
+];
+
+pub const MAYAN_NUMERALS: &'static [(char, char)] = &[
+  ('𝋠', '\u{1d2ff}'),
+];
+
+pub const MEDEFAIDRIN: &'static [(char, char)] = &[
+  ('𖹀', '\u{16e9f}'),
+];
+
+pub const MEETEI_MAYEK: &'static [(char, char)] = &[
+  ('ꯀ', '\u{abff}'),
+];
+
+pub const MEETEI_MAYEK_EXTENSIONS: &'static [(char, char)] = &[
+  ('ꫠ', '\u{aaff}'),
+];
+
+pub const MENDE_KIKAKUI: &'static [(char, char)] = &[
+  ('𞠀', '\u{1e8df}'),
+];
+
+pub const MEROITIC_CURSIVE: &'static [(char, char)] = &[
+  ('𐦠', '𐧿'),
+];
+
+pub const MEROITIC_HIEROGLYPHS: &'static [(char, char)] = &[
+  ('𐦀', '𐦟'),
+];
+
+pub const MIAO: &'static [(char, char)] = &[
+  ('𖼀', '𖾟'),
+];
+
+pub const MISCELLANEOUS_MATHEMATICAL_SYMBOLS_A: &'static [(char, char)] = &[
+  ('⟀', '⟯'),
+];
+
+pub const MISCELLANEOUS_MATHEMATICAL_SYMBOLS_B: &'static [(char, char)] = &[
+  ('⦀', '⧿'),
+];
+
+pub const MISCELLANEOUS_SYMBOLS: &'static [(char, char)] = &[
+  ('☀', '⛿'),
+];
+
+pub const MISCELLANEOUS_SYMBOLS_AND_ARROWS: &'static [(char, char)] = &[
+  ('⬀', '⯿'),
+];
+
+pub const MISCELLANEOUS_SYMBOLS_AND_PICTOGRAPHS: &'static [(char, char)] = &[
+  ('🌀', '🗿'),
+];
+
+pub const MISCELLANEOUS_TECHNICAL: &'static [(char, char)] = &[
+  ('⌀', '⏿'),
+];
+
+pub const MODI: &'static [(char, char)] = &[
+  ('𑘀', '\u{1165f}'),
+];
+
+pub const MODIFIER_TONE_LETTERS: &'static [(char, char)] = &[
+  ('꜀', 'ꜟ'),
+];
+
+pub const MONGOLIAN: &'static [(char, char)] = &[
+  ('᠀', '\u{18af}'),
+];
+
+pub const MONGOLIAN_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𑙠', '\u{1167f}'),
+];
+
+pub const MRO: &'static [(char, char)] = &[
+  ('𖩀', '𖩯'),
+];
+
+pub const MULTANI: &'static [(char, char)] = &[
+  ('𑊀', '\u{112af}'),
+];
+
+pub const MUSICAL_SYMBOLS: &'static [(char, char)] = &[
+  ('𝄀', '\u{1d1ff}'),
+];
+
+pub const MYANMAR: &'static [(char, char)] = &[
+  ('က', '႟'),
+];
+
+pub const MYANMAR_EXTENDED_A: &'static [(char, char)] = &[
+  ('ꩠ', 'ꩿ'),
+];
+
+pub const MYANMAR_EXTENDED_B: &'static [(char, char)] = &[
+  ('ꧠ', '\u{a9ff}'),
+];
+
+pub const MYANMAR_EXTENDED_C: &'static [(char, char)] = &[
+  ('𑛐', '\u{116ff}'),
+];
+
+pub const NKO: &'static [(char, char)] = &[
+  ('߀', '߿'),
+];
+
+pub const NABATAEAN: &'static [(char, char)] = &[
+  ('𐢀', '𐢯'),
+];
+
+pub const NAG_MUNDARI: &'static [(char, char)] = &[
+  ('𞓐', '\u{1e4ff}'),
+];
+
+pub const NANDINAGARI: &'static [(char, char)] = &[
+  ('𑦠', '\u{119ff}'),
+];
+
+pub const NEW_TAI_LUE: &'static [(char, char)] = &[
+  ('ᦀ', '᧟'),
+];
+
+pub const NEWA: &'static [(char, char)] = &[
+  ('𑐀', '\u{1147f}'),
+];
+
+pub const NUMBER_FORMS: &'static [(char, char)] = &[
+  ('⅐', '\u{218f}'),
+];
+
+pub const NUSHU: &'static [(char, char)] = &[
+  ('𛅰', '\u{1b2ff}'),
+];
+
+pub const NYIAKENG_PUACHUE_HMONG: &'static [(char, char)] = &[
+  ('𞄀', '𞅏'),
+];
+
+pub const OGHAM: &'static [(char, char)] = &[
+  ('\u{1680}', '\u{169f}'),
+];
+
+pub const OL_CHIKI: &'static [(char, char)] = &[
+  ('᱐', '᱿'),
+];
+
+pub const OL_ONAL: &'static [(char, char)] = &[
+  ('𞗐', '𞗿'),
+];
+
+pub const OLD_HUNGARIAN: &'static [(char, char)] = &[
+  ('𐲀', '𐳿'),
+];
+
+pub const OLD_ITALIC: &'static [(char, char)] = &[
+  ('𐌀', '𐌯'),
+];
+
+pub const OLD_NORTH_ARABIAN: &'static [(char, char)] = &[
+  ('𐪀', '𐪟'),
+];
+
+pub const OLD_PERMIC: &'static [(char, char)] = &[
+  ('𐍐', '\u{1037f}'),
+];
+
+pub const OLD_PERSIAN: &'static [(char, char)] = &[
+  ('𐎠', '\u{103df}'),
+];
+
+pub const OLD_SOGDIAN: &'static [(char, char)] = &[
+  ('𐼀', '\u{10f2f}'),
+];
+
+pub const OLD_SOUTH_ARABIAN: &'static [(char, char)] = &[
+  ('𐩠', '𐩿'),
+];
+
+pub const OLD_TURKIC: &'static [(char, char)] = &[
+  ('𐰀', '\u{10c4f}'),
+];
+
+pub const OLD_UYGHUR: &'static [(char, char)] = &[
+  ('𐽰', '\u{10faf}'),
+];
+
+pub const OPTICAL_CHARACTER_RECOGNITION: &'static [(char, char)] = &[
+  ('⑀', '\u{245f}'),
+];
+
+pub const ORIYA: &'static [(char, char)] = &[
+  ('\u{b00}', '\u{b7f}'),
+];
+
+pub const ORNAMENTAL_DINGBATS: &'static [(char, char)] = &[
+  ('🙐', '🙿'),
+];
+
+pub const OSAGE: &'static [(char, char)] = &[
+  ('𐒰', '\u{104ff}'),
+];
+
+pub const OSMANYA: &'static [(char, char)] = &[
+  ('𐒀', '\u{104af}'),
+];
+
+pub const OTTOMAN_SIYAQ_NUMBERS: &'static [(char, char)] = &[
+  ('\u{1ed00}', '\u{1ed4f}'),
+];
+
+pub const PAHAWH_HMONG: &'static [(char, char)] = &[
+  ('𖬀', '𖮏'),
+];
+
+pub const PALMYRENE: &'static [(char, char)] = &[
+  ('𐡠', '𐡿'),
+];
+
+pub const PAU_CIN_HAU: &'static [(char, char)] = &[
+  ('𑫀', '\u{11aff}'),
+];
+
+pub const PHAGS_PA: &'static [(char, char)] = &[
+  ('ꡀ', '\u{a87f}'),
+];
+
+pub const PHAISTOS_DISC: &'static [(char, char)] = &[
+  ('𐇐', '\u{101ff}'),
+];
+
+pub const PHOENICIAN: &'static [(char, char)] = &[
+  ('𐤀', '𐤟'),
+];
+
+pub const PHONETIC_EXTENSIONS: &'static [(char, char)] = &[
+  ('ᴀ', 'ᵿ'),
+];
+
+pub const PHONETIC_EXTENSIONS_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ᶀ', 'ᶿ'),
+];
+
+pub const PLAYING_CARDS: &'static [(char, char)] = &[
+  ('🂠', '\u{1f0ff}'),
+];
+
+pub const PRIVATE_USE_AREA: &'static [(char, char)] = &[
+  ('\u{e000}', '\u{f8ff}'),
+];
+
+pub const PSALTER_PAHLAVI: &'static [(char, char)] = &[
+  ('𐮀', '𐮯'),
+];
+
+pub const REJANG: &'static [(char, char)] = &[
+  ('ꤰ', '꥟'),
+];
+
+pub const RUMI_NUMERAL_SYMBOLS: &'static [(char, char)] = &[
+  ('𐹠', '\u{10e7f}'),
+];
+
+pub const RUNIC: &'static [(char, char)] = &[
+  ('ᚠ', '\u{16ff}'),
+];
+
+pub const SAMARITAN: &'static [(char, char)] = &[
+  ('ࠀ', '\u{83f}'),
+];
+
+pub const SAURASHTRA: &'static [(char, char)] = &[
+  ('ꢀ', '\u{a8df}'),
+];
+
+pub const SHARADA: &'static [(char, char)] = &[
+  ('\u{11180}', '𑇟'),
+];
+
+pub const SHAVIAN: &'static [(char, char)] = &[
+  ('𐑐', '𐑿'),
+];
+
+pub const SHORTHAND_FORMAT_CONTROLS: &'static [(char, char)] = &[
+  ('\u{1bca0}', '\u{1bcaf}'),
+];
+
+pub const SIDDHAM: &'static [(char, char)] = &[
+  ('𑖀', '\u{115ff}'),
+];
+
+pub const SINHALA: &'static [(char, char)] = &[
+  ('\u{d80}', '\u{dff}'),
+];
+
+pub const SINHALA_ARCHAIC_NUMBERS: &'static [(char, char)] = &[
+  ('\u{111e0}', '\u{111ff}'),
+];
+
+pub const SMALL_FORM_VARIANTS: &'static [(char, char)] = &[
+  ('﹐', '\u{fe6f}'),
+];
+
+pub const SMALL_KANA_EXTENSION: &'static [(char, char)] = &[
+  ('\u{1b130}', '\u{1b16f}'),
+];
+
+pub const SOGDIAN: &'static [(char, char)] = &[
+  ('𐼰', '\u{10f6f}'),
+];
+
+pub const SORA_SOMPENG: &'static [(char, char)] = &[
+  ('𑃐', '\u{110ff}'),
+];
+
+pub const SOYOMBO: &'static [(char, char)] = &[
+  ('𑩐', '\u{11aaf}'),
+];
+
+pub const SPACING_MODIFIER_LETTERS: &'static [(char, char)] = &[
+  ('ʰ', '˿'),
+];
+
+pub const SPECIALS: &'static [(char, char)] = &[
+  ('\u{fff0}', '\u{ffff}'),
+];
+
+pub const SUNDANESE: &'static [(char, char)] = &[
+  ('\u{1b80}', 'ᮿ'),
+];
+
+pub const SUNDANESE_SUPPLEMENT: &'static [(char, char)] = &[
+  ('᳀', '\u{1ccf}'),
+];
+
+pub const SUNUWAR: &'static [(char, char)] = &[
+  ('𑯀', '\u{11bff}'),
+];
+
+pub const SUPERSCRIPTS_AND_SUBSCRIPTS: &'static [(char, char)] = &[
+  ('⁰', '\u{209f}'),
+];
+
+pub const SUPPLEMENTAL_ARROWS_A: &'static [(char, char)] = &[
+  ('⟰', '⟿'),
+];
+
+pub const SUPPLEMENTAL_ARROWS_B: &'static [(char, char)] = &[
+  ('⤀', '⥿'),
+];
+
+pub const SUPPLEMENTAL_ARROWS_C: &'static [(char, char)] = &[
+  ('🠀', '\u{1f8ff}'),
+];
+
+pub const SUPPLEMENTAL_MATHEMATICAL_OPERATORS: &'static [(char, char)] = &[
+  ('⨀', '⫿'),
+];
+
+pub const SUPPLEMENTAL_PUNCTUATION: &'static [(char, char)] = &[
+  ('⸀', '\u{2e7f}'),
+];
+
+pub const SUPPLEMENTAL_SYMBOLS_AND_PICTOGRAPHS: &'static [(char, char)] = &[
+  ('🤀', '🧿'),
+];
+
+pub const SUPPLEMENTARY_PRIVATE_USE_AREA_A: &'static [(char, char)] = &[
+  ('\u{f0000}', '\u{fffff}'),
+];
+
+pub const SUPPLEMENTARY_PRIVATE_USE_AREA_B: &'static [(char, char)] = &[
+  ('\u{100000}', '\u{10ffff}'),
+];
+
+pub const SUTTON_SIGNWRITING: &'static [(char, char)] = &[
+  ('𝠀', '\u{1daaf}'),
+];
+
+pub const SYLOTI_NAGRI: &'static [(char, char)] = &[
+  ('ꠀ', '\u{a82f}'),
+];
+
+pub const SYMBOLS_AND_PICTOGRAPHS_EXTENDED_A: &'static [(char, char)] = &[
+  ('🩰', '\u{1faff}'),
+];
+
+pub const SYMBOLS_FOR_LEGACY_COMPUTING: &'static [(char, char)] = &[
+  ('🬀', '\u{1fbff}'),
+];
+
+pub const SYMBOLS_FOR_LEGACY_COMPUTING_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𜰀', '𜺿'),
+];
+
+pub const SYRIAC: &'static [(char, char)] = &[
+  ('܀', 'ݏ'),
+];
+
+pub const SYRIAC_SUPPLEMENT: &'static [(char, char)] = &[
+  ('ࡠ', '\u{86f}'),
+];
+
+pub const TAGALOG: &'static [(char, char)] = &[
+  ('ᜀ', 'ᜟ'),
+];
+
+pub const TAGBANWA: &'static [(char, char)] = &[
+  ('ᝠ', '\u{177f}'),
+];
+
+pub const TAGS: &'static [(char, char)] = &[
+  ('\u{e0000}', '\u{e007f}'),
+];
+
+pub const TAI_LE: &'static [(char, char)] = &[
+  ('ᥐ', '\u{197f}'),
+];
+
+pub const TAI_THAM: &'static [(char, char)] = &[
+  ('ᨠ', '\u{1aaf}'),
+];
+
+pub const TAI_VIET: &'static [(char, char)] = &[
+  ('ꪀ', '꫟'),
+];
+
+pub const TAI_XUAN_JING_SYMBOLS: &'static [(char, char)] = &[
+  ('𝌀', '\u{1d35f}'),
+];
+
+pub const TAKRI: &'static [(char, char)] = &[
+  ('𑚀', '\u{116cf}'),
+];
+
+pub const TAMIL: &'static [(char, char)] = &[
+  ('\u{b80}', '\u{bff}'),
+];
+
+pub const TAMIL_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𑿀', '𑿿'),
+];
+
+pub const TANGSA: &'static [(char, char)] = &[
+  ('𖩰', '\u{16acf}'),
+];
+
+pub const TANGUT: &'static [(char, char)] = &[
+  ('𗀀', '𘟿'),
+];
+
+pub const TANGUT_COMPONENTS: &'static [(char, char)] = &[
+  ('𘠀', '𘫿'),
+];
+
+pub const TANGUT_SUPPLEMENT: &'static [(char, char)] = &[
+  ('𘴀', '\u{18d7f}'),
+];
+
+pub const TELUGU: &'static [(char, char)] = &[
+  ('\u{c00}', '౿'),
+];
+
+pub const THAANA: &'static [(char, char)] = &[
+  ('ހ', '\u{7bf}'),
+];
+
+pub const THAI: &'static [(char, char)] = &[
+  ('\u{e00}', '\u{e7f}'),
+];
+
+pub const TIBETAN: &'static [(char, char)] = &[
+  ('ༀ', '\u{fff}'),
+];
+
+pub const TIFINAGH: &'static [(char, char)] = &[
+  ('ⴰ', '\u{2d7f}'),
+];
+
+pub const TIRHUTA: &'static [(char, char)] = &[
+  ('𑒀', '\u{114df}'),
+];
+
+pub const TODHRI: &'static [(char, char)] = &[
+  ('𐗀', '\u{105ff}'),
+];
+
+pub const TOTO: &'static [(char, char)] = &[
+  ('𞊐', '\u{1e2bf}'),
+];
+
+pub const TRANSPORT_AND_MAP_SYMBOLS: &'static [(char, char)] = &[
+  ('🚀', '\u{1f6ff}'),
+];
+
+pub const TULU_TIGALARI: &'static [(char, char)] = &[
+  ('𑎀', '\u{113ff}'),
+];
+
+pub const UGARITIC: &'static [(char, char)] = &[
+  ('𐎀', '𐎟'),
+];
+
+pub const UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS: &'static [(char, char)] = &[
+  ('᐀', 'ᙿ'),
+];
+
+pub const UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS_EXTENDED: &'static [(char, char)] = &[
+  ('ᢰ', '\u{18ff}'),
+];
+
+pub const UNIFIED_CANADIAN_ABORIGINAL_SYLLABICS_EXTENDED_A: &'static [(char, char)] = &[
+  ('𑪰', '𑪿'),
+];
+
+pub const VAI: &'static [(char, char)] = &[
+  ('ꔀ', '\u{a63f}'),
+];
+
+pub const VARIATION_SELECTORS: &'static [(char, char)] = &[
+  ('\u{fe00}', '\u{fe0f}'),
+];
+
+pub const VARIATION_SELECTORS_SUPPLEMENT: &'static [(char, char)] = &[
+  ('\u{e0100}', '\u{e01ef}'),
+];
+
+pub const VEDIC_EXTENSIONS: &'static [(char, char)] = &[
+  ('\u{1cd0}', '\u{1cff}'),
+];
+
+pub const VERTICAL_FORMS: &'static [(char, char)] = &[
+  ('︐', '\u{fe1f}'),
+];
+
+pub const VITHKUQI: &'static [(char, char)] = &[
+  ('𐕰', '\u{105bf}'),
+];
+
+pub const WANCHO: &'static [(char, char)] = &[
+  ('𞋀', '𞋿'),
+];
+
+pub const WARANG_CITI: &'static [(char, char)] = &[
+  ('𑢠', '𑣿'),
+];
+
+pub const YEZIDI: &'static [(char, char)] = &[
+  ('𐺀', '\u{10ebf}'),
+];
+
+pub const YI_RADICALS: &'static [(char, char)] = &[
+  ('꒐', '\u{a4cf}'),
+];
+
+pub const YI_SYLLABLES: &'static [(char, char)] = &[
+  ('ꀀ', '\u{a48f}'),
+];
+
+pub const YIJING_HEXAGRAM_SYMBOLS: &'static [(char, char)] = &[
+  ('䷀', '䷿'),
+];
+
+pub const ZANABAZAR_SQUARE: &'static [(char, char)] = &[
+  ('𑨀', '\u{11a4f}'),
+];
+
+pub const ZNAMENNY_MUSICAL_NOTATION: &'static [(char, char)] = &[
+  ('\u{1cf00}', '\u{1cfcf}'),
+];
@@ -0,0 +1,801 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   ucd-generate case-folding-simple /tmp/ucd-16.0.0 --chars --all-pairs
+//
+// Unicode version: 16.0.0.
+//
+// ucd-generate 0.3.0 is available on crates.io.
+
+pub const CASE_FOLDING_SIMPLE: &'static [(char, &'static [char])] = &[
+  ('A', &['a']), ('B', &['b']), ('C', &['c']), ('D', &['d']), ('E', &['e']),
+  ('F', &['f']), ('G', &['g']), ('H', &['h']), ('I', &['i']), ('J', &['j']),
+  ('K', &['k', 'K']), ('L', &['l']), ('M', &['m']), ('N', &['n']),
+  ('O', &['o']), ('P', &['p']), ('Q', &['q']), ('R', &['r']),
+  ('S', &['s', 'ſ']), ('T', &['t']), ('U', &['u']), ('V', &['v']),
+  ('W', &['w']), ('X', &['x']), ('Y', &['y']), ('Z', &['z']), ('a', &['A']),
+  ('b', &['B']), ('c', &['C']), ('d', &['D']), ('e', &['E']), ('f', &['F']),
+  ('g', &['G']), ('h', &['H']), ('i', &['I']), ('j', &['J']),
+  ('k', &['K', 'K']), ('l', &['L']), ('m', &['M']), ('n', &['N']),
+  ('o', &['O']), ('p', &['P']), ('q', &['Q']), ('r', &['R']),
+  ('s', &['S', 'ſ']), ('t', &['T']), ('u', &['U']), ('v', &['V']),
+  ('w', &['W']), ('x', &['X']), ('y', &['Y']), ('z', &['Z']),
+  ('µ', &['Μ', 'μ']), ('À', &['à']), ('Á', &['á']), ('Â', &['â']),
+  ('Ã', &['ã']), ('Ä', &['ä']), ('Å', &['å', 'Å']), ('Æ', &['æ']),
+  ('Ç', &['ç']), ('È', &['è']), ('É', &['é']), ('Ê', &['ê']),
+  ('Ë', &['ë']), ('Ì', &['ì']), ('Í', &['í']), ('Î', &['î']),
+  ('Ï', &['ï']), ('Ð', &['ð']), ('Ñ', &['ñ']), ('Ò', &['ò']),
+  ('Ó', &['ó']), ('Ô', &['ô']), ('Õ', &['õ']), ('Ö', &['ö']),
+  ('Ø', &['ø']), ('Ù', &['ù']), ('Ú', &['ú']), ('Û', &['û']),
+  ('Ü', &['ü']), ('Ý', &['ý']), ('Þ', &['þ']), ('ß', &['ẞ']),
+  ('à', &['À']), ('á', &['Á']), ('â', &['Â']), ('ã', &['Ã']),
+  ('ä', &['Ä']), ('å', &['Å', 'Å']), ('æ', &['Æ']), ('ç', &['Ç']),
+  ('è', &['È']), ('é', &['É']), ('ê', &['Ê']), ('ë', &['Ë']),
+  ('ì', &['Ì']), ('í', &['Í']), ('î', &['Î']), ('ï', &['Ï']),
+  ('ð', &['Ð']), ('ñ', &['Ñ']), ('ò', &['Ò']), ('ó', &['Ó']),
+  ('ô', &['Ô']), ('õ', &['Õ']), ('ö', &['Ö']), ('ø', &['Ø']),
+  ('ù', &['Ù']), ('ú', &['Ú']), ('û', &['Û']), ('ü', &['Ü']),
+  ('ý', &['Ý']), ('þ', &['Þ']), ('ÿ', &['Ÿ']), ('Ā', &['ā']),
+  ('ā', &['Ā']), ('Ă', &['ă']), ('ă', &['Ă']), ('Ą', &['ą']),
+  ('ą', &['Ą']), ('Ć', &['ć']), ('ć', &['Ć']), ('Ĉ', &['ĉ']),
+  ('ĉ', &['Ĉ']), ('Ċ', &['ċ']), ('ċ', &['Ċ']), ('Č', &['č']),
+  ('č', &['Č']), ('Ď', &['ď']), ('ď', &['Ď']), ('Đ', &['đ']),
+  ('đ', &['Đ']), ('Ē', &['ē']), ('ē', &['Ē']), ('Ĕ', &['ĕ']),
+  ('ĕ', &['Ĕ']), ('Ė', &['ė']), ('ė', &['Ė']), ('Ę', &['ę']),
+  ('ę', &['Ę']), ('Ě', &['ě']), ('ě', &['Ě']), ('Ĝ', &['ĝ']),
+  ('ĝ', &['Ĝ']), ('Ğ', &['ğ']), ('ğ', &['Ğ']), ('Ġ', &['ġ']),
+  ('ġ', &['Ġ']), ('Ģ', &['ģ']), ('ģ', &['Ģ']), ('Ĥ', &['ĥ']),
+  ('ĥ', &['Ĥ']), ('Ħ', &['ħ']), ('ħ', &['Ħ']), ('Ĩ', &['ĩ']),
+  ('ĩ', &['Ĩ']), ('Ī', &['ī']), ('ī', &['Ī']), ('Ĭ', &['ĭ']),
+  ('ĭ', &['Ĭ']), ('Į', &['į']), ('į', &['Į']), ('Ĳ', &['ĳ']),
+  ('ĳ', &['Ĳ']), ('Ĵ', &['ĵ']), ('ĵ', &['Ĵ']), ('Ķ', &['ķ']),
+  ('ķ', &['Ķ']), ('Ĺ', &['ĺ']), ('ĺ', &['Ĺ']), ('Ļ', &['ļ']),
+  ('ļ', &['Ļ']), ('Ľ', &['ľ']), ('ľ', &['Ľ']), ('Ŀ', &['ŀ']),
+  ('ŀ', &['Ŀ']), ('Ł', &['ł']), ('ł', &['Ł']), ('Ń', &['ń']),
+  ('ń', &['Ń']), ('Ņ', &['ņ']), ('ņ', &['Ņ']), ('Ň', &['ň']),
+  ('ň', &['Ň']), ('Ŋ', &['ŋ']), ('ŋ', &['Ŋ']), ('Ō', &['ō']),
+  ('ō', &['Ō']), ('Ŏ', &['ŏ']), ('ŏ', &['Ŏ']), ('Ő', &['ő']),
+  ('ő', &['Ő']), ('Œ', &['œ']), ('œ', &['Œ']), ('Ŕ', &['ŕ']),
+  ('ŕ', &['Ŕ']), ('Ŗ', &['ŗ']), ('ŗ', &['Ŗ']), ('Ř', &['ř']),
+  ('ř', &['Ř']), ('Ś', &['ś']), ('ś', &['Ś']), ('Ŝ', &['ŝ']),
+  ('ŝ', &['Ŝ']), ('Ş', &['ş']), ('ş', &['Ş']), ('Š', &['š']),
+  ('š', &['Š']), ('Ţ', &['ţ']), ('ţ', &['Ţ']), ('Ť', &['ť']),
+  ('ť', &['Ť']), ('Ŧ', &['ŧ']), ('ŧ', &['Ŧ']), ('Ũ', &['ũ']),
+  ('ũ', &['Ũ']), ('Ū', &['ū']), ('ū', &['Ū']), ('Ŭ', &['ŭ']),
+  ('ŭ', &['Ŭ']), ('Ů', &['ů']), ('ů', &['Ů']), ('Ű', &['ű']),
+  ('ű', &['Ű']), ('Ų', &['ų']), ('ų', &['Ų']), ('Ŵ', &['ŵ']),
+  ('ŵ', &['Ŵ']), ('Ŷ', &['ŷ']), ('ŷ', &['Ŷ']), ('Ÿ', &['ÿ']),
+  ('Ź', &['ź']), ('ź', &['Ź']), ('Ż', &['ż']), ('ż', &['Ż']),
+  ('Ž', &['ž']), ('ž', &['Ž']), ('ſ', &['S', 's']), ('ƀ', &['Ƀ']),
+  ('Ɓ', &['ɓ']), ('Ƃ', &['ƃ']), ('ƃ', &['Ƃ']), ('Ƅ', &['ƅ']),
+  ('ƅ', &['Ƅ']), ('Ɔ', &['ɔ']), ('Ƈ', &['ƈ']), ('ƈ', &['Ƈ']),
+  ('Ɖ', &['ɖ']), ('Ɗ', &['ɗ']), ('Ƌ', &['ƌ']), ('ƌ', &['Ƌ']),
+  ('Ǝ', &['ǝ']), ('Ə', &['ə']), ('Ɛ', &['ɛ']), ('Ƒ', &['ƒ']),
+  ('ƒ', &['Ƒ']), ('Ɠ', &['ɠ']), ('Ɣ', &['ɣ']), ('ƕ', &['Ƕ']),
+  ('Ɩ', &['ɩ']), ('Ɨ', &['ɨ']), ('Ƙ', &['ƙ']), ('ƙ', &['Ƙ']),
+  ('ƚ', &['Ƚ']), ('ƛ', &['Ƛ']), ('Ɯ', &['ɯ']), ('Ɲ', &['ɲ']),
+  ('ƞ', &['Ƞ']), ('Ɵ', &['ɵ']), ('Ơ', &['ơ']), ('ơ', &['Ơ']),
+  ('Ƣ', &['ƣ']), ('ƣ', &['Ƣ']), ('Ƥ', &['ƥ']), ('ƥ', &['Ƥ']),
+  ('Ʀ', &['ʀ']), ('Ƨ', &['ƨ']), ('ƨ', &['Ƨ']), ('Ʃ', &['ʃ']),
+  ('Ƭ', &['ƭ']), ('ƭ', &['Ƭ']), ('Ʈ', &['ʈ']), ('Ư', &['ư']),
+  ('ư', &['Ư']), ('Ʊ', &['ʊ']), ('Ʋ', &['ʋ']), ('Ƴ', &['ƴ']),
+  ('ƴ', &['Ƴ']), ('Ƶ', &['ƶ']), ('ƶ', &['Ƶ']), ('Ʒ', &['ʒ']),
+  ('Ƹ', &['ƹ']), ('ƹ', &['Ƹ']), ('Ƽ', &['ƽ']), ('ƽ', &['Ƽ']),
+  ('ƿ', &['Ƿ']), ('Ǆ', &['ǅ', 'ǆ']), ('ǅ', &['Ǆ', 'ǆ']),
+  ('ǆ', &['Ǆ', 'ǅ']), ('Ǉ', &['ǈ', 'ǉ']), ('ǈ', &['Ǉ', 'ǉ']),
+  ('ǉ', &['Ǉ', 'ǈ']), ('Ǌ', &['ǋ', 'ǌ']), ('ǋ', &['Ǌ', 'ǌ']),
+  ('ǌ', &['Ǌ', 'ǋ']), ('Ǎ', &['ǎ']), ('ǎ', &['Ǎ']), ('Ǐ', &['ǐ']),
+  ('ǐ', &['Ǐ']), ('Ǒ', &['ǒ']), ('ǒ', &['Ǒ']), ('Ǔ', &['ǔ']),
+  ('ǔ', &['Ǔ']), ('Ǖ', &['ǖ']), ('ǖ', &['Ǖ']), ('Ǘ', &['ǘ']),
+  ('ǘ', &['Ǘ']), ('Ǚ', &['ǚ']), ('ǚ', &['Ǚ']), ('Ǜ', &['ǜ']),
+  ('ǜ', &['Ǜ']), ('ǝ', &['Ǝ']), ('Ǟ', &['ǟ']), ('ǟ', &['Ǟ']),
+  ('Ǡ', &['ǡ']), ('ǡ', &['Ǡ']), ('Ǣ', &['ǣ']), ('ǣ', &['Ǣ']),
+  ('Ǥ', &['ǥ']), ('ǥ', &['Ǥ']), ('Ǧ', &['ǧ']), ('ǧ', &['Ǧ']),
+  ('Ǩ', &['ǩ']), ('ǩ', &['Ǩ']), ('Ǫ', &['ǫ']), ('ǫ', &['Ǫ']),
+  ('Ǭ', &['ǭ']), ('ǭ', &['Ǭ']), ('Ǯ', &['ǯ']), ('ǯ', &['Ǯ']),
+  ('Ǳ', &['ǲ', 'ǳ']), ('ǲ', &['Ǳ', 'ǳ']), ('ǳ', &['Ǳ', 'ǲ']),
+  ('Ǵ', &['ǵ']), ('ǵ', &['Ǵ']), ('Ƕ', &['ƕ']), ('Ƿ', &['ƿ']),
+  ('Ǹ', &['ǹ']), ('ǹ', &['Ǹ']), ('Ǻ', &['ǻ']), ('ǻ', &['Ǻ']),
+  ('Ǽ', &['ǽ']), ('ǽ', &['Ǽ']), ('Ǿ', &['ǿ']), ('ǿ', &['Ǿ']),
+  ('Ȁ', &['ȁ']), ('ȁ', &['Ȁ']), ('Ȃ', &['ȃ']), ('ȃ', &['Ȃ']),
+  ('Ȅ', &['ȅ']), ('ȅ', &['Ȅ']), ('Ȇ', &['ȇ']), ('ȇ', &['Ȇ']),
+  ('Ȉ', &['ȉ']), ('ȉ', &['Ȉ']), ('Ȋ', &['ȋ']), ('ȋ', &['Ȋ']),
+  ('Ȍ', &['ȍ']), ('ȍ', &['Ȍ']), ('Ȏ', &['ȏ']), ('ȏ', &['Ȏ']),
+  ('Ȑ', &['ȑ']), ('ȑ', &['Ȑ']), ('Ȓ', &['ȓ']), ('ȓ', &['Ȓ']),
+  ('Ȕ', &['ȕ']), ('ȕ', &['Ȕ']), ('Ȗ', &['ȗ']), ('ȗ', &['Ȗ']),
+  ('Ș', &['ș']), ('ș', &['Ș']), ('Ț', &['ț']), ('ț', &['Ț']),
+  ('Ȝ', &['ȝ']), ('ȝ', &['Ȝ']), ('Ȟ', &['ȟ']), ('ȟ', &['Ȟ']),
+  ('Ƞ', &['ƞ']), ('Ȣ', &['ȣ']), ('ȣ', &['Ȣ']), ('Ȥ', &['ȥ']),
+  ('ȥ', &['Ȥ']), ('Ȧ', &['ȧ']), ('ȧ', &['Ȧ']), ('Ȩ', &['ȩ']),
+  ('ȩ', &['Ȩ']), ('Ȫ', &['ȫ']), ('ȫ', &['Ȫ']), ('Ȭ', &['ȭ']),
+  ('ȭ', &['Ȭ']), ('Ȯ', &['ȯ']), ('ȯ', &['Ȯ']), ('Ȱ', &['ȱ']),
+  ('ȱ', &['Ȱ']), ('Ȳ', &['ȳ']), ('ȳ', &['Ȳ']), ('Ⱥ', &['ⱥ']),
+  ('Ȼ', &['ȼ']), ('ȼ', &['Ȼ']), ('Ƚ', &['ƚ']), ('Ⱦ', &['ⱦ']),
+  ('ȿ', &['Ȿ']), ('ɀ', &['Ɀ']), ('Ɂ', &['ɂ']), ('ɂ', &['Ɂ']),
+  ('Ƀ', &['ƀ']), ('Ʉ', &['ʉ']), ('Ʌ', &['ʌ']), ('Ɇ', &['ɇ']),
+  ('ɇ', &['Ɇ']), ('Ɉ', &['ɉ']), ('ɉ', &['Ɉ']), ('Ɋ', &['ɋ']),
+  ('ɋ', &['Ɋ']), ('Ɍ', &['ɍ']), ('ɍ', &['Ɍ']), ('Ɏ', &['ɏ']),
+  ('ɏ', &['Ɏ']), ('ɐ', &['Ɐ']), ('ɑ', &['Ɑ']), ('ɒ', &['Ɒ']),
+  ('ɓ', &['Ɓ']), ('ɔ', &['Ɔ']), ('ɖ', &['Ɖ']), ('ɗ', &['Ɗ']),
+  ('ə', &['Ə']), ('ɛ', &['Ɛ']), ('ɜ', &['Ɜ']), ('ɠ', &['Ɠ']),
+  ('ɡ', &['Ɡ']), ('ɣ', &['Ɣ']), ('ɤ', &['Ɤ']), ('ɥ', &['Ɥ']),
+  ('ɦ', &['Ɦ']), ('ɨ', &['Ɨ']), ('ɩ', &['Ɩ']), ('ɪ', &['Ɪ']),
+  ('ɫ', &['Ɫ']), ('ɬ', &['Ɬ']), ('ɯ', &['Ɯ']), ('ɱ', &['Ɱ']),
+  ('ɲ', &['Ɲ']), ('ɵ', &['Ɵ']), ('ɽ', &['Ɽ']), ('ʀ', &['Ʀ']),
+  ('ʂ', &['Ʂ']), ('ʃ', &['Ʃ']), ('ʇ', &['Ʇ']), ('ʈ', &['Ʈ']),
+  ('ʉ', &['Ʉ']), ('ʊ', &['Ʊ']), ('ʋ', &['Ʋ']), ('ʌ', &['Ʌ']),
+  ('ʒ', &['Ʒ']), ('ʝ', &['Ʝ']), ('ʞ', &['Ʞ']),
+  ('\u{345}', &['Ι', 'ι', 'ι']), ('Ͱ', &['ͱ']), ('ͱ', &['Ͱ']),
+  ('Ͳ', &['ͳ']), ('ͳ', &['Ͳ']), ('Ͷ', &['ͷ']), ('ͷ', &['Ͷ']),
+  ('ͻ', &['Ͻ']), ('ͼ', &['Ͼ']), ('ͽ', &['Ͽ']), ('Ϳ', &['ϳ']),
+  ('Ά', &['ά']), ('Έ', &['έ']), ('Ή', &['ή']), ('Ί', &['ί']),
+  ('Ό', &['ό']), ('Ύ', &['ύ']), ('Ώ', &['ώ']), ('ΐ', &['ΐ']),
+  ('Α', &['α']), ('Β', &['β', 'ϐ']), ('Γ', &['γ']), ('Δ', &['δ']),
+  ('Ε', &['ε', 'ϵ']), ('Ζ', &['ζ']), ('Η', &['η']),
+  ('Θ', &['θ', 'ϑ', 'ϴ']), ('Ι', &['\u{345}', 'ι', 'ι']),
+  ('Κ', &['κ', 'ϰ']), ('Λ', &['λ']), ('Μ', &['µ', 'μ']),
+  ('Ν', &['ν']), ('Ξ', &['ξ']), ('Ο', &['ο']), ('Π', &['π', 'ϖ']),
+  ('Ρ', &['ρ', 'ϱ']), ('Σ', &['ς', 'σ']), ('Τ', &['τ']),
+  ('Υ', &['υ']), ('Φ', &['φ', 'ϕ']), ('Χ', &['χ']), ('Ψ', &['ψ']),
+  ('Ω', &['ω', 'Ω']), ('Ϊ', &['ϊ']), ('Ϋ', &['ϋ']), ('ά', &['Ά']),
+  ('έ', &['Έ']), ('ή', &['Ή']), ('ί', &['Ί']), ('ΰ', &['ΰ']),
+  ('α', &['Α']), ('β', &['Β', 'ϐ']), ('γ', &['Γ']), ('δ', &['Δ']),
+  ('ε', &['Ε', 'ϵ']), ('ζ', &['Ζ']), ('η', &['Η']),
+  ('θ', &['Θ', 'ϑ', 'ϴ']), ('ι', &['\u{345}', 'Ι', 'ι']),
+  ('κ', &['Κ', 'ϰ']), ('λ', &['Λ']), ('μ', &['µ', 'Μ']),
+  ('ν', &['Ν']), ('ξ', &['Ξ']), ('ο', &['Ο']), ('π', &['Π', 'ϖ']),
+  ('ρ', &['Ρ', 'ϱ']), ('ς', &['Σ', 'σ']), ('σ', &['Σ', 'ς']),
+  ('τ', &['Τ']), ('υ', &['Υ']), ('φ', &['Φ', 'ϕ']), ('χ', &['Χ']),
+  ('ψ', &['Ψ']), ('ω', &['Ω', 'Ω']), ('ϊ', &['Ϊ']), ('ϋ', &['Ϋ']),
+  ('ό', &['Ό']), ('ύ', &['Ύ']), ('ώ', &['Ώ']), ('Ϗ', &['ϗ']),
+  ('ϐ', &['Β', 'β']), ('ϑ', &['Θ', 'θ', 'ϴ']), ('ϕ', &['Φ', 'φ']),
+  ('ϖ', &['Π', 'π']), ('ϗ', &['Ϗ']), ('Ϙ', &['ϙ']), ('ϙ', &['Ϙ']),
+  ('Ϛ', &['ϛ']), ('ϛ', &['Ϛ']), ('Ϝ', &['ϝ']), ('ϝ', &['Ϝ']),
+  ('Ϟ', &['ϟ']), ('ϟ', &['Ϟ']), ('Ϡ', &['ϡ']), ('ϡ', &['Ϡ']),
+  ('Ϣ', &['ϣ']), ('ϣ', &['Ϣ']), ('Ϥ', &['ϥ']), ('ϥ', &['Ϥ']),
+  ('Ϧ', &['ϧ']), ('ϧ', &['Ϧ']), ('Ϩ', &['ϩ']), ('ϩ', &['Ϩ']),
+  ('Ϫ', &['ϫ']), ('ϫ', &['Ϫ']), ('Ϭ', &['ϭ']), ('ϭ', &['Ϭ']),
+  ('Ϯ', &['ϯ']), ('ϯ', &['Ϯ']), ('ϰ', &['Κ', 'κ']),
+  ('ϱ', &['Ρ', 'ρ']), ('ϲ', &['Ϲ']), ('ϳ', &['Ϳ']),
+  ('ϴ', &['Θ', 'θ', 'ϑ']), ('ϵ', &['Ε', 'ε']), ('Ϸ', &['ϸ']),
+  ('ϸ', &['Ϸ']), ('Ϲ', &['ϲ']), ('Ϻ', &['ϻ']), ('ϻ', &['Ϻ']),
+  ('Ͻ', &['ͻ']), ('Ͼ', &['ͼ']), ('Ͽ', &['ͽ']), ('Ѐ', &['ѐ']),
+  ('Ё', &['ё']), ('Ђ', &['ђ']), ('Ѓ', &['ѓ']), ('Є', &['є']),
+  ('Ѕ', &['ѕ']), ('І', &['і']), ('Ї', &['ї']), ('Ј', &['ј']),
+  ('Љ', &['љ']), ('Њ', &['њ']), ('Ћ', &['ћ']), ('Ќ', &['ќ']),
+  ('Ѝ', &['ѝ']), ('Ў', &['ў']), ('Џ', &['џ']), ('А', &['а']),
+  ('Б', &['б']), ('В', &['в', 'ᲀ']), ('Г', &['г']),
+  ('Д', &['д', 'ᲁ']), ('Е', &['е']), ('Ж', &['ж']), ('З', &['з']),
+  ('И', &['и']), ('Й', &['й']), ('К', &['к']), ('Л', &['л']),
+  ('М', &['м']), ('Н', &['н']), ('О', &['о', 'ᲂ']), ('П', &['п']),
+  ('Р', &['р']), ('С', &['с', 'ᲃ']), ('Т', &['т', 'ᲄ', 'ᲅ']),
+  ('У', &['у']), ('Ф', &['ф']), ('Х', &['х']), ('Ц', &['ц']),
+  ('Ч', &['ч']), ('Ш', &['ш']), ('Щ', &['щ']), ('Ъ', &['ъ', 'ᲆ']),
+  ('Ы', &['ы']), ('Ь', &['ь']), ('Э', &['э']), ('Ю', &['ю']),
+  ('Я', &['я']), ('а', &['А']), ('б', &['Б']), ('в', &['В', 'ᲀ']),
+  ('г', &['Г']), ('д', &['Д', 'ᲁ']), ('е', &['Е']), ('ж', &['Ж']),
+  ('з', &['З']), ('и', &['И']), ('й', &['Й']), ('к', &['К']),
+  ('л', &['Л']), ('м', &['М']), ('н', &['Н']), ('о', &['О', 'ᲂ']),
+  ('п', &['П']), ('р', &['Р']), ('с', &['С', 'ᲃ']),
+  ('т', &['Т', 'ᲄ', 'ᲅ']), ('у', &['У']), ('ф', &['Ф']),
+  ('х', &['Х']), ('ц', &['Ц']), ('ч', &['Ч']), ('ш', &['Ш']),
+  ('щ', &['Щ']), ('ъ', &['Ъ', 'ᲆ']), ('ы', &['Ы']), ('ь', &['Ь']),
+  ('э', &['Э']), ('ю', &['Ю']), ('я', &['Я']), ('ѐ', &['Ѐ']),
+  ('ё', &['Ё']), ('ђ', &['Ђ']), ('ѓ', &['Ѓ']), ('є', &['Є']),
+  ('ѕ', &['Ѕ']), ('і', &['І']), ('ї', &['Ї']), ('ј', &['Ј']),
+  ('љ', &['Љ']), ('њ', &['Њ']), ('ћ', &['Ћ']), ('ќ', &['Ќ']),
+  ('ѝ', &['Ѝ']), ('ў', &['Ў']), ('џ', &['Џ']), ('Ѡ', &['ѡ']),
+  ('ѡ', &['Ѡ']), ('Ѣ', &['ѣ', 'ᲇ']), ('ѣ', &['Ѣ', 'ᲇ']),
+  ('Ѥ', &['ѥ']), ('ѥ', &['Ѥ']), ('Ѧ', &['ѧ']), ('ѧ', &['Ѧ']),
+  ('Ѩ', &['ѩ']), ('ѩ', &['Ѩ']), ('Ѫ', &['ѫ']), ('ѫ', &['Ѫ']),
+  ('Ѭ', &['ѭ']), ('ѭ', &['Ѭ']), ('Ѯ', &['ѯ']), ('ѯ', &['Ѯ']),
+  ('Ѱ', &['ѱ']), ('ѱ', &['Ѱ']), ('Ѳ', &['ѳ']), ('ѳ', &['Ѳ']),
+  ('Ѵ', &['ѵ']), ('ѵ', &['Ѵ']), ('Ѷ', &['ѷ']), ('ѷ', &['Ѷ']),
+  ('Ѹ', &['ѹ']), ('ѹ', &['Ѹ']), ('Ѻ', &['ѻ']), ('ѻ', &['Ѻ']),
+  ('Ѽ', &['ѽ']), ('ѽ', &['Ѽ']), ('Ѿ', &['ѿ']), ('ѿ', &['Ѿ']),
+  ('Ҁ', &['ҁ']), ('ҁ', &['Ҁ']), ('Ҋ', &['ҋ']), ('ҋ', &['Ҋ']),
+  ('Ҍ', &['ҍ']), ('ҍ', &['Ҍ']), ('Ҏ', &['ҏ']), ('ҏ', &['Ҏ']),
+  ('Ґ', &['ґ']), ('ґ', &['Ґ']), ('Ғ', &['ғ']), ('ғ', &['Ғ']),
+  ('Ҕ', &['ҕ']), ('ҕ', &['Ҕ']), ('Җ', &['җ']), ('җ', &['Җ']),
+  ('Ҙ', &['ҙ']), ('ҙ', &['Ҙ']), ('Қ', &['қ']), ('қ', &['Қ']),
+  ('Ҝ', &['ҝ']), ('ҝ', &['Ҝ']), ('Ҟ', &['ҟ']), ('ҟ', &['Ҟ']),
+  ('Ҡ', &['ҡ']), ('ҡ', &['Ҡ']), ('Ң', &['ң']), ('ң', &['Ң']),
+  ('Ҥ', &['ҥ']), ('ҥ', &['Ҥ']), ('Ҧ', &['ҧ']), ('ҧ', &['Ҧ']),
+  ('Ҩ', &['ҩ']), ('ҩ', &['Ҩ']), ('Ҫ', &['ҫ']), ('ҫ', &['Ҫ']),
+  ('Ҭ', &['ҭ']), ('ҭ', &['Ҭ']), ('Ү', &['ү']), ('ү', &['Ү']),
+  ('Ұ', &['ұ']), ('ұ', &['Ұ']), ('Ҳ', &['ҳ']), ('ҳ', &['Ҳ']),
+  ('Ҵ', &['ҵ']), ('ҵ', &['Ҵ']), ('Ҷ', &['ҷ']), ('ҷ', &['Ҷ']),
+  ('Ҹ', &['ҹ']), ('ҹ', &['Ҹ']), ('Һ', &['һ']), ('һ', &['Һ']),
+  ('Ҽ', &['ҽ']), ('ҽ', &['Ҽ']), ('Ҿ', &['ҿ']), ('ҿ', &['Ҿ']),
+  ('Ӏ', &['ӏ']), ('Ӂ', &['ӂ']), ('ӂ', &['Ӂ']), ('Ӄ', &['ӄ']),
+  ('ӄ', &['Ӄ']), ('Ӆ', &['ӆ']), ('ӆ', &['Ӆ']), ('Ӈ', &['ӈ']),
+  ('ӈ', &['Ӈ']), ('Ӊ', &['ӊ']), ('ӊ', &['Ӊ']), ('Ӌ', &['ӌ']),
+  ('ӌ', &['Ӌ']), ('Ӎ', &['ӎ']), ('ӎ', &['Ӎ']), ('ӏ', &['Ӏ']),
+  ('Ӑ', &['ӑ']), ('ӑ', &['Ӑ']), ('Ӓ', &['ӓ']), ('ӓ', &['Ӓ']),
+  ('Ӕ', &['ӕ']), ('ӕ', &['Ӕ']), ('Ӗ', &['ӗ']), ('ӗ', &['Ӗ']),
+  ('Ә', &['ә']), ('ә', &['Ә']), ('Ӛ', &['ӛ']), ('ӛ', &['Ӛ']),
+  ('Ӝ', &['ӝ']), ('ӝ', &['Ӝ']), ('Ӟ', &['ӟ']), ('ӟ', &['Ӟ']),
+  ('Ӡ', &['ӡ']), ('ӡ', &['Ӡ']), ('Ӣ', &['ӣ']), ('ӣ', &['Ӣ']),
+  ('Ӥ', &['ӥ']), ('ӥ', &['Ӥ']), ('Ӧ', &['ӧ']), ('ӧ', &['Ӧ']),
+  ('Ө', &['ө']), ('ө', &['Ө']), ('Ӫ', &['ӫ']), ('ӫ', &['Ӫ']),
+  ('Ӭ', &['ӭ']), ('ӭ', &['Ӭ']), ('Ӯ', &['ӯ']), ('ӯ', &['Ӯ']),
+  ('Ӱ', &['ӱ']), ('ӱ', &['Ӱ']), ('Ӳ', &['ӳ']), ('ӳ', &['Ӳ']),
+  ('Ӵ', &['ӵ']), ('ӵ', &['Ӵ']), ('Ӷ', &['ӷ']), ('ӷ', &['Ӷ']),
+  ('Ӹ', &['ӹ']), ('ӹ', &['Ӹ']), ('Ӻ', &['ӻ']), ('ӻ', &['Ӻ']),
+  ('Ӽ', &['ӽ']), ('ӽ', &['Ӽ']), ('Ӿ', &['ӿ']), ('ӿ', &['Ӿ']),
+  ('Ԁ', &['ԁ']), ('ԁ', &['Ԁ']), ('Ԃ', &['ԃ']), ('ԃ', &['Ԃ']),
+  ('Ԅ', &['ԅ']), ('ԅ', &['Ԅ']), ('Ԇ', &['ԇ']), ('ԇ', &['Ԇ']),
+  ('Ԉ', &['ԉ']), ('ԉ', &['Ԉ']), ('Ԋ', &['ԋ']), ('ԋ', &['Ԋ']),
+  ('Ԍ', &['ԍ']), ('ԍ', &['Ԍ']), ('Ԏ', &['ԏ']), ('ԏ', &['Ԏ']),
+  ('Ԑ', &['ԑ']), ('ԑ', &['Ԑ']), ('Ԓ', &['ԓ']), ('ԓ', &['Ԓ']),
+  ('Ԕ', &['ԕ']), ('ԕ', &['Ԕ']), ('Ԗ', &['ԗ']), ('ԗ', &['Ԗ']),
+  ('Ԙ', &['ԙ']), ('ԙ', &['Ԙ']), ('Ԛ', &['ԛ']), ('ԛ', &['Ԛ']),
+  ('Ԝ', &['ԝ']), ('ԝ', &['Ԝ']), ('Ԟ', &['ԟ']), ('ԟ', &['Ԟ']),
+  ('Ԡ', &['ԡ']), ('ԡ', &['Ԡ']), ('Ԣ', &['ԣ']), ('ԣ', &['Ԣ']),
+  ('Ԥ', &['ԥ']), ('ԥ', &['Ԥ']), ('Ԧ', &['ԧ']), ('ԧ', &['Ԧ']),
+  ('Ԩ', &['ԩ']), ('ԩ', &['Ԩ']), ('Ԫ', &['ԫ']), ('ԫ', &['Ԫ']),
+  ('Ԭ', &['ԭ']), ('ԭ', &['Ԭ']), ('Ԯ', &['ԯ']), ('ԯ', &['Ԯ']),
+  ('Ա', &['ա']), ('Բ', &['բ']), ('Գ', &['գ']), ('Դ', &['դ']),
+  ('Ե', &['ե']), ('Զ', &['զ']), ('Է', &['է']), ('Ը', &['ը']),
+  ('Թ', &['թ']), ('Ժ', &['ժ']), ('Ի', &['ի']), ('Լ', &['լ']),
+  ('Խ', &['խ']), ('Ծ', &['ծ']), ('Կ', &['կ']), ('Հ', &['հ']),
+  ('Ձ', &['ձ']), ('Ղ', &['ղ']), ('Ճ', &['ճ']), ('Մ', &['մ']),
+  ('Յ', &['յ']), ('Ն', &['ն']), ('Շ', &['շ']), ('Ո', &['ո']),
+  ('Չ', &['չ']), ('Պ', &['պ']), ('Ջ', &['ջ']), ('Ռ', &['ռ']),
+  ('Ս', &['ս']), ('Վ', &['վ']), ('Տ', &['տ']), ('Ր', &['ր']),
+  ('Ց', &['ց']), ('Ւ', &['ւ']), ('Փ', &['փ']), ('Ք', &['ք']),
+  ('Օ', &['օ']), ('Ֆ', &['ֆ']), ('ա', &['Ա']), ('բ', &['Բ']),
+  ('գ', &['Գ']), ('դ', &['Դ']), ('ե', &['Ե']), ('զ', &['Զ']),
+  ('է', &['Է']), ('ը', &['Ը']), ('թ', &['Թ']), ('ժ', &['Ժ']),
+  ('ի', &['Ի']), ('լ', &['Լ']), ('խ', &['Խ']), ('ծ', &['Ծ']),
+  ('կ', &['Կ']), ('հ', &['Հ']), ('ձ', &['Ձ']), ('ղ', &['Ղ']),
+  ('ճ', &['Ճ']), ('մ', &['Մ']), ('յ', &['Յ']), ('ն', &['Ն']),
+  ('շ', &['Շ']), ('ո', &['Ո']), ('չ', &['Չ']), ('պ', &['Պ']),
+  ('ջ', &['Ջ']), ('ռ', &['Ռ']), ('ս', &['Ս']), ('վ', &['Վ']),
+  ('տ', &['Տ']), ('ր', &['Ր']), ('ց', &['Ց']), ('ւ', &['Ւ']),
+  ('փ', &['Փ']), ('ք', &['Ք']), ('օ', &['Օ']), ('ֆ', &['Ֆ']),
+  ('Ⴀ', &['ⴀ']), ('Ⴁ', &['ⴁ']), ('Ⴂ', &['ⴂ']), ('Ⴃ', &['ⴃ']),
+  ('Ⴄ', &['ⴄ']), ('Ⴅ', &['ⴅ']), ('Ⴆ', &['ⴆ']), ('Ⴇ', &['ⴇ']),
+  ('Ⴈ', &['ⴈ']), ('Ⴉ', &['ⴉ']), ('Ⴊ', &['ⴊ']), ('Ⴋ', &['ⴋ']),
+  ('Ⴌ', &['ⴌ']), ('Ⴍ', &['ⴍ']), ('Ⴎ', &['ⴎ']), ('Ⴏ', &['ⴏ']),
+  ('Ⴐ', &['ⴐ']), ('Ⴑ', &['ⴑ']), ('Ⴒ', &['ⴒ']), ('Ⴓ', &['ⴓ']),
+  ('Ⴔ', &['ⴔ']), ('Ⴕ', &['ⴕ']), ('Ⴖ', &['ⴖ']), ('Ⴗ', &['ⴗ']),
+  ('Ⴘ', &['ⴘ']), ('Ⴙ', &['ⴙ']), ('Ⴚ', &['ⴚ']), ('Ⴛ', &['ⴛ']),
+  ('Ⴜ', &['ⴜ']), ('Ⴝ', &['ⴝ']), ('Ⴞ', &['ⴞ']), ('Ⴟ', &['ⴟ']),
+  ('Ⴠ', &['ⴠ']), ('Ⴡ', &['ⴡ']), ('Ⴢ', &['ⴢ']), ('Ⴣ', &['ⴣ']),
+  ('Ⴤ', &['ⴤ']), ('Ⴥ', &['ⴥ']), ('Ⴧ', &['ⴧ']), ('Ⴭ', &['ⴭ']),
+  ('ა', &['Ა']), ('ბ', &['Ბ']), ('გ', &['Გ']), ('დ', &['Დ']),
+  ('ე', &['Ე']), ('ვ', &['Ვ']), ('ზ', &['Ზ']), ('თ', &['Თ']),
+  ('ი', &['Ი']), ('კ', &['Კ']), ('ლ', &['Ლ']), ('მ', &['Მ']),
+  ('ნ', &['Ნ']), ('ო', &['Ო']), ('პ', &['Პ']), ('ჟ', &['Ჟ']),
+  ('რ', &['Რ']), ('ს', &['Ს']), ('ტ', &['Ტ']), ('უ', &['Უ']),
+  ('ფ', &['Ფ']), ('ქ', &['Ქ']), ('ღ', &['Ღ']), ('ყ', &['Ყ']),
+  ('შ', &['Შ']), ('ჩ', &['Ჩ']), ('ც', &['Ც']), ('ძ', &['Ძ']),
+  ('წ', &['Წ']), ('ჭ', &['Ჭ']), ('ხ', &['Ხ']), ('ჯ', &['Ჯ']),
+  ('ჰ', &['Ჰ']), ('ჱ', &['Ჱ']), ('ჲ', &['Ჲ']), ('ჳ', &['Ჳ']),
+  ('ჴ', &['Ჴ']), ('ჵ', &['Ჵ']), ('ჶ', &['Ჶ']), ('ჷ', &['Ჷ']),
+  ('ჸ', &['Ჸ']), ('ჹ', &['Ჹ']), ('ჺ', &['Ჺ']), ('ჽ', &['Ჽ']),
+  ('ჾ', &['Ჾ']), ('ჿ', &['Ჿ']), ('Ꭰ', &['ꭰ']), ('Ꭱ', &['ꭱ']),
+  ('Ꭲ', &['ꭲ']), ('Ꭳ', &['ꭳ']), ('Ꭴ', &['ꭴ']), ('Ꭵ', &['ꭵ']),
+  ('Ꭶ', &['ꭶ']), ('Ꭷ', &['ꭷ']), ('Ꭸ', &['ꭸ']), ('Ꭹ', &['ꭹ']),
+  ('Ꭺ', &['ꭺ']), ('Ꭻ', &['ꭻ']), ('Ꭼ', &['ꭼ']), ('Ꭽ', &['ꭽ']),
+  ('Ꭾ', &['ꭾ']), ('Ꭿ', &['ꭿ']), ('Ꮀ', &['ꮀ']), ('Ꮁ', &['ꮁ']),
+  ('Ꮂ', &['ꮂ']), ('Ꮃ', &['ꮃ']), ('Ꮄ', &['ꮄ']), ('Ꮅ', &['ꮅ']),
+  ('Ꮆ', &['ꮆ']), ('Ꮇ', &['ꮇ']), ('Ꮈ', &['ꮈ']), ('Ꮉ', &['ꮉ']),
+  ('Ꮊ', &['ꮊ']), ('Ꮋ', &['ꮋ']), ('Ꮌ', &['ꮌ']), ('Ꮍ', &['ꮍ']),
+  ('Ꮎ', &['ꮎ']), ('Ꮏ', &['ꮏ']), ('Ꮐ', &['ꮐ']), ('Ꮑ', &['ꮑ']),
+  ('Ꮒ', &['ꮒ']), ('Ꮓ', &['ꮓ']), ('Ꮔ', &['ꮔ']), ('Ꮕ', &['ꮕ']),
+  ('Ꮖ', &['ꮖ']), ('Ꮗ', &['ꮗ']), ('Ꮘ', &['ꮘ']), ('Ꮙ', &['ꮙ']),
+  ('Ꮚ', &['ꮚ']), ('Ꮛ', &['ꮛ']), ('Ꮜ', &['ꮜ']), ('Ꮝ', &['ꮝ']),
+  ('Ꮞ', &['ꮞ']), ('Ꮟ', &['ꮟ']), ('Ꮠ', &['ꮠ']), ('Ꮡ', &['ꮡ']),
+  ('Ꮢ', &['ꮢ']), ('Ꮣ', &['ꮣ']), ('Ꮤ', &['ꮤ']), ('Ꮥ', &['ꮥ']),
+  ('Ꮦ', &['ꮦ']), ('Ꮧ', &['ꮧ']), ('Ꮨ', &['ꮨ']), ('Ꮩ', &['ꮩ']),
+  ('Ꮪ', &['ꮪ']), ('Ꮫ', &['ꮫ']), ('Ꮬ', &['ꮬ']), ('Ꮭ', &['ꮭ']),
+  ('Ꮮ', &['ꮮ']), ('Ꮯ', &['ꮯ']), ('Ꮰ', &['ꮰ']), ('Ꮱ', &['ꮱ']),
+  ('Ꮲ', &['ꮲ']), ('Ꮳ', &['ꮳ']), ('Ꮴ', &['ꮴ']), ('Ꮵ', &['ꮵ']),
+  ('Ꮶ', &['ꮶ']), ('Ꮷ', &['ꮷ']), ('Ꮸ', &['ꮸ']), ('Ꮹ', &['ꮹ']),
+  ('Ꮺ', &['ꮺ']), ('Ꮻ', &['ꮻ']), ('Ꮼ', &['ꮼ']), ('Ꮽ', &['ꮽ']),
+  ('Ꮾ', &['ꮾ']), ('Ꮿ', &['ꮿ']), ('Ᏸ', &['ᏸ']), ('Ᏹ', &['ᏹ']),
+  ('Ᏺ', &['ᏺ']), ('Ᏻ', &['ᏻ']), ('Ᏼ', &['ᏼ']), ('Ᏽ', &['ᏽ']),
+  ('ᏸ', &['Ᏸ']), ('ᏹ', &['Ᏹ']), ('ᏺ', &['Ᏺ']), ('ᏻ', &['Ᏻ']),
+  ('ᏼ', &['Ᏼ']), ('ᏽ', &['Ᏽ']), ('ᲀ', &['В', 'в']),
+  ('ᲁ', &['Д', 'д']), ('ᲂ', &['О', 'о']), ('ᲃ', &['С', 'с']),
+  ('ᲄ', &['Т', 'т', 'ᲅ']), ('ᲅ', &['Т', 'т', 'ᲄ']),
+  ('ᲆ', &['Ъ', 'ъ']), ('ᲇ', &['Ѣ', 'ѣ']), ('ᲈ', &['Ꙋ', 'ꙋ']),
+  ('Ᲊ', &['ᲊ']), ('ᲊ', &['Ᲊ']), ('Ა', &['ა']), ('Ბ', &['ბ']),
+  ('Გ', &['გ']), ('Დ', &['დ']), ('Ე', &['ე']), ('Ვ', &['ვ']),
+  ('Ზ', &['ზ']), ('Თ', &['თ']), ('Ი', &['ი']), ('Კ', &['კ']),
+  ('Ლ', &['ლ']), ('Მ', &['მ']), ('Ნ', &['ნ']), ('Ო', &['ო']),
+  ('Პ', &['პ']), ('Ჟ', &['ჟ']), ('Რ', &['რ']), ('Ს', &['ს']),
+  ('Ტ', &['ტ']), ('Უ', &['უ']), ('Ფ', &['ფ']), ('Ქ', &['ქ']),
+  ('Ღ', &['ღ']), ('Ყ', &['ყ']), ('Შ', &['შ']), ('Ჩ', &['ჩ']),
+  ('Ც', &['ც']), ('Ძ', &['ძ']), ('Წ', &['წ']), ('Ჭ', &['ჭ']),
+  ('Ხ', &['ხ']), ('Ჯ', &['ჯ']), ('Ჰ', &['ჰ']), ('Ჱ', &['ჱ']),
+  ('Ჲ', &['ჲ']), ('Ჳ', &['ჳ']), ('Ჴ', &['ჴ']), ('Ჵ', &['ჵ']),
+  ('Ჶ', &['ჶ']), ('Ჷ', &['ჷ']), ('Ჸ', &['ჸ']), ('Ჹ', &['ჹ']),
+  ('Ჺ', &['ჺ']), ('Ჽ', &['ჽ']), ('Ჾ', &['ჾ']), ('Ჿ', &['ჿ']),
+  ('ᵹ', &['Ᵹ']), ('ᵽ', &['Ᵽ']), ('ᶎ', &['Ᶎ']), ('Ḁ', &['ḁ']),
+  ('ḁ', &['Ḁ']), ('Ḃ', &['ḃ']), ('ḃ', &['Ḃ']), ('Ḅ', &['ḅ']),
+  ('ḅ', &['Ḅ']), ('Ḇ', &['ḇ']), ('ḇ', &['Ḇ']), ('Ḉ', &['ḉ']),
+  ('ḉ', &['Ḉ']), ('Ḋ', &['ḋ']), ('ḋ', &['Ḋ']), ('Ḍ', &['ḍ']),
+  ('ḍ', &['Ḍ']), ('Ḏ', &['ḏ']), ('ḏ', &['Ḏ']), ('Ḑ', &['ḑ']),
+  ('ḑ', &['Ḑ']), ('Ḓ', &['ḓ']), ('ḓ', &['Ḓ']), ('Ḕ', &['ḕ']),
+  ('ḕ', &['Ḕ']), ('Ḗ', &['ḗ']), ('ḗ', &['Ḗ']), ('Ḙ', &['ḙ']),
+  ('ḙ', &['Ḙ']), ('Ḛ', &['ḛ']), ('ḛ', &['Ḛ']), ('Ḝ', &['ḝ']),
+  ('ḝ', &['Ḝ']), ('Ḟ', &['ḟ']), ('ḟ', &['Ḟ']), ('Ḡ', &['ḡ']),
+  ('ḡ', &['Ḡ']), ('Ḣ', &['ḣ']), ('ḣ', &['Ḣ']), ('Ḥ', &['ḥ']),
+  ('ḥ', &['Ḥ']), ('Ḧ', &['ḧ']), ('ḧ', &['Ḧ']), ('Ḩ', &['ḩ']),
+  ('ḩ', &['Ḩ']), ('Ḫ', &['ḫ']), ('ḫ', &['Ḫ']), ('Ḭ', &['ḭ']),
+  ('ḭ', &['Ḭ']), ('Ḯ', &['ḯ']), ('ḯ', &['Ḯ']), ('Ḱ', &['ḱ']),
+  ('ḱ', &['Ḱ']), ('Ḳ', &['ḳ']), ('ḳ', &['Ḳ']), ('Ḵ', &['ḵ']),
+  ('ḵ', &['Ḵ']), ('Ḷ', &['ḷ']), ('ḷ', &['Ḷ']), ('Ḹ', &['ḹ']),
+  ('ḹ', &['Ḹ']), ('Ḻ', &['ḻ']), ('ḻ', &['Ḻ']), ('Ḽ', &['ḽ']),
+  ('ḽ', &['Ḽ']), ('Ḿ', &['ḿ']), ('ḿ', &['Ḿ']), ('Ṁ', &['ṁ']),
+  ('ṁ', &['Ṁ']), ('Ṃ', &['ṃ']), ('ṃ', &['Ṃ']), ('Ṅ', &['ṅ']),
+  ('ṅ', &['Ṅ']), ('Ṇ', &['ṇ']), ('ṇ', &['Ṇ']), ('Ṉ', &['ṉ']),
+  ('ṉ', &['Ṉ']), ('Ṋ', &['ṋ']), ('ṋ', &['Ṋ']), ('Ṍ', &['ṍ']),
+  ('ṍ', &['Ṍ']), ('Ṏ', &['ṏ']), ('ṏ', &['Ṏ']), ('Ṑ', &['ṑ']),
+  ('ṑ', &['Ṑ']), ('Ṓ', &['ṓ']), ('ṓ', &['Ṓ']), ('Ṕ', &['ṕ']),
+  ('ṕ', &['Ṕ']), ('Ṗ', &['ṗ']), ('ṗ', &['Ṗ']), ('Ṙ', &['ṙ']),
+  ('ṙ', &['Ṙ']), ('Ṛ', &['ṛ']), ('ṛ', &['Ṛ']), ('Ṝ', &['ṝ']),
+  ('ṝ', &['Ṝ']), ('Ṟ', &['ṟ']), ('ṟ', &['Ṟ']),
+  ('Ṡ', &['ṡ', 'ẛ']), ('ṡ', &['Ṡ', 'ẛ']), ('Ṣ', &['ṣ']),
+  ('ṣ', &['Ṣ']), ('Ṥ', &['ṥ']), ('ṥ', &['Ṥ']), ('Ṧ', &['ṧ']),
+  ('ṧ', &['Ṧ']), ('Ṩ', &['ṩ']), ('ṩ', &['Ṩ']), ('Ṫ', &['ṫ']),
+  ('ṫ', &['Ṫ']), ('Ṭ', &['ṭ']), ('ṭ', &['Ṭ']), ('Ṯ', &['ṯ']),
+  ('ṯ', &['Ṯ']), ('Ṱ', &['ṱ']), ('ṱ', &['Ṱ']), ('Ṳ', &['ṳ']),
+  ('ṳ', &['Ṳ']), ('Ṵ', &['ṵ']), ('ṵ', &['Ṵ']), ('Ṷ', &['ṷ']),
+  ('ṷ', &['Ṷ']), ('Ṹ', &['ṹ']), ('ṹ', &['Ṹ']), ('Ṻ', &['ṻ']),
+  ('ṻ', &['Ṻ']), ('Ṽ', &['ṽ']), ('ṽ', &['Ṽ']), ('Ṿ', &['ṿ']),
+  ('ṿ', &['Ṿ']), ('Ẁ', &['ẁ']), ('ẁ', &['Ẁ']), ('Ẃ', &['ẃ']),
+  ('ẃ', &['Ẃ']), ('Ẅ', &['ẅ']), ('ẅ', &['Ẅ']), ('Ẇ', &['ẇ']),
+  ('ẇ', &['Ẇ']), ('Ẉ', &['ẉ']), ('ẉ', &['Ẉ']), ('Ẋ', &['ẋ']),
+  ('ẋ', &['Ẋ']), ('Ẍ', &['ẍ']), ('ẍ', &['Ẍ']), ('Ẏ', &['ẏ']),
+  ('ẏ', &['Ẏ']), ('Ẑ', &['ẑ']), ('ẑ', &['Ẑ']), ('Ẓ', &['ẓ']),
+  ('ẓ', &['Ẓ']), ('Ẕ', &['ẕ']), ('ẕ', &['Ẕ']),
+  ('ẛ', &['Ṡ', 'ṡ']), ('ẞ', &['ß']), ('Ạ', &['ạ']),
+  ('ạ', &['Ạ']), ('Ả', &['ả']), ('ả', &['Ả']), ('Ấ', &['ấ']),
+  ('ấ', &['Ấ']), ('Ầ', &['ầ']), ('ầ', &['Ầ']), ('Ẩ', &['ẩ']),
+  ('ẩ', &['Ẩ']), ('Ẫ', &['ẫ']), ('ẫ', &['Ẫ']), ('Ậ', &['ậ']),
+  ('ậ', &['Ậ']), ('Ắ', &['ắ']), ('ắ', &['Ắ']), ('Ằ', &['ằ']),
+  ('ằ', &['Ằ']), ('Ẳ', &['ẳ']), ('ẳ', &['Ẳ']), ('Ẵ', &['ẵ']),
+  ('ẵ', &['Ẵ']), ('Ặ', &['ặ']), ('ặ', &['Ặ']), ('Ẹ', &['ẹ']),
+  ('ẹ', &['Ẹ']), ('Ẻ', &['ẻ']), ('ẻ', &['Ẻ']), ('Ẽ', &['ẽ']),
+  ('ẽ', &['Ẽ']), ('Ế', &['ế']), ('ế', &['Ế']), ('Ề', &['ề']),
+  ('ề', &['Ề']), ('Ể', &['ể']), ('ể', &['Ể']), ('Ễ', &['ễ']),
+  ('ễ', &['Ễ']), ('Ệ', &['ệ']), ('ệ', &['Ệ']), ('Ỉ', &['ỉ']),
+  ('ỉ', &['Ỉ']), ('Ị', &['ị']), ('ị', &['Ị']), ('Ọ', &['ọ']),
+  ('ọ', &['Ọ']), ('Ỏ', &['ỏ']), ('ỏ', &['Ỏ']), ('Ố', &['ố']),
+  ('ố', &['Ố']), ('Ồ', &['ồ']), ('ồ', &['Ồ']), ('Ổ', &['ổ']),
+  ('ổ', &['Ổ']), ('Ỗ', &['ỗ']), ('ỗ', &['Ỗ']), ('Ộ', &['ộ']),
+  ('ộ', &['Ộ']), ('Ớ', &['ớ']), ('ớ', &['Ớ']), ('Ờ', &['ờ']),
+  ('ờ', &['Ờ']), ('Ở', &['ở']), ('ở', &['Ở']), ('Ỡ', &['ỡ']),
+  ('ỡ', &['Ỡ']), ('Ợ', &['ợ']), ('ợ', &['Ợ']), ('Ụ', &['ụ']),
+  ('ụ', &['Ụ']), ('Ủ', &['ủ']), ('ủ', &['Ủ']), ('Ứ', &['ứ']),
+  ('ứ', &['Ứ']), ('Ừ', &['ừ']), ('ừ', &['Ừ']), ('Ử', &['ử']),
+  ('ử', &['Ử']), ('Ữ', &['ữ']), ('ữ', &['Ữ']), ('Ự', &['ự']),
+  ('ự', &['Ự']), ('Ỳ', &['ỳ']), ('ỳ', &['Ỳ']), ('Ỵ', &['ỵ']),
+  ('ỵ', &['Ỵ']), ('Ỷ', &['ỷ']), ('ỷ', &['Ỷ']), ('Ỹ', &['ỹ']),
+  ('ỹ', &['Ỹ']), ('Ỻ', &['ỻ']), ('ỻ', &['Ỻ']), ('Ỽ', &['ỽ']),
+  ('ỽ', &['Ỽ']), ('Ỿ', &['ỿ']), ('ỿ', &['Ỿ']), ('ἀ', &['Ἀ']),
+  ('ἁ', &['Ἁ']), ('ἂ', &['Ἂ']), ('ἃ', &['Ἃ']), ('ἄ', &['Ἄ']),
+  ('ἅ', &['Ἅ']), ('ἆ', &['Ἆ']), ('ἇ', &['Ἇ']), ('Ἀ', &['ἀ']),
+  ('Ἁ', &['ἁ']), ('Ἂ', &['ἂ']), ('Ἃ', &['ἃ']), ('Ἄ', &['ἄ']),
+  ('Ἅ', &['ἅ']), ('Ἆ', &['ἆ']), ('Ἇ', &['ἇ']), ('ἐ', &['Ἐ']),
+  ('ἑ', &['Ἑ']), ('ἒ', &['Ἒ']), ('ἓ', &['Ἓ']), ('ἔ', &['Ἔ']),
+  ('ἕ', &['Ἕ']), ('Ἐ', &['ἐ']), ('Ἑ', &['ἑ']), ('Ἒ', &['ἒ']),
+  ('Ἓ', &['ἓ']), ('Ἔ', &['ἔ']), ('Ἕ', &['ἕ']), ('ἠ', &['Ἠ']),
+  ('ἡ', &['Ἡ']), ('ἢ', &['Ἢ']), ('ἣ', &['Ἣ']), ('ἤ', &['Ἤ']),
+  ('ἥ', &['Ἥ']), ('ἦ', &['Ἦ']), ('ἧ', &['Ἧ']), ('Ἠ', &['ἠ']),
+  ('Ἡ', &['ἡ']), ('Ἢ', &['ἢ']), ('Ἣ', &['ἣ']), ('Ἤ', &['ἤ']),
+  ('Ἥ', &['ἥ']), ('Ἦ', &['ἦ']), ('Ἧ', &['ἧ']), ('ἰ', &['Ἰ']),
+  ('ἱ', &['Ἱ']), ('ἲ', &['Ἲ']), ('ἳ', &['Ἳ']), ('ἴ', &['Ἴ']),
+  ('ἵ', &['Ἵ']), ('ἶ', &['Ἶ']), ('ἷ', &['Ἷ']), ('Ἰ', &['ἰ']),
+  ('Ἱ', &['ἱ']), ('Ἲ', &['ἲ']), ('Ἳ', &['ἳ']), ('Ἴ', &['ἴ']),
+  ('Ἵ', &['ἵ']), ('Ἶ', &['ἶ']), ('Ἷ', &['ἷ']), ('ὀ', &['Ὀ']),
+  ('ὁ', &['Ὁ']), ('ὂ', &['Ὂ']), ('ὃ', &['Ὃ']), ('ὄ', &['Ὄ']),
+  ('ὅ', &['Ὅ']), ('Ὀ', &['ὀ']), ('Ὁ', &['ὁ']), ('Ὂ', &['ὂ']),
+  ('Ὃ', &['ὃ']), ('Ὄ', &['ὄ']), ('Ὅ', &['ὅ']), ('ὑ', &['Ὑ']),
+  ('ὓ', &['Ὓ']), ('ὕ', &['Ὕ']), ('ὗ', &['Ὗ']), ('Ὑ', &['ὑ']),
+  ('Ὓ', &['ὓ']), ('Ὕ', &['ὕ']), ('Ὗ', &['ὗ']), ('ὠ', &['Ὠ']),
+  ('ὡ', &['Ὡ']), ('ὢ', &['Ὢ']), ('ὣ', &['Ὣ']), ('ὤ', &['Ὤ']),
+  ('ὥ', &['Ὥ']), ('ὦ', &['Ὦ']), ('ὧ', &['Ὧ']), ('Ὠ', &['ὠ']),
+  ('Ὡ', &['ὡ']), ('Ὢ', &['ὢ']), ('Ὣ', &['ὣ']), ('Ὤ', &['ὤ']),
+  ('Ὥ', &['ὥ']), ('Ὦ', &['ὦ']), ('Ὧ', &['ὧ']), ('ὰ', &['Ὰ']),
+  ('ά', &['Ά']), ('ὲ', &['Ὲ']), ('έ', &['Έ']), ('ὴ', &['Ὴ']),
+  ('ή', &['Ή']), ('ὶ', &['Ὶ']), ('ί', &['Ί']), ('ὸ', &['Ὸ']),
+  ('ό', &['Ό']), ('ὺ', &['Ὺ']), ('ύ', &['Ύ']), ('ὼ', &['Ὼ']),
+  ('ώ', &['Ώ']), ('ᾀ', &['ᾈ']), ('ᾁ', &['ᾉ']), ('ᾂ', &['ᾊ']),
+  ('ᾃ', &['ᾋ']), ('ᾄ', &['ᾌ']), ('ᾅ', &['ᾍ']), ('ᾆ', &['ᾎ']),
+  ('ᾇ', &['ᾏ']), ('ᾈ', &['ᾀ']), ('ᾉ', &['ᾁ']), ('ᾊ', &['ᾂ']),
+  ('ᾋ', &['ᾃ']), ('ᾌ', &['ᾄ']), ('ᾍ', &['ᾅ']), ('ᾎ', &['ᾆ']),
+  ('ᾏ', &['ᾇ']), ('ᾐ', &['ᾘ']), ('ᾑ', &['ᾙ']), ('ᾒ', &['ᾚ']),
+  ('ᾓ', &['ᾛ']), ('ᾔ', &['ᾜ']), ('ᾕ', &['ᾝ']), ('ᾖ', &['ᾞ']),
+  ('ᾗ', &['ᾟ']), ('ᾘ', &['ᾐ']), ('ᾙ', &['ᾑ']), ('ᾚ', &['ᾒ']),
+  ('ᾛ', &['ᾓ']), ('ᾜ', &['ᾔ']), ('ᾝ', &['ᾕ']), ('ᾞ', &['ᾖ']),
+  ('ᾟ', &['ᾗ']), ('ᾠ', &['ᾨ']), ('ᾡ', &['ᾩ']), ('ᾢ', &['ᾪ']),
+  ('ᾣ', &['ᾫ']), ('ᾤ', &['ᾬ']), ('ᾥ', &['ᾭ']), ('ᾦ', &['ᾮ']),
+  ('ᾧ', &['ᾯ']), ('ᾨ', &['ᾠ']), ('ᾩ', &['ᾡ']), ('ᾪ', &['ᾢ']),
+  ('ᾫ', &['ᾣ']), ('ᾬ', &['ᾤ']), ('ᾭ', &['ᾥ']), ('ᾮ', &['ᾦ']),
+  ('ᾯ', &['ᾧ']), ('ᾰ', &['Ᾰ']), ('ᾱ', &['Ᾱ']), ('ᾳ', &['ᾼ']),
+  ('Ᾰ', &['ᾰ']), ('Ᾱ', &['ᾱ']), ('Ὰ', &['ὰ']), ('Ά', &['ά']),
+  ('ᾼ', &['ᾳ']), ('ι', &['\u{345}', 'Ι', 'ι']), ('ῃ', &['ῌ']),
+  ('Ὲ', &['ὲ']), ('Έ', &['έ']), ('Ὴ', &['ὴ']), ('Ή', &['ή']),
+  ('ῌ', &['ῃ']), ('ῐ', &['Ῐ']), ('ῑ', &['Ῑ']), ('ΐ', &['ΐ']),
+  ('Ῐ', &['ῐ']), ('Ῑ', &['ῑ']), ('Ὶ', &['ὶ']), ('Ί', &['ί']),
+  ('ῠ', &['Ῠ']), ('ῡ', &['Ῡ']), ('ΰ', &['ΰ']), ('ῥ', &['Ῥ']),
+  ('Ῠ', &['ῠ']), ('Ῡ', &['ῡ']), ('Ὺ', &['ὺ']), ('Ύ', &['ύ']),
+  ('Ῥ', &['ῥ']), ('ῳ', &['ῼ']), ('Ὸ', &['ὸ']), ('Ό', &['ό']),
+  ('Ὼ', &['ὼ']), ('Ώ', &['ώ']), ('ῼ', &['ῳ']),
+  ('Ω', &['Ω', 'ω']), ('K', &['K', 'k']), ('Å', &['Å', 'å']),
+  ('Ⅎ', &['ⅎ']), ('ⅎ', &['Ⅎ']), ('Ⅰ', &['ⅰ']), ('Ⅱ', &['ⅱ']),
+  ('Ⅲ', &['ⅲ']), ('Ⅳ', &['ⅳ']), ('Ⅴ', &['ⅴ']), ('Ⅵ', &['ⅵ']),
+  ('Ⅶ', &['ⅶ']), ('Ⅷ', &['ⅷ']), ('Ⅸ', &['ⅸ']), ('Ⅹ', &['ⅹ']),
+  ('Ⅺ', &['ⅺ']), ('Ⅻ', &['ⅻ']), ('Ⅼ', &['ⅼ']), ('Ⅽ', &['ⅽ']),
+  ('Ⅾ', &['ⅾ']), ('Ⅿ', &['ⅿ']), ('ⅰ', &['Ⅰ']), ('ⅱ', &['Ⅱ']),
+  ('ⅲ', &['Ⅲ']), ('ⅳ', &['Ⅳ']), ('ⅴ', &['Ⅴ']), ('ⅵ', &['Ⅵ']),
+  ('ⅶ', &['Ⅶ']), ('ⅷ', &['Ⅷ']), ('ⅸ', &['Ⅸ']), ('ⅹ', &['Ⅹ']),
+  ('ⅺ', &['Ⅺ']), ('ⅻ', &['Ⅻ']), ('ⅼ', &['Ⅼ']), ('ⅽ', &['Ⅽ']),
+  ('ⅾ', &['Ⅾ']), ('ⅿ', &['Ⅿ']), ('Ↄ', &['ↄ']), ('ↄ', &['Ↄ']),
+  ('Ⓐ', &['ⓐ']), ('Ⓑ', &['ⓑ']), ('Ⓒ', &['ⓒ']), ('Ⓓ', &['ⓓ']),
+  ('Ⓔ', &['ⓔ']), ('Ⓕ', &['ⓕ']), ('Ⓖ', &['ⓖ']), ('Ⓗ', &['ⓗ']),
+  ('Ⓘ', &['ⓘ']), ('Ⓙ', &['ⓙ']), ('Ⓚ', &['ⓚ']), ('Ⓛ', &['ⓛ']),
+  ('Ⓜ', &['ⓜ']), ('Ⓝ', &['ⓝ']), ('Ⓞ', &['ⓞ']), ('Ⓟ', &['ⓟ']),
+  ('Ⓠ', &['ⓠ']), ('Ⓡ', &['ⓡ']), ('Ⓢ', &['ⓢ']), ('Ⓣ', &['ⓣ']),
+  ('Ⓤ', &['ⓤ']), ('Ⓥ', &['ⓥ']), ('Ⓦ', &['ⓦ']), ('Ⓧ', &['ⓧ']),
+  ('Ⓨ', &['ⓨ']), ('Ⓩ', &['ⓩ']), ('ⓐ', &['Ⓐ']), ('ⓑ', &['Ⓑ']),
+  ('ⓒ', &['Ⓒ']), ('ⓓ', &['Ⓓ']), ('ⓔ', &['Ⓔ']), ('ⓕ', &['Ⓕ']),
+  ('ⓖ', &['Ⓖ']), ('ⓗ', &['Ⓗ']), ('ⓘ', &['Ⓘ']), ('ⓙ', &['Ⓙ']),
+  ('ⓚ', &['Ⓚ']), ('ⓛ', &['Ⓛ']), ('ⓜ', &['Ⓜ']), ('ⓝ', &['Ⓝ']),
+  ('ⓞ', &['Ⓞ']), ('ⓟ', &['Ⓟ']), ('ⓠ', &['Ⓠ']), ('ⓡ', &['Ⓡ']),
+  ('ⓢ', &['Ⓢ']), ('ⓣ', &['Ⓣ']), ('ⓤ', &['Ⓤ']), ('ⓥ', &['Ⓥ']),
+  ('ⓦ', &['Ⓦ']), ('ⓧ', &['Ⓧ']), ('ⓨ', &['Ⓨ']), ('ⓩ', &['Ⓩ']),
+  ('Ⰰ', &['ⰰ']), ('Ⰱ', &['ⰱ']), ('Ⰲ', &['ⰲ']), ('Ⰳ', &['ⰳ']),
+  ('Ⰴ', &['ⰴ']), ('Ⰵ', &['ⰵ']), ('Ⰶ', &['ⰶ']), ('Ⰷ', &['ⰷ']),
+  ('Ⰸ', &['ⰸ']), ('Ⰹ', &['ⰹ']), ('Ⰺ', &['ⰺ']), ('Ⰻ', &['ⰻ']),
+  ('Ⰼ', &['ⰼ']), ('Ⰽ', &['ⰽ']), ('Ⰾ', &['ⰾ']), ('Ⰿ', &['ⰿ']),
+  ('Ⱀ', &['ⱀ']), ('Ⱁ', &['ⱁ']), ('Ⱂ', &['ⱂ']), ('Ⱃ', &['ⱃ']),
+  ('Ⱄ', &['ⱄ']), ('Ⱅ', &['ⱅ']), ('Ⱆ', &['ⱆ']), ('Ⱇ', &['ⱇ']),
+  ('Ⱈ', &['ⱈ']), ('Ⱉ', &['ⱉ']), ('Ⱊ', &['ⱊ']), ('Ⱋ', &['ⱋ']),
+  ('Ⱌ', &['ⱌ']), ('Ⱍ', &['ⱍ']), ('Ⱎ', &['ⱎ']), ('Ⱏ', &['ⱏ']),
+  ('Ⱐ', &['ⱐ']), ('Ⱑ', &['ⱑ']), ('Ⱒ', &['ⱒ']), ('Ⱓ', &['ⱓ']),
+  ('Ⱔ', &['ⱔ']), ('Ⱕ', &['ⱕ']), ('Ⱖ', &['ⱖ']), ('Ⱗ', &['ⱗ']),
+  ('Ⱘ', &['ⱘ']), ('Ⱙ', &['ⱙ']), ('Ⱚ', &['ⱚ']), ('Ⱛ', &['ⱛ']),
+  ('Ⱜ', &['ⱜ']), ('Ⱝ', &['ⱝ']), ('Ⱞ', &['ⱞ']), ('Ⱟ', &['ⱟ']),
+  ('ⰰ', &['Ⰰ']), ('ⰱ', &['Ⰱ']), ('ⰲ', &['Ⰲ']), ('ⰳ', &['Ⰳ']),
+  ('ⰴ', &['Ⰴ']), ('ⰵ', &['Ⰵ']), ('ⰶ', &['Ⰶ']), ('ⰷ', &['Ⰷ']),
+  ('ⰸ', &['Ⰸ']), ('ⰹ', &['Ⰹ']), ('ⰺ', &['Ⰺ']), ('ⰻ', &['Ⰻ']),
+  ('ⰼ', &['Ⰼ']), ('ⰽ', &['Ⰽ']), ('ⰾ', &['Ⰾ']), ('ⰿ', &['Ⰿ']),
+  ('ⱀ', &['Ⱀ']), ('ⱁ', &['Ⱁ']), ('ⱂ', &['Ⱂ']), ('ⱃ', &['Ⱃ']),
+  ('ⱄ', &['Ⱄ']), ('ⱅ', &['Ⱅ']), ('ⱆ', &['Ⱆ']), ('ⱇ', &['Ⱇ']),
+  ('ⱈ', &['Ⱈ']), ('ⱉ', &['Ⱉ']), ('ⱊ', &['Ⱊ']), ('ⱋ', &['Ⱋ']),
+  ('ⱌ', &['Ⱌ']), ('ⱍ', &['Ⱍ']), ('ⱎ', &['Ⱎ']), ('ⱏ', &['Ⱏ']),
+  ('ⱐ', &['Ⱐ']), ('ⱑ', &['Ⱑ']), ('ⱒ', &['Ⱒ']), ('ⱓ', &['Ⱓ']),
+  ('ⱔ', &['Ⱔ']), ('ⱕ', &['Ⱕ']), ('ⱖ', &['Ⱖ']), ('ⱗ', &['Ⱗ']),
+  ('ⱘ', &['Ⱘ']), ('ⱙ', &['Ⱙ']), ('ⱚ', &['Ⱚ']), ('ⱛ', &['Ⱛ']),
+  ('ⱜ', &['Ⱜ']), ('ⱝ', &['Ⱝ']), ('ⱞ', &['Ⱞ']), ('ⱟ', &['Ⱟ']),
+  ('Ⱡ', &['ⱡ']), ('ⱡ', &['Ⱡ']), ('Ɫ', &['ɫ']), ('Ᵽ', &['ᵽ']),
+  ('Ɽ', &['ɽ']), ('ⱥ', &['Ⱥ']), ('ⱦ', &['Ⱦ']), ('Ⱨ', &['ⱨ']),
+  ('ⱨ', &['Ⱨ']), ('Ⱪ', &['ⱪ']), ('ⱪ', &['Ⱪ']), ('Ⱬ', &['ⱬ']),
+  ('ⱬ', &['Ⱬ']), ('Ɑ', &['ɑ']), ('Ɱ', &['ɱ']), ('Ɐ', &['ɐ']),
+  ('Ɒ', &['ɒ']), ('Ⱳ', &['ⱳ']), ('ⱳ', &['Ⱳ']), ('Ⱶ', &['ⱶ']),
+  ('ⱶ', &['Ⱶ']), ('Ȿ', &['ȿ']), ('Ɀ', &['ɀ']), ('Ⲁ', &['ⲁ']),
+  ('ⲁ', &['Ⲁ']), ('Ⲃ', &['ⲃ']), ('ⲃ', &['Ⲃ']), ('Ⲅ', &['ⲅ']),
+  ('ⲅ', &['Ⲅ']), ('Ⲇ', &['ⲇ']), ('ⲇ', &['Ⲇ']), ('Ⲉ', &['ⲉ']),
+  ('ⲉ', &['Ⲉ']), ('Ⲋ', &['ⲋ']), ('ⲋ', &['Ⲋ']), ('Ⲍ', &['ⲍ']),
+  ('ⲍ', &['Ⲍ']), ('Ⲏ', &['ⲏ']), ('ⲏ', &['Ⲏ']), ('Ⲑ', &['ⲑ']),
+  ('ⲑ', &['Ⲑ']), ('Ⲓ', &['ⲓ']), ('ⲓ', &['Ⲓ']), ('Ⲕ', &['ⲕ']),
+  ('ⲕ', &['Ⲕ']), ('Ⲗ', &['ⲗ']), ('ⲗ', &['Ⲗ']), ('Ⲙ', &['ⲙ']),
+  ('ⲙ', &['Ⲙ']), ('Ⲛ', &['ⲛ']), ('ⲛ', &['Ⲛ']), ('Ⲝ', &['ⲝ']),
+  ('ⲝ', &['Ⲝ']), ('Ⲟ', &['ⲟ']), ('ⲟ', &['Ⲟ']), ('Ⲡ', &['ⲡ']),
+  ('ⲡ', &['Ⲡ']), ('Ⲣ', &['ⲣ']), ('ⲣ', &['Ⲣ']), ('Ⲥ', &['ⲥ']),
+  ('ⲥ', &['Ⲥ']), ('Ⲧ', &['ⲧ']), ('ⲧ', &['Ⲧ']), ('Ⲩ', &['ⲩ']),
+  ('ⲩ', &['Ⲩ']), ('Ⲫ', &['ⲫ']), ('ⲫ', &['Ⲫ']), ('Ⲭ', &['ⲭ']),
+  ('ⲭ', &['Ⲭ']), ('Ⲯ', &['ⲯ']), ('ⲯ', &['Ⲯ']), ('Ⲱ', &['ⲱ']),
+  ('ⲱ', &['Ⲱ']), ('Ⲳ', &['ⲳ']), ('ⲳ', &['Ⲳ']), ('Ⲵ', &['ⲵ']),
+  ('ⲵ', &['Ⲵ']), ('Ⲷ', &['ⲷ']), ('ⲷ', &['Ⲷ']), ('Ⲹ', &['ⲹ']),
+  ('ⲹ', &['Ⲹ']), ('Ⲻ', &['ⲻ']), ('ⲻ', &['Ⲻ']), ('Ⲽ', &['ⲽ']),
+  ('ⲽ', &['Ⲽ']), ('Ⲿ', &['ⲿ']), ('ⲿ', &['Ⲿ']), ('Ⳁ', &['ⳁ']),
+  ('ⳁ', &['Ⳁ']), ('Ⳃ', &['ⳃ']), ('ⳃ', &['Ⳃ']), ('Ⳅ', &['ⳅ']),
+  ('ⳅ', &['Ⳅ']), ('Ⳇ', &['ⳇ']), ('ⳇ', &['Ⳇ']), ('Ⳉ', &['ⳉ']),
+  ('ⳉ', &['Ⳉ']), ('Ⳋ', &['ⳋ']), ('ⳋ', &['Ⳋ']), ('Ⳍ', &['ⳍ']),
+  ('ⳍ', &['Ⳍ']), ('Ⳏ', &['ⳏ']), ('ⳏ', &['Ⳏ']), ('Ⳑ', &['ⳑ']),
+  ('ⳑ', &['Ⳑ']), ('Ⳓ', &['ⳓ']), ('ⳓ', &['Ⳓ']), ('Ⳕ', &['ⳕ']),
+  ('ⳕ', &['Ⳕ']), ('Ⳗ', &['ⳗ']), ('ⳗ', &['Ⳗ']), ('Ⳙ', &['ⳙ']),
+  ('ⳙ', &['Ⳙ']), ('Ⳛ', &['ⳛ']), ('ⳛ', &['Ⳛ']), ('Ⳝ', &['ⳝ']),
+  ('ⳝ', &['Ⳝ']), ('Ⳟ', &['ⳟ']), ('ⳟ', &['Ⳟ']), ('Ⳡ', &['ⳡ']),
+  ('ⳡ', &['Ⳡ']), ('Ⳣ', &['ⳣ']), ('ⳣ', &['Ⳣ']), ('Ⳬ', &['ⳬ']),
+  ('ⳬ', &['Ⳬ']), ('Ⳮ', &['ⳮ']), ('ⳮ', &['Ⳮ']), ('Ⳳ', &['ⳳ']),
+  ('ⳳ', &['Ⳳ']), ('ⴀ', &['Ⴀ']), ('ⴁ', &['Ⴁ']), ('ⴂ', &['Ⴂ']),
+  ('ⴃ', &['Ⴃ']), ('ⴄ', &['Ⴄ']), ('ⴅ', &['Ⴅ']), ('ⴆ', &['Ⴆ']),
+  ('ⴇ', &['Ⴇ']), ('ⴈ', &['Ⴈ']), ('ⴉ', &['Ⴉ']), ('ⴊ', &['Ⴊ']),
+  ('ⴋ', &['Ⴋ']), ('ⴌ', &['Ⴌ']), ('ⴍ', &['Ⴍ']), ('ⴎ', &['Ⴎ']),
+  ('ⴏ', &['Ⴏ']), ('ⴐ', &['Ⴐ']), ('ⴑ', &['Ⴑ']), ('ⴒ', &['Ⴒ']),
+  ('ⴓ', &['Ⴓ']), ('ⴔ', &['Ⴔ']), ('ⴕ', &['Ⴕ']), ('ⴖ', &['Ⴖ']),
+  ('ⴗ', &['Ⴗ']), ('ⴘ', &['Ⴘ']), ('ⴙ', &['Ⴙ']), ('ⴚ', &['Ⴚ']),
+  ('ⴛ', &['Ⴛ']), ('ⴜ', &['Ⴜ']), ('ⴝ', &['Ⴝ']), ('ⴞ', &['Ⴞ']),
+  ('ⴟ', &['Ⴟ']), ('ⴠ', &['Ⴠ']), ('ⴡ', &['Ⴡ']), ('ⴢ', &['Ⴢ']),
+  ('ⴣ', &['Ⴣ']), ('ⴤ', &['Ⴤ']), ('ⴥ', &['Ⴥ']), ('ⴧ', &['Ⴧ']),
+  ('ⴭ', &['Ⴭ']), ('Ꙁ', &['ꙁ']), ('ꙁ', &['Ꙁ']), ('Ꙃ', &['ꙃ']),
+  ('ꙃ', &['Ꙃ']), ('Ꙅ', &['ꙅ']), ('ꙅ', &['Ꙅ']), ('Ꙇ', &['ꙇ']),
+  ('ꙇ', &['Ꙇ']), ('Ꙉ', &['ꙉ']), ('ꙉ', &['Ꙉ']),
+  ('Ꙋ', &['ᲈ', 'ꙋ']), ('ꙋ', &['ᲈ', 'Ꙋ']), ('Ꙍ', &['ꙍ']),
+  ('ꙍ', &['Ꙍ']), ('Ꙏ', &['ꙏ']), ('ꙏ', &['Ꙏ']), ('Ꙑ', &['ꙑ']),
+  ('ꙑ', &['Ꙑ']), ('Ꙓ', &['ꙓ']), ('ꙓ', &['Ꙓ']), ('Ꙕ', &['ꙕ']),
+  ('ꙕ', &['Ꙕ']), ('Ꙗ', &['ꙗ']), ('ꙗ', &['Ꙗ']), ('Ꙙ', &['ꙙ']),
+  ('ꙙ', &['Ꙙ']), ('Ꙛ', &['ꙛ']), ('ꙛ', &['Ꙛ']), ('Ꙝ', &['ꙝ']),
+  ('ꙝ', &['Ꙝ']), ('Ꙟ', &['ꙟ']), ('ꙟ', &['Ꙟ']), ('Ꙡ', &['ꙡ']),
+  ('ꙡ', &['Ꙡ']), ('Ꙣ', &['ꙣ']), ('ꙣ', &['Ꙣ']), ('Ꙥ', &['ꙥ']),
+  ('ꙥ', &['Ꙥ']), ('Ꙧ', &['ꙧ']), ('ꙧ', &['Ꙧ']), ('Ꙩ', &['ꙩ']),
+  ('ꙩ', &['Ꙩ']), ('Ꙫ', &['ꙫ']), ('ꙫ', &['Ꙫ']), ('Ꙭ', &['ꙭ']),
+  ('ꙭ', &['Ꙭ']), ('Ꚁ', &['ꚁ']), ('ꚁ', &['Ꚁ']), ('Ꚃ', &['ꚃ']),
+  ('ꚃ', &['Ꚃ']), ('Ꚅ', &['ꚅ']), ('ꚅ', &['Ꚅ']), ('Ꚇ', &['ꚇ']),
+  ('ꚇ', &['Ꚇ']), ('Ꚉ', &['ꚉ']), ('ꚉ', &['Ꚉ']), ('Ꚋ', &['ꚋ']),
+  ('ꚋ', &['Ꚋ']), ('Ꚍ', &['ꚍ']), ('ꚍ', &['Ꚍ']), ('Ꚏ', &['ꚏ']),
+  ('ꚏ', &['Ꚏ']), ('Ꚑ', &['ꚑ']), ('ꚑ', &['Ꚑ']), ('Ꚓ', &['ꚓ']),
+  ('ꚓ', &['Ꚓ']), ('Ꚕ', &['ꚕ']), ('ꚕ', &['Ꚕ']), ('Ꚗ', &['ꚗ']),
+  ('ꚗ', &['Ꚗ']), ('Ꚙ', &['ꚙ']), ('ꚙ', &['Ꚙ']), ('Ꚛ', &['ꚛ']),
+  ('ꚛ', &['Ꚛ']), ('Ꜣ', &['ꜣ']), ('ꜣ', &['Ꜣ']), ('Ꜥ', &['ꜥ']),
+  ('ꜥ', &['Ꜥ']), ('Ꜧ', &['ꜧ']), ('ꜧ', &['Ꜧ']), ('Ꜩ', &['ꜩ']),
+  ('ꜩ', &['Ꜩ']), ('Ꜫ', &['ꜫ']), ('ꜫ', &['Ꜫ']), ('Ꜭ', &['ꜭ']),
+  ('ꜭ', &['Ꜭ']), ('Ꜯ', &['ꜯ']), ('ꜯ', &['Ꜯ']), ('Ꜳ', &['ꜳ']),
+  ('ꜳ', &['Ꜳ']), ('Ꜵ', &['ꜵ']), ('ꜵ', &['Ꜵ']), ('Ꜷ', &['ꜷ']),
+  ('ꜷ', &['Ꜷ']), ('Ꜹ', &['ꜹ']), ('ꜹ', &['Ꜹ']), ('Ꜻ', &['ꜻ']),
+  ('ꜻ', &['Ꜻ']), ('Ꜽ', &['ꜽ']), ('ꜽ', &['Ꜽ']), ('Ꜿ', &['ꜿ']),
+  ('ꜿ', &['Ꜿ']), ('Ꝁ', &['ꝁ']), ('ꝁ', &['Ꝁ']), ('Ꝃ', &['ꝃ']),
+  ('ꝃ', &['Ꝃ']), ('Ꝅ', &['ꝅ']), ('ꝅ', &['Ꝅ']), ('Ꝇ', &['ꝇ']),
+  ('ꝇ', &['Ꝇ']), ('Ꝉ', &['ꝉ']), ('ꝉ', &['Ꝉ']), ('Ꝋ', &['ꝋ']),
+  ('ꝋ', &['Ꝋ']), ('Ꝍ', &['ꝍ']), ('ꝍ', &['Ꝍ']), ('Ꝏ', &['ꝏ']),
+  ('ꝏ', &['Ꝏ']), ('Ꝑ', &['ꝑ']), ('ꝑ', &['Ꝑ']), ('Ꝓ', &['ꝓ']),
+  ('ꝓ', &['Ꝓ']), ('Ꝕ', &['ꝕ']), ('ꝕ', &['Ꝕ']), ('Ꝗ', &['ꝗ']),
+  ('ꝗ', &['Ꝗ']), ('Ꝙ', &['ꝙ']), ('ꝙ', &['Ꝙ']), ('Ꝛ', &['ꝛ']),
+  ('ꝛ', &['Ꝛ']), ('Ꝝ', &['ꝝ']), ('ꝝ', &['Ꝝ']), ('Ꝟ', &['ꝟ']),
+  ('ꝟ', &['Ꝟ']), ('Ꝡ', &['ꝡ']), ('ꝡ', &['Ꝡ']), ('Ꝣ', &['ꝣ']),
+  ('ꝣ', &['Ꝣ']), ('Ꝥ', &['ꝥ']), ('ꝥ', &['Ꝥ']), ('Ꝧ', &['ꝧ']),
+  ('ꝧ', &['Ꝧ']), ('Ꝩ', &['ꝩ']), ('ꝩ', &['Ꝩ']), ('Ꝫ', &['ꝫ']),
+  ('ꝫ', &['Ꝫ']), ('Ꝭ', &['ꝭ']), ('ꝭ', &['Ꝭ']), ('Ꝯ', &['ꝯ']),
+  ('ꝯ', &['Ꝯ']), ('Ꝺ', &['ꝺ']), ('ꝺ', &['Ꝺ']), ('Ꝼ', &['ꝼ']),
+  ('ꝼ', &['Ꝼ']), ('Ᵹ', &['ᵹ']), ('Ꝿ', &['ꝿ']), ('ꝿ', &['Ꝿ']),
+  ('Ꞁ', &['ꞁ']), ('ꞁ', &['Ꞁ']), ('Ꞃ', &['ꞃ']), ('ꞃ', &['Ꞃ']),
+  ('Ꞅ', &['ꞅ']), ('ꞅ', &['Ꞅ']), ('Ꞇ', &['ꞇ']), ('ꞇ', &['Ꞇ']),
+  ('Ꞌ', &['ꞌ']), ('ꞌ', &['Ꞌ']), ('Ɥ', &['ɥ']), ('Ꞑ', &['ꞑ']),
+  ('ꞑ', &['Ꞑ']), ('Ꞓ', &['ꞓ']), ('ꞓ', &['Ꞓ']), ('ꞔ', &['Ꞔ']),
+  ('Ꞗ', &['ꞗ']), ('ꞗ', &['Ꞗ']), ('Ꞙ', &['ꞙ']), ('ꞙ', &['Ꞙ']),
+  ('Ꞛ', &['ꞛ']), ('ꞛ', &['Ꞛ']), ('Ꞝ', &['ꞝ']), ('ꞝ', &['Ꞝ']),
+  ('Ꞟ', &['ꞟ']), ('ꞟ', &['Ꞟ']), ('Ꞡ', &['ꞡ']), ('ꞡ', &['Ꞡ']),
+  ('Ꞣ', &['ꞣ']), ('ꞣ', &['Ꞣ']), ('Ꞥ', &['ꞥ']), ('ꞥ', &['Ꞥ']),
+  ('Ꞧ', &['ꞧ']), ('ꞧ', &['Ꞧ']), ('Ꞩ', &['ꞩ']), ('ꞩ', &['Ꞩ']),
+  ('Ɦ', &['ɦ']), ('Ɜ', &['ɜ']), ('Ɡ', &['ɡ']), ('Ɬ', &['ɬ']),
+  ('Ɪ', &['ɪ']), ('Ʞ', &['ʞ']), ('Ʇ', &['ʇ']), ('Ʝ', &['ʝ']),
+  ('Ꭓ', &['ꭓ']), ('Ꞵ', &['ꞵ']), ('ꞵ', &['Ꞵ']), ('Ꞷ', &['ꞷ']),
+  ('ꞷ', &['Ꞷ']), ('Ꞹ', &['ꞹ']), ('ꞹ', &['Ꞹ']), ('Ꞻ', &['ꞻ']),
+  ('ꞻ', &['Ꞻ']), ('Ꞽ', &['ꞽ']), ('ꞽ', &['Ꞽ']), ('Ꞿ', &['ꞿ']),
+  ('ꞿ', &['Ꞿ']), ('Ꟁ', &['ꟁ']), ('ꟁ', &['Ꟁ']), ('Ꟃ', &['ꟃ']),
+  ('ꟃ', &['Ꟃ']), ('Ꞔ', &['ꞔ']), ('Ʂ', &['ʂ']), ('Ᶎ', &['ᶎ']),
+  ('Ꟈ', &['ꟈ']), ('ꟈ', &['Ꟈ']), ('Ꟊ', &['ꟊ']), ('ꟊ', &['Ꟊ']),
+  ('Ɤ', &['ɤ']), ('Ꟍ', &['ꟍ']), ('ꟍ', &['Ꟍ']), ('Ꟑ', &['ꟑ']),
+  ('ꟑ', &['Ꟑ']), ('Ꟗ', &['ꟗ']), ('ꟗ', &['Ꟗ']), ('Ꟙ', &['ꟙ']),
+  ('ꟙ', &['Ꟙ']), ('Ꟛ', &['ꟛ']), ('ꟛ', &['Ꟛ']), ('Ƛ', &['ƛ']),
+  ('Ꟶ', &['ꟶ']), ('ꟶ', &['Ꟶ']), ('ꭓ', &['Ꭓ']), ('ꭰ', &['Ꭰ']),
+  ('ꭱ', &['Ꭱ']), ('ꭲ', &['Ꭲ']), ('ꭳ', &['Ꭳ']), ('ꭴ', &['Ꭴ']),
+  ('ꭵ', &['Ꭵ']), ('ꭶ', &['Ꭶ']), ('ꭷ', &['Ꭷ']), ('ꭸ', &['Ꭸ']),
+  ('ꭹ', &['Ꭹ']), ('ꭺ', &['Ꭺ']), ('ꭻ', &['Ꭻ']), ('ꭼ', &['Ꭼ']),
+  ('ꭽ', &['Ꭽ']), ('ꭾ', &['Ꭾ']), ('ꭿ', &['Ꭿ']), ('ꮀ', &['Ꮀ']),
+  ('ꮁ', &['Ꮁ']), ('ꮂ', &['Ꮂ']), ('ꮃ', &['Ꮃ']), ('ꮄ', &['Ꮄ']),
+  ('ꮅ', &['Ꮅ']), ('ꮆ', &['Ꮆ']), ('ꮇ', &['Ꮇ']), ('ꮈ', &['Ꮈ']),
+  ('ꮉ', &['Ꮉ']), ('ꮊ', &['Ꮊ']), ('ꮋ', &['Ꮋ']), ('ꮌ', &['Ꮌ']),
+  ('ꮍ', &['Ꮍ']), ('ꮎ', &['Ꮎ']), ('ꮏ', &['Ꮏ']), ('ꮐ', &['Ꮐ']),
+  ('ꮑ', &['Ꮑ']), ('ꮒ', &['Ꮒ']), ('ꮓ', &['Ꮓ']), ('ꮔ', &['Ꮔ']),
+  ('ꮕ', &['Ꮕ']), ('ꮖ', &['Ꮖ']), ('ꮗ', &['Ꮗ']), ('ꮘ', &['Ꮘ']),
+  ('ꮙ', &['Ꮙ']), ('ꮚ', &['Ꮚ']), ('ꮛ', &['Ꮛ']), ('ꮜ', &['Ꮜ']),
+  ('ꮝ', &['Ꮝ']), ('ꮞ', &['Ꮞ']), ('ꮟ', &['Ꮟ']), ('ꮠ', &['Ꮠ']),
+  ('ꮡ', &['Ꮡ']), ('ꮢ', &['Ꮢ']), ('ꮣ', &['Ꮣ']), ('ꮤ', &['Ꮤ']),
+  ('ꮥ', &['Ꮥ']), ('ꮦ', &['Ꮦ']), ('ꮧ', &['Ꮧ']), ('ꮨ', &['Ꮨ']),
+  ('ꮩ', &['Ꮩ']), ('ꮪ', &['Ꮪ']), ('ꮫ', &['Ꮫ']), ('ꮬ', &['Ꮬ']),
+  ('ꮭ', &['Ꮭ']), ('ꮮ', &['Ꮮ']), ('ꮯ', &['Ꮯ']), ('ꮰ', &['Ꮰ']),
+  ('ꮱ', &['Ꮱ']), ('ꮲ', &['Ꮲ']), ('ꮳ', &['Ꮳ']), ('ꮴ', &['Ꮴ']),
+  ('ꮵ', &['Ꮵ']), ('ꮶ', &['Ꮶ']), ('ꮷ', &['Ꮷ']), ('ꮸ', &['Ꮸ']),
+  ('ꮹ', &['Ꮹ']), ('ꮺ', &['Ꮺ']), ('ꮻ', &['Ꮻ']), ('ꮼ', &['Ꮼ']),
+  ('ꮽ', &['Ꮽ']), ('ꮾ', &['Ꮾ']), ('ꮿ', &['Ꮿ']), ('ﬅ', &['ﬆ']),
+  ('ﬆ', &['ﬅ']), ('Ａ', &['ａ']), ('Ｂ', &['ｂ']), ('Ｃ', &['ｃ']),
+  ('Ｄ', &['ｄ']), ('Ｅ', &['ｅ']), ('Ｆ', &['ｆ']), ('Ｇ', &['ｇ']),
+  ('Ｈ', &['ｈ']), ('Ｉ', &['ｉ']), ('Ｊ', &['ｊ']), ('Ｋ', &['ｋ']),
+  ('Ｌ', &['ｌ']), ('Ｍ', &['ｍ']), ('Ｎ', &['ｎ']), ('Ｏ', &['ｏ']),
+  ('Ｐ', &['ｐ']), ('Ｑ', &['ｑ']), ('Ｒ', &['ｒ']), ('Ｓ', &['ｓ']),
+  ('Ｔ', &['ｔ']), ('Ｕ', &['ｕ']), ('Ｖ', &['ｖ']), ('Ｗ', &['ｗ']),
+  ('Ｘ', &['ｘ']), ('Ｙ', &['ｙ']), ('Ｚ', &['ｚ']), ('ａ', &['Ａ']),
+  ('ｂ', &['Ｂ']), ('ｃ', &['Ｃ']), ('ｄ', &['Ｄ']), ('ｅ', &['Ｅ']),
+  ('ｆ', &['Ｆ']), ('ｇ', &['Ｇ']), ('ｈ', &['Ｈ']), ('ｉ', &['Ｉ']),
+  ('ｊ', &['Ｊ']), ('ｋ', &['Ｋ']), ('ｌ', &['Ｌ']), ('ｍ', &['Ｍ']),
+  ('ｎ', &['Ｎ']), ('ｏ', &['Ｏ']), ('ｐ', &['Ｐ']), ('ｑ', &['Ｑ']),
+  ('ｒ', &['Ｒ']), ('ｓ', &['Ｓ']), ('ｔ', &['Ｔ']), ('ｕ', &['Ｕ']),
+  ('ｖ', &['Ｖ']), ('ｗ', &['Ｗ']), ('ｘ', &['Ｘ']), ('ｙ', &['Ｙ']),
+  ('ｚ', &['Ｚ']), ('𐐀', &['𐐨']), ('𐐁', &['𐐩']),
+  ('𐐂', &['𐐪']), ('𐐃', &['𐐫']), ('𐐄', &['𐐬']),
+  ('𐐅', &['𐐭']), ('𐐆', &['𐐮']), ('𐐇', &['𐐯']),
+  ('𐐈', &['𐐰']), ('𐐉', &['𐐱']), ('𐐊', &['𐐲']),
+  ('𐐋', &['𐐳']), ('𐐌', &['𐐴']), ('𐐍', &['𐐵']),
+  ('𐐎', &['𐐶']), ('𐐏', &['𐐷']), ('𐐐', &['𐐸']),
+  ('𐐑', &['𐐹']), ('𐐒', &['𐐺']), ('𐐓', &['𐐻']),
+  ('𐐔', &['𐐼']), ('𐐕', &['𐐽']), ('𐐖', &['𐐾']),
+  ('𐐗', &['𐐿']), ('𐐘', &['𐑀']), ('𐐙', &['𐑁']),
+  ('𐐚', &['𐑂']), ('𐐛', &['𐑃']), ('𐐜', &['𐑄']),
+  ('𐐝', &['𐑅']), ('𐐞', &['𐑆']), ('𐐟', &['𐑇']),
+  ('𐐠', &['𐑈']), ('𐐡', &['𐑉']), ('𐐢', &['𐑊']),
+  ('𐐣', &['𐑋']), ('𐐤', &['𐑌']), ('𐐥', &['𐑍']),
+  ('𐐦', &['𐑎']), ('𐐧', &['𐑏']), ('𐐨', &['𐐀']),
+  ('𐐩', &['𐐁']), ('𐐪', &['𐐂']), ('𐐫', &['𐐃']),
+  ('𐐬', &['𐐄']), ('𐐭', &['𐐅']), ('𐐮', &['𐐆']),
+  ('𐐯', &['𐐇']), ('𐐰', &['𐐈']), ('𐐱', &['𐐉']),
+  ('𐐲', &['𐐊']), ('𐐳', &['𐐋']), ('𐐴', &['𐐌']),
+  ('𐐵', &['𐐍']), ('𐐶', &['𐐎']), ('𐐷', &['𐐏']),
+  ('𐐸', &['𐐐']), ('𐐹', &['𐐑']), ('𐐺', &['𐐒']),
+  ('𐐻', &['𐐓']), ('𐐼', &['𐐔']), ('𐐽', &['𐐕']),
+  ('𐐾', &['𐐖']), ('𐐿', &['𐐗']), ('𐑀', &['𐐘']),
+  ('𐑁', &['𐐙']), ('𐑂', &['𐐚']), ('𐑃', &['𐐛']),
+  ('𐑄', &['𐐜']), ('𐑅', &['𐐝']), ('𐑆', &['𐐞']),
+  ('𐑇', &['𐐟']), ('𐑈', &['𐐠']), ('𐑉', &['𐐡']),
+  ('𐑊', &['𐐢']), ('𐑋', &['𐐣']), ('𐑌', &['𐐤']),
+  ('𐑍', &['𐐥']), ('𐑎', &['𐐦']), ('𐑏', &['𐐧']),
+  ('𐒰', &['𐓘']), ('𐒱', &['𐓙']), ('𐒲', &['𐓚']),
+  ('𐒳', &['𐓛']), ('𐒴', &['𐓜']), ('𐒵', &['𐓝']),
+  ('𐒶', &['𐓞']), ('𐒷', &['𐓟']), ('𐒸', &['𐓠']),
+  ('𐒹', &['𐓡']), ('𐒺', &['𐓢']), ('𐒻', &['𐓣']),
+  ('𐒼', &['𐓤']), ('𐒽', &['𐓥']), ('𐒾', &['𐓦']),
+  ('𐒿', &['𐓧']), ('𐓀', &['𐓨']), ('𐓁', &['𐓩']),
+  ('𐓂', &['𐓪']), ('𐓃', &['𐓫']), ('𐓄', &['𐓬']),
+  ('𐓅', &['𐓭']), ('𐓆', &['𐓮']), ('𐓇', &['𐓯']),
+  ('𐓈', &['𐓰']), ('𐓉', &['𐓱']), ('𐓊', &['𐓲']),
+  ('𐓋', &['𐓳']), ('𐓌', &['𐓴']), ('𐓍', &['𐓵']),
+  ('𐓎', &['𐓶']), ('𐓏', &['𐓷']), ('𐓐', &['𐓸']),
+  ('𐓑', &['𐓹']), ('𐓒', &['𐓺']), ('𐓓', &['𐓻']),
+  ('𐓘', &['𐒰']), ('𐓙', &['𐒱']), ('𐓚', &['𐒲']),
+  ('𐓛', &['𐒳']), ('𐓜', &['𐒴']), ('𐓝', &['𐒵']),
+  ('𐓞', &['𐒶']), ('𐓟', &['𐒷']), ('𐓠', &['𐒸']),
+  ('𐓡', &['𐒹']), ('𐓢', &['𐒺']), ('𐓣', &['𐒻']),
+  ('𐓤', &['𐒼']), ('𐓥', &['𐒽']), ('𐓦', &['𐒾']),
+  ('𐓧', &['𐒿']), ('𐓨', &['𐓀']), ('𐓩', &['𐓁']),
+  ('𐓪', &['𐓂']), ('𐓫', &['𐓃']), ('𐓬', &['𐓄']),
+  ('𐓭', &['𐓅']), ('𐓮', &['𐓆']), ('𐓯', &['𐓇']),
+  ('𐓰', &['𐓈']), ('𐓱', &['𐓉']), ('𐓲', &['𐓊']),
+  ('𐓳', &['𐓋']), ('𐓴', &['𐓌']), ('𐓵', &['𐓍']),
+  ('𐓶', &['𐓎']), ('𐓷', &['𐓏']), ('𐓸', &['𐓐']),
+  ('𐓹', &['𐓑']), ('𐓺', &['𐓒']), ('𐓻', &['𐓓']),
+  ('𐕰', &['𐖗']), ('𐕱', &['𐖘']), ('𐕲', &['𐖙']),
+  ('𐕳', &['𐖚']), ('𐕴', &['𐖛']), ('𐕵', &['𐖜']),
+  ('𐕶', &['𐖝']), ('𐕷', &['𐖞']), ('𐕸', &['𐖟']),
+  ('𐕹', &['𐖠']), ('𐕺', &['𐖡']), ('𐕼', &['𐖣']),
+  ('𐕽', &['𐖤']), ('𐕾', &['𐖥']), ('𐕿', &['𐖦']),
+  ('𐖀', &['𐖧']), ('𐖁', &['𐖨']), ('𐖂', &['𐖩']),
+  ('𐖃', &['𐖪']), ('𐖄', &['𐖫']), ('𐖅', &['𐖬']),
+  ('𐖆', &['𐖭']), ('𐖇', &['𐖮']), ('𐖈', &['𐖯']),
+  ('𐖉', &['𐖰']), ('𐖊', &['𐖱']), ('𐖌', &['𐖳']),
+  ('𐖍', &['𐖴']), ('𐖎', &['𐖵']), ('𐖏', &['𐖶']),
+  ('𐖐', &['𐖷']), ('𐖑', &['𐖸']), ('𐖒', &['𐖹']),
+  ('𐖔', &['𐖻']), ('𐖕', &['𐖼']), ('𐖗', &['𐕰']),
+  ('𐖘', &['𐕱']), ('𐖙', &['𐕲']), ('𐖚', &['𐕳']),
+  ('𐖛', &['𐕴']), ('𐖜', &['𐕵']), ('𐖝', &['𐕶']),
+  ('𐖞', &['𐕷']), ('𐖟', &['𐕸']), ('𐖠', &['𐕹']),
+  ('𐖡', &['𐕺']), ('𐖣', &['𐕼']), ('𐖤', &['𐕽']),
+  ('𐖥', &['𐕾']), ('𐖦', &['𐕿']), ('𐖧', &['𐖀']),
+  ('𐖨', &['𐖁']), ('𐖩', &['𐖂']), ('𐖪', &['𐖃']),
+  ('𐖫', &['𐖄']), ('𐖬', &['𐖅']), ('𐖭', &['𐖆']),
+  ('𐖮', &['𐖇']), ('𐖯', &['𐖈']), ('𐖰', &['𐖉']),
+  ('𐖱', &['𐖊']), ('𐖳', &['𐖌']), ('𐖴', &['𐖍']),
+  ('𐖵', &['𐖎']), ('𐖶', &['𐖏']), ('𐖷', &['𐖐']),
+  ('𐖸', &['𐖑']), ('𐖹', &['𐖒']), ('𐖻', &['𐖔']),
+  ('𐖼', &['𐖕']), ('𐲀', &['𐳀']), ('𐲁', &['𐳁']),
+  ('𐲂', &['𐳂']), ('𐲃', &['𐳃']), ('𐲄', &['𐳄']),
+  ('𐲅', &['𐳅']), ('𐲆', &['𐳆']), ('𐲇', &['𐳇']),
+  ('𐲈', &['𐳈']), ('𐲉', &['𐳉']), ('𐲊', &['𐳊']),
+  ('𐲋', &['𐳋']), ('𐲌', &['𐳌']), ('𐲍', &['𐳍']),
+  ('𐲎', &['𐳎']), ('𐲏', &['𐳏']), ('𐲐', &['𐳐']),
+  ('𐲑', &['𐳑']), ('𐲒', &['𐳒']), ('𐲓', &['𐳓']),
+  ('𐲔', &['𐳔']), ('𐲕', &['𐳕']), ('𐲖', &['𐳖']),
+  ('𐲗', &['𐳗']), ('𐲘', &['𐳘']), ('𐲙', &['𐳙']),
+  ('𐲚', &['𐳚']), ('𐲛', &['𐳛']), ('𐲜', &['𐳜']),
+  ('𐲝', &['𐳝']), ('𐲞', &['𐳞']), ('𐲟', &['𐳟']),
+  ('𐲠', &['𐳠']), ('𐲡', &['𐳡']), ('𐲢', &['𐳢']),
+  ('𐲣', &['𐳣']), ('𐲤', &['𐳤']), ('𐲥', &['𐳥']),
+  ('𐲦', &['𐳦']), ('𐲧', &['𐳧']), ('𐲨', &['𐳨']),
+  ('𐲩', &['𐳩']), ('𐲪', &['𐳪']), ('𐲫', &['𐳫']),
+  ('𐲬', &['𐳬']), ('𐲭', &['𐳭']), ('𐲮', &['𐳮']),
+  ('𐲯', &['𐳯']), ('𐲰', &['𐳰']), ('𐲱', &['𐳱']),
+  ('𐲲', &['𐳲']), ('𐳀', &['𐲀']), ('𐳁', &['𐲁']),
+  ('𐳂', &['𐲂']), ('𐳃', &['𐲃']), ('𐳄', &['𐲄']),
+  ('𐳅', &['𐲅']), ('𐳆', &['𐲆']), ('𐳇', &['𐲇']),
+  ('𐳈', &['𐲈']), ('𐳉', &['𐲉']), ('𐳊', &['𐲊']),
+  ('𐳋', &['𐲋']), ('𐳌', &['𐲌']), ('𐳍', &['𐲍']),
+  ('𐳎', &['𐲎']), ('𐳏', &['𐲏']), ('𐳐', &['𐲐']),
+  ('𐳑', &['𐲑']), ('𐳒', &['𐲒']), ('𐳓', &['𐲓']),
+  ('𐳔', &['𐲔']), ('𐳕', &['𐲕']), ('𐳖', &['𐲖']),
+  ('𐳗', &['𐲗']), ('𐳘', &['𐲘']), ('𐳙', &['𐲙']),
+  ('𐳚', &['𐲚']), ('𐳛', &['𐲛']), ('𐳜', &['𐲜']),
+  ('𐳝', &['𐲝']), ('𐳞', &['𐲞']), ('𐳟', &['𐲟']),
+  ('𐳠', &['𐲠']), ('𐳡', &['𐲡']), ('𐳢', &['𐲢']),
+  ('𐳣', &['𐲣']), ('𐳤', &['𐲤']), ('𐳥', &['𐲥']),
+  ('𐳦', &['𐲦']), ('𐳧', &['𐲧']), ('𐳨', &['𐲨']),
+  ('𐳩', &['𐲩']), ('𐳪', &['𐲪']), ('𐳫', &['𐲫']),
+  ('𐳬', &['𐲬']), ('𐳭', &['𐲭']), ('𐳮', &['𐲮']),
+  ('𐳯', &['𐲯']), ('𐳰', &['𐲰']), ('𐳱', &['𐲱']),
+  ('𐳲', &['𐲲']), ('𐵐', &['𐵰']), ('𐵑', &['𐵱']),
+  ('𐵒', &['𐵲']), ('𐵓', &['𐵳']), ('𐵔', &['𐵴']),
+  ('𐵕', &['𐵵']), ('𐵖', &['𐵶']), ('𐵗', &['𐵷']),
+  ('𐵘', &['𐵸']), ('𐵙', &['𐵹']), ('𐵚', &['𐵺']),
+  ('𐵛', &['𐵻']), ('𐵜', &['𐵼']), ('𐵝', &['𐵽']),
+  ('𐵞', &['𐵾']), ('𐵟', &['𐵿']), ('𐵠', &['𐶀']),
+  ('𐵡', &['𐶁']), ('𐵢', &['𐶂']), ('𐵣', &['𐶃']),
+  ('𐵤', &['𐶄']), ('𐵥', &['𐶅']), ('𐵰', &['𐵐']),
+  ('𐵱', &['𐵑']), ('𐵲', &['𐵒']), ('𐵳', &['𐵓']),
+  ('𐵴', &['𐵔']), ('𐵵', &['𐵕']), ('𐵶', &['𐵖']),
+  ('𐵷', &['𐵗']), ('𐵸', &['𐵘']), ('𐵹', &['𐵙']),
+  ('𐵺', &['𐵚']), ('𐵻', &['𐵛']), ('𐵼', &['𐵜']),
+  ('𐵽', &['𐵝']), ('𐵾', &['𐵞']), ('𐵿', &['𐵟']),
+  ('𐶀', &['𐵠']), ('𐶁', &['𐵡']), ('𐶂', &['𐵢']),
+  ('𐶃', &['𐵣']), ('𐶄', &['𐵤']), ('𐶅', &['𐵥']),
+  ('𑢠', &['𑣀']), ('𑢡', &['𑣁']), ('𑢢', &['𑣂']),
+  ('𑢣', &['𑣃']), ('𑢤', &['𑣄']), ('𑢥', &['𑣅']),
+  ('𑢦', &['𑣆']), ('𑢧', &['𑣇']), ('𑢨', &['𑣈']),
+  ('𑢩', &['𑣉']), ('𑢪', &['𑣊']), ('𑢫', &['𑣋']),
+  ('𑢬', &['𑣌']), ('𑢭', &['𑣍']), ('𑢮', &['𑣎']),
+  ('𑢯', &['𑣏']), ('𑢰', &['𑣐']), ('𑢱', &['𑣑']),
+  ('𑢲', &['𑣒']), ('𑢳', &['𑣓']), ('𑢴', &['𑣔']),
+  ('𑢵', &['𑣕']), ('𑢶', &['𑣖']), ('𑢷', &['𑣗']),
+  ('𑢸', &['𑣘']), ('𑢹', &['𑣙']), ('𑢺', &['𑣚']),
+  ('𑢻', &['𑣛']), ('𑢼', &['𑣜']), ('𑢽', &['𑣝']),
+  ('𑢾', &['𑣞']), ('𑢿', &['𑣟']), ('𑣀', &['𑢠']),
+  ('𑣁', &['𑢡']), ('𑣂', &['𑢢']), ('𑣃', &['𑢣']),
+  ('𑣄', &['𑢤']), ('𑣅', &['𑢥']), ('𑣆', &['𑢦']),
+  ('𑣇', &['𑢧']), ('𑣈', &['𑢨']), ('𑣉', &['𑢩']),
+  ('𑣊', &['𑢪']), ('𑣋', &['𑢫']), ('𑣌', &['𑢬']),
+  ('𑣍', &['𑢭']), ('𑣎', &['𑢮']), ('𑣏', &['𑢯']),
+  ('𑣐', &['𑢰']), ('𑣑', &['𑢱']), ('𑣒', &['𑢲']),
+  ('𑣓', &['𑢳']), ('𑣔', &['𑢴']), ('𑣕', &['𑢵']),
+  ('𑣖', &['𑢶']), ('𑣗', &['𑢷']), ('𑣘', &['𑢸']),
+  ('𑣙', &['𑢹']), ('𑣚', &['𑢺']), ('𑣛', &['𑢻']),
+  ('𑣜', &['𑢼']), ('𑣝', &['𑢽']), ('𑣞', &['𑢾']),
+  ('𑣟', &['𑢿']), ('𖹀', &['𖹠']), ('𖹁', &['𖹡']),
+  ('𖹂', &['𖹢']), ('𖹃', &['𖹣']), ('𖹄', &['𖹤']),
+  ('𖹅', &['𖹥']), ('𖹆', &['𖹦']), ('𖹇', &['𖹧']),
+  ('𖹈', &['𖹨']), ('𖹉', &['𖹩']), ('𖹊', &['𖹪']),
+  ('𖹋', &['𖹫']), ('𖹌', &['𖹬']), ('𖹍', &['𖹭']),
+  ('𖹎', &['𖹮']), ('𖹏', &['𖹯']), ('𖹐', &['𖹰']),
+  ('𖹑', &['𖹱']), ('𖹒', &['𖹲']), ('𖹓', &['𖹳']),
+  ('𖹔', &['𖹴']), ('𖹕', &['𖹵']), ('𖹖', &['𖹶']),
+  ('𖹗', &['𖹷']), ('𖹘', &['𖹸']), ('𖹙', &['𖹹']),
+  ('𖹚', &['𖹺']), ('𖹛', &['𖹻']), ('𖹜', &['𖹼']),
+  ('𖹝', &['𖹽']), ('𖹞', &['𖹾']), ('𖹟', &['𖹿']),
+  ('𖹠', &['𖹀']), ('𖹡', &['𖹁']), ('𖹢', &['𖹂']),
+  ('𖹣', &['𖹃']), ('𖹤', &['𖹄']), ('𖹥', &['𖹅']),
+  ('𖹦', &['𖹆']), ('𖹧', &['𖹇']), ('𖹨', &['𖹈']),
+  ('𖹩', &['𖹉']), ('𖹪', &['𖹊']), ('𖹫', &['𖹋']),
+  ('𖹬', &['𖹌']), ('𖹭', &['𖹍']), ('𖹮', &['𖹎']),
+  ('𖹯', &['𖹏']), ('𖹰', &['𖹐']), ('𖹱', &['𖹑']),
+  ('𖹲', &['𖹒']), ('𖹳', &['𖹓']), ('𖹴', &['𖹔']),
+  ('𖹵', &['𖹕']), ('𖹶', &['𖹖']), ('𖹷', &['𖹗']),
+  ('𖹸', &['𖹘']), ('𖹹', &['𖹙']), ('𖹺', &['𖹚']),
+  ('𖹻', &['𖹛']), ('𖹼', &['𖹜']), ('𖹽', &['𖹝']),
+  ('𖹾', &['𖹞']), ('𖹿', &['𖹟']), ('𞤀', &['𞤢']),
+  ('𞤁', &['𞤣']), ('𞤂', &['𞤤']), ('𞤃', &['𞤥']),
+  ('𞤄', &['𞤦']), ('𞤅', &['𞤧']), ('𞤆', &['𞤨']),
+  ('𞤇', &['𞤩']), ('𞤈', &['𞤪']), ('𞤉', &['𞤫']),
+  ('𞤊', &['𞤬']), ('𞤋', &['𞤭']), ('𞤌', &['𞤮']),
+  ('𞤍', &['𞤯']), ('𞤎', &['𞤰']), ('𞤏', &['𞤱']),
+  ('𞤐', &['𞤲']), ('𞤑', &['𞤳']), ('𞤒', &['𞤴']),
+  ('𞤓', &['𞤵']), ('𞤔', &['𞤶']), ('𞤕', &['𞤷']),
+  ('𞤖', &['𞤸']), ('𞤗', &['𞤹']), ('𞤘', &['𞤺']),
+  ('𞤙', &['𞤻']), ('𞤚', &['𞤼']), ('𞤛', &['𞤽']),
+  ('𞤜', &['𞤾']), ('𞤝', &['𞤿']), ('𞤞', &['𞥀']),
+  ('𞤟', &['𞥁']), ('𞤠', &['𞥂']), ('𞤡', &['𞥃']),
+  ('𞤢', &['𞤀']), ('𞤣', &['𞤁']), ('𞤤', &['𞤂']),
+  ('𞤥', &['𞤃']), ('𞤦', &['𞤄']), ('𞤧', &['𞤅']),
+  ('𞤨', &['𞤆']), ('𞤩', &['𞤇']), ('𞤪', &['𞤈']),
+  ('𞤫', &['𞤉']), ('𞤬', &['𞤊']), ('𞤭', &['𞤋']),
+  ('𞤮', &['𞤌']), ('𞤯', &['𞤍']), ('𞤰', &['𞤎']),
+  ('𞤱', &['𞤏']), ('𞤲', &['𞤐']), ('𞤳', &['𞤑']),
+  ('𞤴', &['𞤒']), ('𞤵', &['𞤓']), ('𞤶', &['𞤔']),
+  ('𞤷', &['𞤕']), ('𞤸', &['𞤖']), ('𞤹', &['𞤗']),
+  ('𞤺', &['𞤘']), ('𞤻', &['𞤙']), ('𞤼', &['𞤚']),
+  ('𞤽', &['𞤛']), ('𞤾', &['𞤜']), ('𞤿', &['𞤝']),
+  ('𞥀', &['𞤞']), ('𞥁', &['𞤟']), ('𞥂', &['𞤠']),
+  ('𞥃', &['𞤡']),
+];
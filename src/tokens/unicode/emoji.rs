@@ -0,0 +1,128 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   ucd-generate property-bool /tmp/ucd-16.0.0 --chars --include emoji,emojicomponent,emojimodifier,emojimodifierbase,emojipresentation,extendedpictographic
+//
+// Unicode version: 16.0.0.
+//
+// ucd-generate 0.3.0 is available on crates.io.
+
+pub const BY_NAME: &'static [(&'static str, &'static [(char, char)])] = &[
+  ("Emoji", EMOJI), ("Emoji_Component", EMOJI_COMPONENT),
+  ("Emoji_Modifier", EMOJI_MODIFIER),
+  ("Emoji_Modifier_Base", EMOJI_MODIFIER_BASE),
+  ("Emoji_Presentation", EMOJI_PRESENTATION),
+  ("Extended_Pictographic", EXTENDED_PICTOGRAPHIC),
+];
+
+pub const EMOJI: &'static [(char, char)] = &[
+  ('#', '#'), ('*', '*'), ('0', '9'), ('©', '©'), ('®', '®'),
+  ('‼', '‼'), ('⁉', '⁉'), ('™', '™'), ('ℹ', 'ℹ'),
+  ('↔', '↙'), ('↩', '↪'), ('⌚', '⌛'), ('⌨', '⌨'),
+  ('⏏', '⏏'), ('⏩', '⏳'), ('⏸', '⏺'), ('Ⓜ', 'Ⓜ'),
+  ('▪', '▫'), ('▶', '▶'), ('◀', '◀'), ('◻', '◾'),
+  ('☀', '☄'), ('☎', '☎'), ('☑', '☑'), ('☔', '☕'),
+  ('☘', '☘'), ('☝', '☝'), ('☠', '☠'), ('☢', '☣'),
+  ('☦', '☦'), ('☪', '☪'), ('☮', '☯'), ('☸', '☺'),
+  ('♀', '♀'), ('♂', '♂'), ('♈', '♓'), ('♟', '♠'),
+  ('♣', '♣'), ('♥', '♦'), ('♨', '♨'), ('♻', '♻'),
+  ('♾', '♿'), ('⚒', '⚗'), ('⚙', '⚙'), ('⚛', '⚜'),
+  ('⚠', '⚡'), ('⚧', '⚧'), ('⚪', '⚫'), ('⚰', '⚱'),
+  ('⚽', '⚾'), ('⛄', '⛅'), ('⛈', '⛈'), ('⛎', '⛏'),
+  ('⛑', '⛑'), ('⛓', '⛔'), ('⛩', '⛪'), ('⛰', '⛵'),
+  ('⛷', '⛺'), ('⛽', '⛽'), ('✂', '✂'), ('✅', '✅'),
+  ('✈', '✍'), ('✏', '✏'), ('✒', '✒'), ('✔', '✔'),
+  ('✖', '✖'), ('✝', '✝'), ('✡', '✡'), ('✨', '✨'),
+  ('✳', '✴'), ('❄', '❄'), ('❇', '❇'), ('❌', '❌'),
+  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('❣', '❤'),
+  ('➕', '➗'), ('➡', '➡'), ('➰', '➰'), ('➿', '➿'),
+  ('⤴', '⤵'), ('⬅', '⬇'), ('⬛', '⬜'), ('⭐', '⭐'),
+  ('⭕', '⭕'), ('〰', '〰'), ('〽', '〽'), ('㊗', '㊗'),
+  ('㊙', '㊙'), ('🀄', '🀄'), ('🃏', '🃏'), ('🅰', '🅱'),
+  ('🅾', '🅿'), ('🆎', '🆎'), ('🆑', '🆚'), ('🇦', '🇿'),
+  ('🈁', '🈂'), ('🈚', '🈚'), ('🈯', '🈯'), ('🈲', '🈺'),
+  ('🉐', '🉑'), ('🌀', '🌡'), ('🌤', '🎓'), ('🎖', '🎗'),
+  ('🎙', '🎛'), ('🎞', '🏰'), ('🏳', '🏵'), ('🏷', '📽'),
+  ('📿', '🔽'), ('🕉', '🕎'), ('🕐', '🕧'), ('🕯', '🕰'),
+  ('🕳', '🕺'), ('🖇', '🖇'), ('🖊', '🖍'), ('🖐', '🖐'),
+  ('🖕', '🖖'), ('🖤', '🖥'), ('🖨', '🖨'), ('🖱', '🖲'),
+  ('🖼', '🖼'), ('🗂', '🗄'), ('🗑', '🗓'), ('🗜', '🗞'),
+  ('🗡', '🗡'), ('🗣', '🗣'), ('🗨', '🗨'), ('🗯', '🗯'),
+  ('🗳', '🗳'), ('🗺', '🙏'), ('🚀', '🛅'), ('🛋', '🛒'),
+  ('🛕', '🛗'), ('🛜', '🛥'), ('🛩', '🛩'), ('🛫', '🛬'),
+  ('🛰', '🛰'), ('🛳', '🛼'), ('🟠', '🟫'), ('🟰', '🟰'),
+  ('🤌', '🤺'), ('🤼', '🥅'), ('🥇', '🧿'), ('🩰', '🩼'),
+  ('🪀', '\u{1fa89}'), ('\u{1fa8f}', '\u{1fac6}'), ('🫎', '\u{1fadc}'),
+  ('\u{1fadf}', '\u{1fae9}'), ('🫰', '🫸'),
+];
+
+pub const EMOJI_COMPONENT: &'static [(char, char)] = &[
+  ('#', '#'), ('*', '*'), ('0', '9'), ('\u{200d}', '\u{200d}'),
+  ('\u{20e3}', '\u{20e3}'), ('\u{fe0f}', '\u{fe0f}'), ('🇦', '🇿'),
+  ('🏻', '🏿'), ('🦰', '🦳'), ('\u{e0020}', '\u{e007f}'),
+];
+
+pub const EMOJI_MODIFIER: &'static [(char, char)] = &[
+  ('🏻', '🏿'),
+];
+
+pub const EMOJI_MODIFIER_BASE: &'static [(char, char)] = &[
+  ('☝', '☝'), ('⛹', '⛹'), ('✊', '✍'), ('🎅', '🎅'),
+  ('🏂', '🏄'), ('🏇', '🏇'), ('🏊', '🏌'), ('👂', '👃'),
+  ('👆', '👐'), ('👦', '👸'), ('👼', '👼'), ('💁', '💃'),
+  ('💅', '💇'), ('💏', '💏'), ('💑', '💑'), ('💪', '💪'),
+  ('🕴', '🕵'), ('🕺', '🕺'), ('🖐', '🖐'), ('🖕', '🖖'),
+  ('🙅', '🙇'), ('🙋', '🙏'), ('🚣', '🚣'), ('🚴', '🚶'),
+  ('🛀', '🛀'), ('🛌', '🛌'), ('🤌', '🤌'), ('🤏', '🤏'),
+  ('🤘', '🤟'), ('🤦', '🤦'), ('🤰', '🤹'), ('🤼', '🤾'),
+  ('🥷', '🥷'), ('🦵', '🦶'), ('🦸', '🦹'), ('🦻', '🦻'),
+  ('🧍', '🧏'), ('🧑', '🧝'), ('🫃', '🫅'), ('🫰', '🫸'),
+];
+
+pub const EMOJI_PRESENTATION: &'static [(char, char)] = &[
+  ('⌚', '⌛'), ('⏩', '⏬'), ('⏰', '⏰'), ('⏳', '⏳'),
+  ('◽', '◾'), ('☔', '☕'), ('♈', '♓'), ('♿', '♿'),
+  ('⚓', '⚓'), ('⚡', '⚡'), ('⚪', '⚫'), ('⚽', '⚾'),
+  ('⛄', '⛅'), ('⛎', '⛎'), ('⛔', '⛔'), ('⛪', '⛪'),
+  ('⛲', '⛳'), ('⛵', '⛵'), ('⛺', '⛺'), ('⛽', '⛽'),
+  ('✅', '✅'), ('✊', '✋'), ('✨', '✨'), ('❌', '❌'),
+  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('➕', '➗'),
+  ('➰', '➰'), ('➿', '➿'), ('⬛', '⬜'), ('⭐', '⭐'),
+  ('⭕', '⭕'), ('🀄', '🀄'), ('🃏', '🃏'), ('🆎', '🆎'),
+  ('🆑', '🆚'), ('🇦', '🇿'), ('🈁', '🈁'), ('🈚', '🈚'),
+  ('🈯', '🈯'), ('🈲', '🈶'), ('🈸', '🈺'), ('🉐', '🉑'),
+  ('🌀', '🌠'), ('🌭', '🌵'), ('🌷', '🍼'), ('🍾', '🎓'),
+  ('🎠', '🏊'), ('🏏', '🏓'), ('🏠', '🏰'), ('🏴', '🏴'),
+  ('🏸', '🐾'), ('👀', '👀'), ('👂', '📼'), ('📿', '🔽'),
+  ('🕋', '🕎'), ('🕐', '🕧'), ('🕺', '🕺'), ('🖕', '🖖'),
+  ('🖤', '🖤'), ('🗻', '🙏'), ('🚀', '🛅'), ('🛌', '🛌'),
+  ('🛐', '🛒'), ('🛕', '🛗'), ('🛜', '🛟'), ('🛫', '🛬'),
+  ('🛴', '🛼'), ('🟠', '🟫'), ('🟰', '🟰'), ('🤌', '🤺'),
+  ('🤼', '🥅'), ('🥇', '🧿'), ('🩰', '🩼'), ('🪀', '\u{1fa89}'),
+  ('\u{1fa8f}', '\u{1fac6}'), ('🫎', '\u{1fadc}'),
+  ('\u{1fadf}', '\u{1fae9}'), ('🫰', '🫸'),
+];
+
+pub const EXTENDED_PICTOGRAPHIC: &'static [(char, char)] = &[
+  ('©', '©'), ('®', '®'), ('‼', '‼'), ('⁉', '⁉'), ('™', '™'),
+  ('ℹ', 'ℹ'), ('↔', '↙'), ('↩', '↪'), ('⌚', '⌛'),
+  ('⌨', '⌨'), ('⎈', '⎈'), ('⏏', '⏏'), ('⏩', '⏳'),
+  ('⏸', '⏺'), ('Ⓜ', 'Ⓜ'), ('▪', '▫'), ('▶', '▶'),
+  ('◀', '◀'), ('◻', '◾'), ('☀', '★'), ('☇', '☒'),
+  ('☔', '⚅'), ('⚐', '✅'), ('✈', '✒'), ('✔', '✔'),
+  ('✖', '✖'), ('✝', '✝'), ('✡', '✡'), ('✨', '✨'),
+  ('✳', '✴'), ('❄', '❄'), ('❇', '❇'), ('❌', '❌'),
+  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('❣', '❧'),
+  ('➕', '➗'), ('➡', '➡'), ('➰', '➰'), ('➿', '➿'),
+  ('⤴', '⤵'), ('⬅', '⬇'), ('⬛', '⬜'), ('⭐', '⭐'),
+  ('⭕', '⭕'), ('〰', '〰'), ('〽', '〽'), ('㊗', '㊗'),
+  ('㊙', '㊙'), ('🀀', '\u{1f0ff}'), ('🄍', '🄏'), ('🄯', '🄯'),
+  ('🅬', '🅱'), ('🅾', '🅿'), ('🆎', '🆎'), ('🆑', '🆚'),
+  ('🆭', '\u{1f1e5}'), ('🈁', '\u{1f20f}'), ('🈚', '🈚'),
+  ('🈯', '🈯'), ('🈲', '🈺'), ('\u{1f23c}', '\u{1f23f}'),
+  ('\u{1f249}', '🏺'), ('🐀', '🔽'), ('🕆', '🙏'),
+  ('🚀', '\u{1f6ff}'), ('🝴', '🝿'), ('🟕', '\u{1f7ff}'),
+  ('\u{1f80c}', '\u{1f80f}'), ('\u{1f848}', '\u{1f84f}'),
+  ('\u{1f85a}', '\u{1f85f}'), ('\u{1f888}', '\u{1f88f}'),
+  ('\u{1f8ae}', '\u{1f8ff}'), ('🤌', '🤺'), ('🤼', '🥅'),
+  ('🥇', '\u{1faff}'), ('\u{1fc00}', '\u{1fffd}'),
+];
@@ -1,12 +1,30 @@
+#[cfg(feature = "unicode-blocks")]
+#[allow(clippy::all)]
+pub mod block;
+
+#[allow(clippy::all)]
+pub mod case_folding_simple;
+
+#[cfg(feature = "unicode-gencat")]
 #[allow(clippy::all)]
 pub mod general_category;
 
+#[cfg(feature = "unicode-props")]
 #[allow(clippy::all)]
 pub mod property_bool;
 
+#[cfg(feature = "unicode-scripts")]
 #[allow(clippy::all)]
 pub mod script;
 
+#[cfg(feature = "unicode-scripts")]
+#[allow(clippy::all)]
+pub mod script_extension;
+
+#[cfg(feature = "emoji")]
+#[allow(clippy::all)]
+pub mod emoji;
+
 #[allow(clippy::all)]
 pub mod perl_decimal;
 
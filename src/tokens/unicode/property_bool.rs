@@ -1,6 +1,6 @@
 // DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
 //
-//   ucd-generate property-bool /tmp/ucd-16.0.0 --chars
+//   ucd-generate property-bool /tmp/ucd-16.0.0 --chars --exclude emoji,emojicomponent,emojimodifier,emojimodifierbase,emojipresentation,extendedpictographic
 //
 // Unicode version: 16.0.0.
 //
@@ -16,14 +16,11 @@ pub const BY_NAME: &'static [(&'static str, &'static [(char, char)])] = &[
   ("Changes_When_Titlecased", CHANGES_WHEN_TITLECASED),
   ("Changes_When_Uppercased", CHANGES_WHEN_UPPERCASED), ("Dash", DASH),
   ("Default_Ignorable_Code_Point", DEFAULT_IGNORABLE_CODE_POINT),
-  ("Deprecated", DEPRECATED), ("Diacritic", DIACRITIC), ("Emoji", EMOJI),
-  ("Emoji_Component", EMOJI_COMPONENT), ("Emoji_Modifier", EMOJI_MODIFIER),
-  ("Emoji_Modifier_Base", EMOJI_MODIFIER_BASE),
-  ("Emoji_Presentation", EMOJI_PRESENTATION),
-  ("Extended_Pictographic", EXTENDED_PICTOGRAPHIC), ("Extender", EXTENDER),
-  ("Grapheme_Base", GRAPHEME_BASE), ("Grapheme_Extend", GRAPHEME_EXTEND),
-  ("Grapheme_Link", GRAPHEME_LINK), ("Hex_Digit", HEX_DIGIT),
-  ("Hyphen", HYPHEN), ("IDS_Binary_Operator", IDS_BINARY_OPERATOR),
+  ("Deprecated", DEPRECATED), ("Diacritic", DIACRITIC),
+  ("Extender", EXTENDER), ("Grapheme_Base", GRAPHEME_BASE),
+  ("Grapheme_Extend", GRAPHEME_EXTEND), ("Grapheme_Link", GRAPHEME_LINK),
+  ("Hex_Digit", HEX_DIGIT), ("Hyphen", HYPHEN),
+  ("IDS_Binary_Operator", IDS_BINARY_OPERATOR),
   ("IDS_Trinary_Operator", IDS_TRINARY_OPERATOR),
   ("IDS_Unary_Operator", IDS_UNARY_OPERATOR),
   ("ID_Compat_Math_Continue", ID_COMPAT_MATH_CONTINUE),
@@ -1254,119 +1251,6 @@ pub const DIACRITIC: &'static [(char, char)] = &[
   ('\u{1e944}', '\u{1e946}'), ('\u{1e948}', '\u{1e94a}'),
 ];
 
-pub const EMOJI: &'static [(char, char)] = &[
-  ('#', '#'), ('*', '*'), ('0', '9'), ('©', '©'), ('®', '®'),
-  ('‼', '‼'), ('⁉', '⁉'), ('™', '™'), ('ℹ', 'ℹ'),
-  ('↔', '↙'), ('↩', '↪'), ('⌚', '⌛'), ('⌨', '⌨'),
-  ('⏏', '⏏'), ('⏩', '⏳'), ('⏸', '⏺'), ('Ⓜ', 'Ⓜ'),
-  ('▪', '▫'), ('▶', '▶'), ('◀', '◀'), ('◻', '◾'),
-  ('☀', '☄'), ('☎', '☎'), ('☑', '☑'), ('☔', '☕'),
-  ('☘', '☘'), ('☝', '☝'), ('☠', '☠'), ('☢', '☣'),
-  ('☦', '☦'), ('☪', '☪'), ('☮', '☯'), ('☸', '☺'),
-  ('♀', '♀'), ('♂', '♂'), ('♈', '♓'), ('♟', '♠'),
-  ('♣', '♣'), ('♥', '♦'), ('♨', '♨'), ('♻', '♻'),
-  ('♾', '♿'), ('⚒', '⚗'), ('⚙', '⚙'), ('⚛', '⚜'),
-  ('⚠', '⚡'), ('⚧', '⚧'), ('⚪', '⚫'), ('⚰', '⚱'),
-  ('⚽', '⚾'), ('⛄', '⛅'), ('⛈', '⛈'), ('⛎', '⛏'),
-  ('⛑', '⛑'), ('⛓', '⛔'), ('⛩', '⛪'), ('⛰', '⛵'),
-  ('⛷', '⛺'), ('⛽', '⛽'), ('✂', '✂'), ('✅', '✅'),
-  ('✈', '✍'), ('✏', '✏'), ('✒', '✒'), ('✔', '✔'),
-  ('✖', '✖'), ('✝', '✝'), ('✡', '✡'), ('✨', '✨'),
-  ('✳', '✴'), ('❄', '❄'), ('❇', '❇'), ('❌', '❌'),
-  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('❣', '❤'),
-  ('➕', '➗'), ('➡', '➡'), ('➰', '➰'), ('➿', '➿'),
-  ('⤴', '⤵'), ('⬅', '⬇'), ('⬛', '⬜'), ('⭐', '⭐'),
-  ('⭕', '⭕'), ('〰', '〰'), ('〽', '〽'), ('㊗', '㊗'),
-  ('㊙', '㊙'), ('🀄', '🀄'), ('🃏', '🃏'), ('🅰', '🅱'),
-  ('🅾', '🅿'), ('🆎', '🆎'), ('🆑', '🆚'), ('🇦', '🇿'),
-  ('🈁', '🈂'), ('🈚', '🈚'), ('🈯', '🈯'), ('🈲', '🈺'),
-  ('🉐', '🉑'), ('🌀', '🌡'), ('🌤', '🎓'), ('🎖', '🎗'),
-  ('🎙', '🎛'), ('🎞', '🏰'), ('🏳', '🏵'), ('🏷', '📽'),
-  ('📿', '🔽'), ('🕉', '🕎'), ('🕐', '🕧'), ('🕯', '🕰'),
-  ('🕳', '🕺'), ('🖇', '🖇'), ('🖊', '🖍'), ('🖐', '🖐'),
-  ('🖕', '🖖'), ('🖤', '🖥'), ('🖨', '🖨'), ('🖱', '🖲'),
-  ('🖼', '🖼'), ('🗂', '🗄'), ('🗑', '🗓'), ('🗜', '🗞'),
-  ('🗡', '🗡'), ('🗣', '🗣'), ('🗨', '🗨'), ('🗯', '🗯'),
-  ('🗳', '🗳'), ('🗺', '🙏'), ('🚀', '🛅'), ('🛋', '🛒'),
-  ('🛕', '🛗'), ('🛜', '🛥'), ('🛩', '🛩'), ('🛫', '🛬'),
-  ('🛰', '🛰'), ('🛳', '🛼'), ('🟠', '🟫'), ('🟰', '🟰'),
-  ('🤌', '🤺'), ('🤼', '🥅'), ('🥇', '🧿'), ('🩰', '🩼'),
-  ('🪀', '\u{1fa89}'), ('\u{1fa8f}', '\u{1fac6}'), ('🫎', '\u{1fadc}'),
-  ('\u{1fadf}', '\u{1fae9}'), ('🫰', '🫸'),
-];
-
-pub const EMOJI_COMPONENT: &'static [(char, char)] = &[
-  ('#', '#'), ('*', '*'), ('0', '9'), ('\u{200d}', '\u{200d}'),
-  ('\u{20e3}', '\u{20e3}'), ('\u{fe0f}', '\u{fe0f}'), ('🇦', '🇿'),
-  ('🏻', '🏿'), ('🦰', '🦳'), ('\u{e0020}', '\u{e007f}'),
-];
-
-pub const EMOJI_MODIFIER: &'static [(char, char)] = &[
-  ('🏻', '🏿'),
-];
-
-pub const EMOJI_MODIFIER_BASE: &'static [(char, char)] = &[
-  ('☝', '☝'), ('⛹', '⛹'), ('✊', '✍'), ('🎅', '🎅'),
-  ('🏂', '🏄'), ('🏇', '🏇'), ('🏊', '🏌'), ('👂', '👃'),
-  ('👆', '👐'), ('👦', '👸'), ('👼', '👼'), ('💁', '💃'),
-  ('💅', '💇'), ('💏', '💏'), ('💑', '💑'), ('💪', '💪'),
-  ('🕴', '🕵'), ('🕺', '🕺'), ('🖐', '🖐'), ('🖕', '🖖'),
-  ('🙅', '🙇'), ('🙋', '🙏'), ('🚣', '🚣'), ('🚴', '🚶'),
-  ('🛀', '🛀'), ('🛌', '🛌'), ('🤌', '🤌'), ('🤏', '🤏'),
-  ('🤘', '🤟'), ('🤦', '🤦'), ('🤰', '🤹'), ('🤼', '🤾'),
-  ('🥷', '🥷'), ('🦵', '🦶'), ('🦸', '🦹'), ('🦻', '🦻'),
-  ('🧍', '🧏'), ('🧑', '🧝'), ('🫃', '🫅'), ('🫰', '🫸'),
-];
-
-pub const EMOJI_PRESENTATION: &'static [(char, char)] = &[
-  ('⌚', '⌛'), ('⏩', '⏬'), ('⏰', '⏰'), ('⏳', '⏳'),
-  ('◽', '◾'), ('☔', '☕'), ('♈', '♓'), ('♿', '♿'),
-  ('⚓', '⚓'), ('⚡', '⚡'), ('⚪', '⚫'), ('⚽', '⚾'),
-  ('⛄', '⛅'), ('⛎', '⛎'), ('⛔', '⛔'), ('⛪', '⛪'),
-  ('⛲', '⛳'), ('⛵', '⛵'), ('⛺', '⛺'), ('⛽', '⛽'),
-  ('✅', '✅'), ('✊', '✋'), ('✨', '✨'), ('❌', '❌'),
-  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('➕', '➗'),
-  ('➰', '➰'), ('➿', '➿'), ('⬛', '⬜'), ('⭐', '⭐'),
-  ('⭕', '⭕'), ('🀄', '🀄'), ('🃏', '🃏'), ('🆎', '🆎'),
-  ('🆑', '🆚'), ('🇦', '🇿'), ('🈁', '🈁'), ('🈚', '🈚'),
-  ('🈯', '🈯'), ('🈲', '🈶'), ('🈸', '🈺'), ('🉐', '🉑'),
-  ('🌀', '🌠'), ('🌭', '🌵'), ('🌷', '🍼'), ('🍾', '🎓'),
-  ('🎠', '🏊'), ('🏏', '🏓'), ('🏠', '🏰'), ('🏴', '🏴'),
-  ('🏸', '🐾'), ('👀', '👀'), ('👂', '📼'), ('📿', '🔽'),
-  ('🕋', '🕎'), ('🕐', '🕧'), ('🕺', '🕺'), ('🖕', '🖖'),
-  ('🖤', '🖤'), ('🗻', '🙏'), ('🚀', '🛅'), ('🛌', '🛌'),
-  ('🛐', '🛒'), ('🛕', '🛗'), ('🛜', '🛟'), ('🛫', '🛬'),
-  ('🛴', '🛼'), ('🟠', '🟫'), ('🟰', '🟰'), ('🤌', '🤺'),
-  ('🤼', '🥅'), ('🥇', '🧿'), ('🩰', '🩼'), ('🪀', '\u{1fa89}'),
-  ('\u{1fa8f}', '\u{1fac6}'), ('🫎', '\u{1fadc}'),
-  ('\u{1fadf}', '\u{1fae9}'), ('🫰', '🫸'),
-];
-
-pub const EXTENDED_PICTOGRAPHIC: &'static [(char, char)] = &[
-  ('©', '©'), ('®', '®'), ('‼', '‼'), ('⁉', '⁉'), ('™', '™'),
-  ('ℹ', 'ℹ'), ('↔', '↙'), ('↩', '↪'), ('⌚', '⌛'),
-  ('⌨', '⌨'), ('⎈', '⎈'), ('⏏', '⏏'), ('⏩', '⏳'),
-  ('⏸', '⏺'), ('Ⓜ', 'Ⓜ'), ('▪', '▫'), ('▶', '▶'),
-  ('◀', '◀'), ('◻', '◾'), ('☀', '★'), ('☇', '☒'),
-  ('☔', '⚅'), ('⚐', '✅'), ('✈', '✒'), ('✔', '✔'),
-  ('✖', '✖'), ('✝', '✝'), ('✡', '✡'), ('✨', '✨'),
-  ('✳', '✴'), ('❄', '❄'), ('❇', '❇'), ('❌', '❌'),
-  ('❎', '❎'), ('❓', '❕'), ('❗', '❗'), ('❣', '❧'),
-  ('➕', '➗'), ('➡', '➡'), ('➰', '➰'), ('➿', '➿'),
-  ('⤴', '⤵'), ('⬅', '⬇'), ('⬛', '⬜'), ('⭐', '⭐'),
-  ('⭕', '⭕'), ('〰', '〰'), ('〽', '〽'), ('㊗', '㊗'),
-  ('㊙', '㊙'), ('🀀', '\u{1f0ff}'), ('🄍', '🄏'), ('🄯', '🄯'),
-  ('🅬', '🅱'), ('🅾', '🅿'), ('🆎', '🆎'), ('🆑', '🆚'),
-  ('🆭', '\u{1f1e5}'), ('🈁', '\u{1f20f}'), ('🈚', '🈚'),
-  ('🈯', '🈯'), ('🈲', '🈺'), ('\u{1f23c}', '\u{1f23f}'),
-  ('\u{1f249}', '🏺'), ('🐀', '🔽'), ('🕆', '🙏'),
-  ('🚀', '\u{1f6ff}'), ('🝴', '🝿'), ('🟕', '\u{1f7ff}'),
-  ('\u{1f80c}', '\u{1f80f}'), ('\u{1f848}', '\u{1f84f}'),
-  ('\u{1f85a}', '\u{1f85f}'), ('\u{1f888}', '\u{1f88f}'),
-  ('\u{1f8ae}', '\u{1f8ff}'), ('🤌', '🤺'), ('🤼', '🥅'),
-  ('🥇', '\u{1faff}'), ('\u{1fc00}', '\u{1fffd}'),
-];
-
 pub const EXTENDER: &'static [(char, char)] = &[
   ('·', '·'), ('ː', 'ˑ'), ('ـ', 'ـ'), ('ߺ', 'ߺ'),
   ('\u{a71}', '\u{a71}'), ('\u{afb}', '\u{afb}'), ('\u{b55}', '\u{b55}'),
@@ -0,0 +1,329 @@
+#![cfg(feature = "regex")]
+
+use irange::{range::AnyRange, RangeSet};
+use regex::{bytes, Regex, RegexSet};
+use regex_charclass::{
+    char::Char,
+    options::{EmptyClassStyle, RegexFlavor, RegexOptions},
+    CharacterClass,
+};
+
+fn corpus() -> Vec<RangeSet<Char>> {
+    let mut corpus = vec![
+        RangeSet::empty(),
+        RangeSet::total(),
+        RangeSet::dot(),
+        RangeSet::new_from_range_char('a'..='z'),
+        RangeSet::new_from_range_char('\0'..='\0'),
+        RangeSet::new_from_range_char('\0'..='\u{1F}'),
+        RangeSet::new_from_range_char('\u{D7FF}'..='\u{E000}'),
+        RangeSet::new_from_range_char('\u{D7FF}'..='\u{D7FF}'),
+        RangeSet::new_from_range_char('\u{E000}'..='\u{E000}'),
+        RangeSet::new_from_range_char('\u{10000}'..='\u{1F64F}'),
+        RangeSet::new_from_range_char('\u{10FFFF}'..='\u{10FFFF}'),
+        RangeSet::new_from_ranges(&[
+            AnyRange::from(Char::new('0')..=Char::new('9')),
+            AnyRange::from(Char::new('A')..=Char::new('F')),
+            AnyRange::from(Char::new('\u{1F600}')..=Char::new('\u{1F64F}')),
+        ]),
+    ];
+
+    let metacharacters = "\\.+*?()|[]{}^$-&~#";
+    let all_metacharacters = metacharacters
+        .chars()
+        .map(|c| AnyRange::from(Char::new(c)..=Char::new(c)))
+        .collect::<Vec<_>>();
+    corpus.push(RangeSet::new_from_ranges(&all_metacharacters));
+    for c in metacharacters.chars() {
+        corpus.push(RangeSet::new_from_range_char(c..=c));
+    }
+
+    for regex in [
+        "\\d",
+        "\\w",
+        "\\s",
+        "\\p{Greek}",
+        "\\p{scx=Adlam}",
+        "\\p{Uppercase_Letter}",
+        "\\p{Cc}",
+        "\\p{Emoji}",
+        "[[:punct:]]",
+        "[\\p{Han}\\p{Latin}]",
+        "[\\w&&[^_]]",
+    ] {
+        corpus.push(RangeSet::from_regex(regex).unwrap());
+    }
+
+    let complements = corpus
+        .iter()
+        .map(|range| range.complement())
+        .collect::<Vec<_>>();
+    corpus.extend(complements);
+    corpus
+}
+
+/// Assert that `regex` matches exactly the `chars` that `range` contains.
+fn assert_same_members(regex: &Regex, range: &RangeSet<Char>, chars: &[char]) {
+    for &c in chars {
+        assert_eq!(
+            range.contains(Char::new(c)),
+            regex.is_match(&c.to_string()),
+            "{:?} on {:?}",
+            regex.as_str(),
+            c
+        );
+    }
+}
+
+/// Assert that `regex` matches the UTF-8 encoding of exactly the `chars` that `range` contains.
+fn assert_same_members_bytes(regex: &bytes::Regex, range: &RangeSet<Char>, chars: &[char]) {
+    for &c in chars {
+        assert_eq!(
+            range.contains(Char::new(c)),
+            regex.is_match(c.to_string().as_bytes()),
+            "{:?} on {:?}",
+            regex.as_str(),
+            c
+        );
+    }
+}
+
+#[test]
+fn test_corpus_compiles() -> Result<(), String> {
+    let options = RegexOptions {
+        flavor: RegexFlavor::Rust,
+        empty_as: EmptyClassStyle::NegatedTotal,
+        ..Default::default()
+    };
+
+    for range in corpus() {
+        let regex = range.to_regex_with(&options);
+        Regex::new(&regex).map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_corpus_re2_matches() -> Result<(), String> {
+    // The syntax written for RE2 is a subset of the one of the `regex` crate.
+    let options = RegexOptions {
+        flavor: RegexFlavor::Re2,
+        empty_as: EmptyClassStyle::NegatedTotal,
+        ..Default::default()
+    };
+
+    for range in corpus() {
+        let regex = range.to_regex_with(&options);
+        let compiled = Regex::new(&format!("^{}$", regex))
+            .map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+        assert_same_members(
+            &compiled,
+            &range,
+            &['\0', '\n', '-', '^', ']', 'a', '\u{E000}', '\u{10FFFF}'],
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_corpus_bytes_matches() -> Result<(), String> {
+    let options = RegexOptions {
+        flavor: RegexFlavor::RustBytes,
+        empty_as: EmptyClassStyle::NegatedTotal,
+        ..Default::default()
+    };
+
+    for range in corpus() {
+        let regex = range.to_regex_with(&options);
+        // The classes must keep matching UTF-8 encodings with Unicode mode disabled around them.
+        let compiled = bytes::Regex::new(&format!("(?-u)^{}$", regex))
+            .map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+        assert_same_members_bytes(
+            &compiled,
+            &range,
+            &[
+                '\0',
+                '\n',
+                '-',
+                ']',
+                'a',
+                '\u{80}',
+                '\u{D7FF}',
+                '\u{E000}',
+                '\u{1F600}',
+                '\u{10FFFF}',
+            ],
+        );
+        for input in [&b"\xFF"[..], b"\xC3", b"\xED\xA0\x80"] {
+            assert!(!compiled.is_match(input), "{:?} on {:?}", regex, input);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_regex_delta_matches() -> Result<(), String> {
+    let options = RegexOptions {
+        use_set_ops: true,
+        ..Default::default()
+    };
+
+    let base = RangeSet::new_from_range_char('a'..='z');
+    for (added, removed) in [('&', 'q'), ('~', 'q'), ('-', 'a'), ('.', 'z')] {
+        let range = base
+            .union(&RangeSet::new_from_range_char(added..=added))
+            .difference(&RangeSet::new_from_range_char(removed..=removed));
+        let regex = range.to_regex_delta(&base, &options);
+        let compiled = Regex::new(&format!("^{}$", regex))
+            .map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+        assert_same_members(
+            &compiled,
+            &range,
+            &['a', 'q', 'z', '&', '~', '-', '.', '\n'],
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ecmascript_total() -> Result<(), String> {
+    let options = RegexOptions {
+        flavor: RegexFlavor::EcmaScript,
+        ..Default::default()
+    };
+
+    let regex = RangeSet::<Char>::total().to_regex_with(&options);
+    assert_eq!("[\\s\\S]", regex);
+    let compiled = Regex::new(&format!("^{}$", regex)).map_err(|err| err.to_string())?;
+    assert_same_members(
+        &compiled,
+        &RangeSet::total(),
+        &['\0', '\n', '\r', 'a', '\u{2028}', '\u{FFFF}'],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_corpus_always_bracket() -> Result<(), String> {
+    let options = RegexOptions {
+        always_bracket: true,
+        empty_as: EmptyClassStyle::NegatedTotal,
+        ..Default::default()
+    };
+
+    for range in corpus() {
+        let regex = range.to_regex_with(&options);
+        assert!(
+            regex.starts_with('[') && regex.ends_with(']'),
+            "{:?}",
+            regex
+        );
+        let compiled = Regex::new(&format!("^{}$", regex))
+            .map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+        assert_same_members(
+            &compiled,
+            &range,
+            &['\0', '\n', '-', '^', ']', 'a', '\u{10FFFF}'],
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_regex_set_patterns() -> Result<(), String> {
+    let sets = [
+        RangeSet::new_from_range_char('a'..='z'),
+        RangeSet::from_regex("\\d").unwrap(),
+        RangeSet::from_regex("\\p{Greek}").unwrap(),
+        RangeSet::new_from_range_char('.'..='.'),
+        RangeSet::empty(),
+        RangeSet::total(),
+        RangeSet::dot(),
+    ];
+
+    let patterns = RangeSet::to_regex_set_patterns(&sets);
+    assert_eq!(sets.len(), patterns.len());
+    let regex_set = RegexSet::new(&patterns).map_err(|err| err.to_string())?;
+    for input in ["a", "7", "α", ".", "", "é1", "\n"] {
+        let expected = sets
+            .iter()
+            .enumerate()
+            .filter(|(_, set)| input.chars().any(|c| set.contains(Char::new(c))))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expected,
+            regex_set.matches(input).into_iter().collect::<Vec<_>>(),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_corpus_anchored() -> Result<(), String> {
+    for range in corpus() {
+        let regex = range.to_anchored_regex();
+        let compiled =
+            Regex::new(&regex).map_err(|err| format!("{:?} failed to compile: {}", regex, err))?;
+        assert_same_members(
+            &compiled,
+            &range,
+            &[
+                '\0',
+                '\n',
+                '-',
+                '^',
+                '$',
+                ']',
+                'a',
+                '\u{E000}',
+                '\u{10FFFF}',
+            ],
+        );
+        for input in ["", "aa", "a\n", "\n\n", "--"] {
+            assert!(!compiled.is_match(input), "{:?} on {:?}", regex, input);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_corpus_matches() -> Result<(), String> {
+    let options = RegexOptions {
+        empty_as: EmptyClassStyle::NegatedTotal,
+        ..Default::default()
+    };
+
+    for range in corpus() {
+        let regex = range.to_regex_with(&options);
+        let compiled = Regex::new(&format!("^{}$", regex)).unwrap();
+        assert_same_members(
+            &compiled,
+            &range,
+            &[
+                '\0',
+                '\n',
+                '-',
+                '^',
+                '$',
+                ']',
+                'a',
+                '\u{D7FF}',
+                '\u{E000}',
+                '\u{10FFFF}',
+            ],
+        );
+    }
+
+    Ok(())
+}